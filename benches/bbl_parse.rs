@@ -0,0 +1,66 @@
+use bibextract::latex::Bibliography;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::fs;
+use std::path::Path;
+
+/// A natbib bibliography of `count` items with journal, proceedings and preprint entries.
+/// Every third heading is broken over two lines, as BibTeX does for long labels.
+fn synthetic_bbl(count: usize, natbib: bool) -> String {
+    let mut bbl = String::from("\\begin{thebibliography}{9999}\n");
+    if natbib {
+        bbl.push_str("\\providecommand{\\natexlab}[1]{#1}\n\\providecommand{\\url}[1]{\\texttt{#1}}\n");
+    }
+    for i in 0..count {
+        let year = 1990 + i % 35;
+        let label = if natbib {
+            format!("Author{} et~al.({})Author{}, Second, and Third", i, year, i)
+        } else {
+            (i + 1).to_string()
+        };
+        let break_heading = if i % 3 == 0 { "%\n  " } else { "" };
+        bbl.push_str(&format!("\n\\bibitem[{}]{}{{key{}}}\n", label, break_heading, i));
+        bbl.push_str(&format!("Ann Author{}, Bob Second, and Carl~D. Third.\n", i));
+        match i % 3 {
+            0 => bbl.push_str(&format!(
+                "\\newblock A study of item {} in large bibliographies.\n\\newblock \\emph{{Journal of Synthetic Results}}, {}({}):{}--{}, {}.\n",
+                i, i % 50, i % 12, i, i + 10, year
+            )),
+            1 => bbl.push_str(&format!(
+                "\\newblock Learning to parse reference {}.\n\\newblock In \\emph{{Proceedings of the Conference on Benchmarks}}, pages {}--{}. Curran Associates, Inc., {}.\n",
+                i, i, i + 12, year
+            )),
+            _ => bbl.push_str(&format!(
+                "\\newblock Preprint number {}.\n\\newblock \\emph{{arXiv preprint arXiv:{}.{:05}}}, {}.\n",
+                i, 2001 + i % 20, i, year
+            )),
+        }
+    }
+    bbl.push_str("\n\\end{thebibliography}\n");
+    bbl
+}
+
+fn bench_fixtures(c: &mut Criterion) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let mut group = c.benchmark_group("fixtures");
+    for fixture in ["1.bbl", "2.bbl", "3.bbl", "4.bbl", "5.bbl"] {
+        let content = fs::read_to_string(fixtures.join(fixture)).expect("Failed to read fixture");
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(fixture, |b| b.iter(|| Bibliography::parse_bbl(black_box(&content)).unwrap()));
+    }
+    group.finish();
+}
+
+fn bench_synthetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("synthetic");
+    group.sample_size(10);
+    for (name, natbib) in [("natbib_5000", true), ("generic_5000", false)] {
+        let content = synthetic_bbl(5000, natbib);
+        assert_eq!(Bibliography::parse_bbl(&content).unwrap().entries.len(), 5000);
+        group.throughput(Throughput::Elements(5000));
+        group.bench_function(name, |b| b.iter(|| Bibliography::parse_bbl(black_box(&content)).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fixtures, bench_synthetic);
+criterion_main!(benches);
@@ -5,8 +5,8 @@ bibextract - A Python package for extracting survey content and bibliography fro
 from importlib.metadata import version
 
 # Import the Rust-implemented function directly.
-from .bibextract import extract_survey
+from .bibextract import crawl_survey, extract_survey
 
 __version__ = version("bibextract")
 
-__all__ = ["extract_survey"]
+__all__ = ["crawl_survey", "extract_survey"]
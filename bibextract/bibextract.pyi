@@ -1,4 +1,4 @@
-from typing import List, TypedDict
+from typing import List, Optional, TypedDict
 
 class ExtractResult(TypedDict):
     """
@@ -25,3 +25,31 @@ def extract_survey(paper_ids: List[str]) -> ExtractResult:
                       missing files, or parsing failures.
     """
     ...
+
+def crawl_survey(
+    paper_ids: List[str],
+    max_depth: int = 1,
+    max_papers: int = 20,
+    categories: Optional[List[str]] = None,
+    min_year: Optional[int] = None,
+    max_year: Optional[int] = None,
+) -> ExtractResult:
+    """
+    Crawls arXiv papers starting from seed IDs, following the arXiv IDs cited in their
+    related work sections, and merges all visited papers into a single survey.
+
+    Args:
+        paper_ids: The seed arXiv paper IDs.
+        max_depth: How many citation hops to follow from the seeds.
+        max_papers: Maximum number of papers to download, seeds included.
+        categories: Only follow papers in these arXiv categories (e.g. ['cs.CL']).
+        min_year: Only follow papers submitted in or after this year.
+        max_year: Only follow papers submitted in or before this year.
+
+    Returns:
+        A dictionary with the same keys as `extract_survey`.
+
+    Raises:
+        RuntimeError: If a seed paper cannot be processed.
+    """
+    ...
@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::latex::http::HTTP_CLIENT;
+
+use crate::error::BibExtractError;
+use crate::internal::fetch_paper_with;
+use crate::latex::{ArxivPaper, Verifier};
+
+
+// New-style arXiv identifiers (YYMM.NNNNN), the only ones the crawl follows
+static NEW_STYLE_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([0-9]{2})([0-9]{2})\.[0-9]{4,5}$").expect("Invalid new-style arXiv ID regex pattern")
+});
+static CATEGORY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<category[^>]*term="([^"]+)""#).expect("Invalid arXiv category regex pattern")
+});
+
+/// Options controlling a recursive crawl over arXiv references
+#[derive(Debug, Clone)]
+pub struct CrawlOptions {
+    /// arXiv IDs to start the crawl from
+    pub seed_ids: Vec<String>,
+    /// How many citation hops to follow from the seeds (0 only processes the seeds)
+    pub max_depth: usize,
+    /// Maximum number of papers to download, seeds included
+    pub max_papers: usize,
+    /// Only follow papers with a category starting with one of these (e.g. "cs" or "cs.CL")
+    pub categories: Vec<String>,
+    /// Only follow papers submitted in or after this year
+    pub min_year: Option<u32>,
+    /// Only follow papers submitted in or before this year
+    pub max_year: Option<u32>,
+    /// Verifies the bibliography of every downloaded paper
+    pub verifier: Verifier,
+}
+
+impl CrawlOptions {
+    /// Create crawl options with a depth of 1, a budget of 20 papers and the default verifier
+    pub fn new(seed_ids: Vec<String>) -> Self {
+        Self {
+            seed_ids,
+            max_depth: 1,
+            max_papers: 20,
+            categories: Vec::new(),
+            min_year: None,
+            max_year: None,
+            verifier: Verifier::default(),
+        }
+    }
+}
+
+/// Submission year encoded in a new-style arXiv ID (e.g. 2104.08653 -> 2021)
+pub fn arxiv_id_year(arxiv_id: &str) -> Option<u32> {
+    let captures = NEW_STYLE_ID_REGEX.captures(arxiv_id)?;
+    let year: u32 = captures.get(1)?.as_str().parse().ok()?;
+    Some(2000 + year)
+}
+
+/// Fetch the categories of an arXiv paper from the arXiv export API
+pub async fn fetch_arxiv_categories(arxiv_id: &str) -> Result<Vec<String>, BibExtractError> {
+    // Support configurable base URL for testing
+    let base_url = std::env::var("ARXIV_API_BASE_URL").unwrap_or_else(|_| "https://export.arxiv.org".to_string());
+    let url = format!("{}/api/query?id_list={}", base_url, arxiv_id);
+
+    let response = HTTP_CLIENT.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(BibExtractError::ApiError(format!("arXiv API returned status {}", response.status())));
+    }
+    let body = response.text().await?;
+
+    Ok(CATEGORY_REGEX
+        .captures_iter(&body)
+        .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+        .collect())
+}
+
+/// Collect the arXiv IDs of works cited inside the extracted sections of a paper
+pub fn cited_arxiv_ids(paper: &ArxivPaper) -> Vec<String> {
+    let mut ids = Vec::new();
+    for section in &paper.sections {
+        for key in &section.citations {
+            if let Some(arxiv_id) = paper.bibliography.get(key).and_then(|entry| paper.bibliography.extract_arxiv_id(entry)) {
+                if !ids.contains(&arxiv_id) {
+                    ids.push(arxiv_id);
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Check a candidate arXiv ID against the year and category filters
+async fn passes_filters(arxiv_id: &str, options: &CrawlOptions) -> bool {
+    if !NEW_STYLE_ID_REGEX.is_match(arxiv_id) {
+        return false;
+    }
+
+    if options.min_year.is_some() || options.max_year.is_some() {
+        let year = match arxiv_id_year(arxiv_id) {
+            Some(year) => year,
+            None => return false,
+        };
+        if options.min_year.is_some_and(|min| year < min) || options.max_year.is_some_and(|max| year > max) {
+            return false;
+        }
+    }
+
+    if !options.categories.is_empty() {
+        match fetch_arxiv_categories(arxiv_id).await {
+            Ok(categories) => {
+                let matches = categories
+                    .iter()
+                    .any(|category| options.categories.iter().any(|wanted| category.starts_with(wanted.as_str())));
+                if !matches {
+                    return false;
+                }
+            }
+            Err(e) => {
+                warn!("Could not fetch categories for {}: {}", arxiv_id, e);
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Crawl arXiv papers breadth-first, following arXiv IDs cited in their related work sections.
+///
+/// Seed papers that fail to download are reported as errors; papers discovered during the crawl
+/// are skipped with a warning instead.
+pub async fn crawl(options: &CrawlOptions) -> Result<Vec<ArxivPaper>, BibExtractError> {
+    if options.seed_ids.is_empty() {
+        return Err(BibExtractError::NoPaperIdsProvided);
+    }
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut papers = Vec::new();
+    let mut frontier: Vec<String> = Vec::new();
+    for seed in &options.seed_ids {
+        if visited.insert(seed.clone()) {
+            frontier.push(seed.clone());
+        }
+    }
+
+    let mut depth = 0;
+    while !frontier.is_empty() && papers.len() < options.max_papers {
+        // Never download more papers than the remaining budget allows
+        frontier.truncate(options.max_papers - papers.len());
+        info!("Crawling depth {} with {} papers", depth, frontier.len());
+
+        // Process the current level concurrently using tokio tasks
+        let tasks: Vec<_> = frontier
+            .drain(..)
+            .map(|paper_id| (paper_id.clone(), tokio::spawn(fetch_paper_with(paper_id, options.verifier.clone()))))
+            .collect();
+
+        let mut level_papers = Vec::new();
+        for (paper_id, task) in tasks {
+            let result = task.await.map_err(|e| BibExtractError::ApiError(format!("Task join error: {}", e)))?;
+            match result {
+                Ok(paper) => level_papers.push(paper),
+                Err(e) if depth == 0 => return Err(e),
+                Err(e) => warn!("Skipping paper {} during crawl: {}", paper_id, e),
+            }
+        }
+
+        if depth < options.max_depth {
+            for paper in &level_papers {
+                for arxiv_id in cited_arxiv_ids(paper) {
+                    if visited.contains(&arxiv_id) {
+                        continue;
+                    }
+                    visited.insert(arxiv_id.clone());
+                    if passes_filters(&arxiv_id, options).await {
+                        frontier.push(arxiv_id);
+                    }
+                }
+            }
+        }
+
+        papers.extend(level_papers);
+        depth += 1;
+    }
+
+    info!("Crawl finished with {} papers", papers.len());
+    Ok(papers)
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use log::info;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::error::BibExtractError;
+use crate::latex::bibtex::parse_bibtex;
+use crate::latex::local_index::TitleIndex;
+use crate::latex::similarity::{author_similarity, MatchThresholds};
+use crate::latex::providers::{match_score, Candidate, MetadataProvider};
+use crate::latex::BibEntry;
+
+/// Anthology IDs in Anthology URLs and ACL DOIs: old-style `N19-1423` or `2020.acl-main.1`
+static ANTHOLOGY_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:aclanthology\.org/|aclweb\.org/anthology/|10\.18653/v1/)([A-Z][0-9]{2}-[0-9]{4}|[0-9]{4}\.[a-z0-9]+(?:-[a-z0-9]+)*\.[0-9]+)")
+        .expect("Invalid Anthology ID regex pattern")
+});
+
+/// The Anthology ID an entry links to through its URL or DOI, e.g. `N19-1423`
+pub fn anthology_id(entry: &BibEntry) -> Option<String> {
+    ["url", "doi", "note", "raw"]
+        .iter()
+        .filter_map(|field| entry.get(field))
+        .find_map(|content| ANTHOLOGY_ID_REGEX.captures(content))
+        .map(|captures| captures[1].to_string())
+}
+
+/// An index over the ACL Anthology's BibTeX export, by Anthology ID, title and author
+#[derive(Debug, Default)]
+pub struct AnthologyIndex {
+    entries: Vec<BibEntry>,
+    by_id: HashMap<String, usize>,
+    titles: TitleIndex,
+}
+
+impl AnthologyIndex {
+    pub fn from_entries(entries: Vec<BibEntry>) -> Self {
+        let mut index = AnthologyIndex::default();
+        for entry in entries {
+            index.insert(entry);
+        }
+        index
+    }
+
+    fn insert(&mut self, mut entry: BibEntry) {
+        let Some(title) = entry.get("title").cloned() else {
+            return;
+        };
+        // anthology+abstracts.bib carries abstracts, which do not belong in a bibliography
+        entry.fields.remove("abstract");
+        let position = self.entries.len();
+        if let Some(id) = anthology_id(&entry) {
+            self.by_id.insert(id, position);
+        }
+        let authors = entry.authors();
+        self.titles.insert(&title, authors.persons.iter().map(|person| person.last.as_str()));
+        self.entries.push(entry);
+    }
+
+    /// Load `anthology.bib` or `anthology+abstracts.bib`, gzip-compressed if the name ends in `.gz`
+    pub fn from_bib(path: &Path) -> Result<Self, BibExtractError> {
+        info!("Indexing ACL Anthology {:?}", path);
+        let mut file = File::open(path)?;
+        let mut content = String::new();
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            GzDecoder::new(file).read_to_string(&mut content)?;
+        } else {
+            file.read_to_string(&mut content)?;
+        }
+        let index = Self::from_entries(parse_bibtex(&content));
+        info!("Indexed {} Anthology entries", index.len());
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entry of a paper by its Anthology ID
+    pub fn get(&self, id: &str) -> Option<&BibEntry> {
+        self.by_id.get(id).map(|&position| &self.entries[position])
+    }
+
+    /// Entries that may be the entry: those with its title, and those by its first author
+    /// with a similar title
+    pub fn candidates(&self, entry: &BibEntry) -> Vec<&BibEntry> {
+        self.titles
+            .candidates(entry)
+            .into_iter()
+            .map(|position| &self.entries[position])
+            .collect()
+    }
+}
+
+/// Verifies entries against a local copy of the ACL Anthology's BibTeX export, which has
+/// more complete records of ACL venues than DBLP. Entries linking to the Anthology are
+/// looked up by their ID, the others by title among papers sharing an author. Matches are
+/// the Anthology's own entries, with its URL and DOI.
+#[derive(Debug, Clone)]
+pub struct AclAnthologyProvider {
+    index: Arc<AnthologyIndex>,
+}
+
+impl AclAnthologyProvider {
+    pub fn new(index: Arc<AnthologyIndex>) -> Self {
+        Self { index }
+    }
+
+    /// Load an Anthology BibTeX file (see `AnthologyIndex::from_bib`)
+    pub fn open(path: &Path) -> Result<Self, BibExtractError> {
+        Ok(Self::new(Arc::new(AnthologyIndex::from_bib(path)?)))
+    }
+
+    /// The Anthology's entry under the key of the entry it verifies
+    fn rekeyed(anthology_entry: &BibEntry, entry: &BibEntry) -> BibEntry {
+        let mut verified = anthology_entry.clone();
+        verified.key = entry.key.clone();
+        verified
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for AclAnthologyProvider {
+    fn name(&self) -> &str {
+        "ACL Anthology"
+    }
+
+    async fn lookup_id(&self, entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        Ok(anthology_id(entry)
+            .and_then(|id| self.index.get(&id))
+            .map(|found| Candidate::new(Self::rekeyed(found, entry), 1.0, self.name())))
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let authors = entry.authors();
+        let mut candidates: Vec<Candidate> = self
+            .index
+            .candidates(entry)
+            .into_iter()
+            .filter(|found| {
+                // A paper with the same title by other people is another paper
+                authors.is_empty() || author_similarity(&authors.persons, &found.authors().persons) > 0.0
+            })
+            .map(|found| {
+                let verified = Self::rekeyed(found, entry);
+                let score = match_score(entry, &verified);
+                Candidate::new(verified, score, self.name())
+            })
+            .filter(|candidate| candidate.score >= MatchThresholds::default().min_score)
+            .collect();
+        // Stable, so equal scores keep the Anthology's order
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,217 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::latex::biblatex::Cursor;
+use crate::latex::{BibEntry, BibEntryBuilder, Person, PersonList};
+
+// `\bib{key}{type}{` opening an amsrefs record; `\bib*` records are cross-reference targets
+static BIB_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\bib\*?\{([^}]+)\}\{([^}]+)\}").expect("Invalid amsrefs record regex pattern")
+});
+
+// `\MR{0019911}` review numbers and `\arxiv{1234.5678}` links inside values
+static MR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\MR\{([^}]+)\}").expect("Invalid amsrefs review regex pattern")
+});
+static ARXIV_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:\\arxiv\{|arxiv:\s*)([a-z-]*/?\d{4}\.?\d{3,5}(?:v\d+)?)").expect("Invalid amsrefs eprint regex pattern")
+});
+
+/// Name keys, repeated once per person
+const NAME_FIELDS: [&str; 3] = ["author", "editor", "translator"];
+
+/// amsrefs keys with no BibTeX counterpart
+const IGNORED_FIELDS: [&str; 6] = ["label", "hyphenation", "xref", "partial", "reprint", "translation"];
+
+/// amsrefs keys that have a different name in BibTeX
+const FIELD_RENAMES: [(&str, &str); 4] = [
+    ("fulljournal", "journal"),
+    ("place", "address"),
+    ("review", "mrnumber"),
+    ("status", "note"),
+];
+
+/// Returns true if the content holds amsrefs `\bib` records
+pub fn is_amsrefs(content: &str) -> bool {
+    content.contains("\\begin{biblist}")
+        || (!content.contains("\\begin{thebibliography}") && BIB_REGEX.is_match(content))
+}
+
+/// Parse the `\bib{key}{type}{field={value}, ...}` records of an amsrefs bibliography.
+///
+/// amsrefs repeats `author=` once per person and nests the containing book and
+/// conference in `book={...}` and `conference={...}`; both are flattened into the usual
+/// `booktitle`, `publisher` and `address` fields. The first record of a key wins.
+pub fn parse_amsrefs(content: &str) -> Vec<BibEntry> {
+    let mut entries: Vec<BibEntry> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for cap in BIB_REGEX.captures_iter(content) {
+        let key = cap[1].trim().to_string();
+        let mut cursor = Cursor::new(content);
+        cursor.pos = cap.get(0).unwrap().end();
+        let Some(body) = cursor.read_group() else {
+            continue;
+        };
+        if seen.insert(key.clone()) {
+            entries.push(parse_record(key, cap[2].trim(), body));
+        }
+    }
+
+    entries
+}
+
+/// Convert the fields of one `\bib` record into a BibEntry
+fn parse_record(key: String, amsrefs_type: &str, body: &str) -> BibEntry {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut names: HashMap<String, PersonList> = HashMap::new();
+    let mut container: Option<&str> = None;
+    let mut conference = false;
+
+    for (name, value) in parse_fields(body) {
+        match name.as_str() {
+            "book" | "conference" => {
+                // The containing volume: its title is the booktitle, the rest fills gaps
+                conference |= name == "conference";
+                container = Some(if conference { "conference" } else { "book" });
+                for (inner, inner_value) in parse_fields(value) {
+                    let field = match inner.as_str() {
+                        "title" => "booktitle".to_string(),
+                        "date" => "year".to_string(),
+                        "editor" => {
+                            names.entry(inner).or_default().persons.push(Person::parse(inner_value));
+                            continue;
+                        }
+                        _ => bibtex_field_name(&inner),
+                    };
+                    let value = clean_value(&field, inner_value);
+                    if !value.is_empty() {
+                        fields.entry(field).or_insert(value);
+                    }
+                }
+            }
+            _ if NAME_FIELDS.contains(&name.as_str()) => {
+                names.entry(name).or_default().persons.push(Person::parse(value));
+            }
+            _ if IGNORED_FIELDS.contains(&name.as_str()) => {}
+            "eprint" | "arxiv" => {
+                match ARXIV_REGEX.captures(value) {
+                    Some(id) => {
+                        fields.insert("eprint".to_string(), id[1].to_string());
+                        fields.insert("archivePrefix".to_string(), "arXiv".to_string());
+                    }
+                    None => {
+                        fields.insert("eprint".to_string(), clean_value("eprint", value));
+                    }
+                }
+            }
+            _ => {
+                let field = if name == "date" { "year".to_string() } else { bibtex_field_name(&name) };
+                let value = clean_value(&field, value);
+                if !value.is_empty() {
+                    fields.insert(field, value);
+                }
+            }
+        }
+    }
+
+    for (role, persons) in names {
+        if !persons.is_empty() {
+            fields.insert(role, persons.to_bibtex());
+        }
+    }
+    if let Some(subtitle) = fields.remove("subtitle") {
+        if let Some(title) = fields.get_mut("title") {
+            title.push_str(": ");
+            title.push_str(&subtitle);
+        }
+    }
+
+    let entry_type = bibtex_entry_type(amsrefs_type, container, fields.get("type").map(String::as_str));
+    // Theses and reports name the school or institution as the organization
+    if let Some(organization) = fields.remove("organization") {
+        let field = match entry_type {
+            "phdthesis" | "mastersthesis" => "school",
+            "techreport" => "institution",
+            _ => "organization",
+        };
+        fields.insert(field.to_string(), organization);
+    }
+    BibEntryBuilder::new(key, entry_type).fields(fields).build()
+}
+
+/// Map an amsrefs record type onto the closest BibTeX entry type
+fn bibtex_entry_type(amsrefs_type: &str, container: Option<&str>, thesis_type: Option<&str>) -> &'static str {
+    match (amsrefs_type.to_lowercase().as_str(), container) {
+        // A contribution to a book or conference is written as an article with a container
+        ("article", Some("conference")) | ("inproceedings", _) => "inproceedings",
+        ("article", Some(_)) | ("incollection", _) => "incollection",
+        ("article", None) => "article",
+        ("book", _) | ("collection", _) => "book",
+        ("proceedings", _) => "proceedings",
+        ("innerbook", _) | ("inbook", _) => "inbook",
+        ("thesis", _) if thesis_type.is_some_and(|kind| kind.to_lowercase().contains("master")) => "mastersthesis",
+        ("thesis", _) | ("phdthesis", _) => "phdthesis",
+        ("report", _) | ("techreport", _) => "techreport",
+        ("manual", _) => "manual",
+        ("unpublished", _) => "unpublished",
+        _ => "misc",
+    }
+}
+
+/// Map an amsrefs key onto its BibTeX name
+fn bibtex_field_name(name: &str) -> String {
+    FIELD_RENAMES
+        .iter()
+        .find(|(amsrefs, _)| *amsrefs == name)
+        .map(|(_, bibtex)| bibtex.to_string())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Collapse whitespace, unwrap `\MR{...}` and keep only the year of a date
+fn clean_value(field: &str, value: &str) -> String {
+    let value = MR_REGEX.replace_all(value, "$1");
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    match field {
+        // Dates are `1947`, `1947-03` or `1947-03-12`
+        "year" => value.chars().take_while(|c| c.is_ascii_digit()).collect(),
+        _ => value,
+    }
+}
+
+/// Parse the `key={value}, key=value` list of an amsrefs record, keeping repeated keys
+fn parse_fields(body: &str) -> Vec<(String, &str)> {
+    let mut fields = Vec::new();
+    let mut cursor = Cursor::new(body);
+    loop {
+        cursor.skip_separators();
+        let start = cursor.pos;
+        while cursor.peek().is_some_and(|b| b != b'=' && b != b',') {
+            cursor.pos += 1;
+        }
+        let name = body[start..cursor.pos].trim().to_lowercase();
+        if cursor.peek() != Some(b'=') {
+            break;
+        }
+        cursor.pos += 1;
+        while cursor.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            cursor.pos += 1;
+        }
+        let value = if cursor.peek() == Some(b'{') {
+            match cursor.read_group() {
+                Some(value) => value,
+                None => break,
+            }
+        } else {
+            // Simple values may be written without braces: `date=1947`
+            let start = cursor.pos;
+            while cursor.peek().is_some_and(|b| b != b',') {
+                cursor.pos += 1;
+            }
+            body[start..cursor.pos].trim()
+        };
+        fields.push((name, value));
+    }
+    fields
+}
@@ -0,0 +1,261 @@
+use async_trait::async_trait;
+use backoff::future::retry;
+use log::info;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::BibExtractError;
+use crate::latex::dblp_dump::element_text;
+use crate::latex::http::{api_backoff, status_error, HTTP_CLIENT};
+use crate::latex::person::latex_to_unicode;
+use crate::latex::providers::{Candidate, MetadataProvider};
+use crate::latex::similarity::{match_record, MatchThresholds};
+use crate::latex::{BibEntry, BibEntryBuilder, Person, PersonList};
+
+static FEED_ENTRY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<entry>(.*?)</entry>").expect("Invalid arXiv feed entry regex pattern")
+});
+static PRIMARY_CATEGORY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<arxiv:primary_category[^>]*term="([^"]+)""#).expect("Invalid arXiv primary category regex pattern")
+});
+// http://arxiv.org/abs/1706.03762v7 or http://arxiv.org/abs/hep-th/9901001v1
+static ABS_URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"arxiv\.org/abs/(.+?)(?:v[0-9]+)?$").expect("Invalid arXiv abstract URL regex pattern")
+});
+
+/// How many papers a title search asks arXiv for
+const SEARCH_RESULTS: &str = "5";
+
+/// One paper of an arXiv export API (Atom) response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArxivSearchResult {
+    /// arXiv ID without version
+    pub id: String,
+    pub title: String,
+    pub authors: Vec<String>,
+    /// Year of the first version
+    pub year: String,
+    pub primary_category: Option<String>,
+    pub doi: Option<String>,
+}
+
+impl ArxivSearchResult {
+    /// The authors, parsed as BibTeX names
+    pub fn persons(&self) -> Vec<Person> {
+        self.authors.iter().map(|name| Person::parse(name)).collect()
+    }
+
+    /// The entry with this paper's title, authors and arXiv identifiers. Its venue and type are
+    /// kept, as is its year, which may be the year of the published version.
+    pub fn to_entry(&self, entry: &BibEntry) -> BibEntry {
+        let mut builder = BibEntryBuilder::new(entry.key.clone(), entry.entry_type.clone());
+        for (field, value) in &entry.fields {
+            if field != "verified_source" {
+                builder = builder.field(field, value);
+            }
+        }
+        builder = builder
+            .field("title", self.title.as_str())
+            .field("eprint", self.id.as_str())
+            .field("archivePrefix", "arXiv");
+        let persons = self.persons();
+        if !persons.is_empty() {
+            builder = builder.field("author", PersonList { persons, is_truncated: false }.to_bibtex());
+        }
+        if entry.get("year").is_none() {
+            builder = builder.field("year", self.year.as_str());
+        }
+        if let Some(category) = &self.primary_category {
+            builder = builder.field("primaryClass", category.as_str());
+        }
+        if entry.get("url").is_none() {
+            builder = builder.field("url", format!("https://arxiv.org/abs/{}", self.id));
+        }
+        if let (Some(doi), None) = (&self.doi, entry.get("doi")) {
+            builder = builder.field("doi", doi.as_str());
+        }
+        builder.build()
+    }
+}
+
+/// Read the papers of an arXiv export API response. Entries without an ID, title or
+/// publication date (such as error entries) are skipped.
+pub fn parse_arxiv_feed(feed: &str) -> Vec<ArxivSearchResult> {
+    FEED_ENTRY_REGEX
+        .captures_iter(feed)
+        .filter_map(|captures| {
+            let entry = captures.get(1)?.as_str();
+            let id_url = element(entry, "id")?;
+            let id = ABS_URL_REGEX.captures(&id_url)?.get(1)?.as_str().to_string();
+            let title = element(entry, "title").filter(|title| !title.is_empty())?;
+            // 2017-06-12T17:57:34Z
+            let year = element(entry, "published")?.get(..4)?.to_string();
+            let authors = entry
+                .split("<name>")
+                .skip(1)
+                .filter_map(|rest| rest.find("</name>").map(|end| element_text(&rest[..end])))
+                .collect();
+            Some(ArxivSearchResult {
+                id,
+                title,
+                authors,
+                year,
+                primary_category: PRIMARY_CATEGORY_REGEX.captures(entry).map(|c| c[1].to_string()),
+                doi: element(entry, "arxiv:doi"),
+            })
+        })
+        .collect()
+}
+
+/// The text of the first `name` element of an Atom entry
+fn element(entry: &str, name: &str) -> Option<String> {
+    let start = entry.find(&format!("<{}", name))?;
+    let content_start = start + entry[start..].find('>')? + 1;
+    let content_end = content_start + entry[content_start..].find(&format!("</{}>", name))?;
+    Some(element_text(&entry[content_start..content_end]))
+}
+
+/// Whether an entry may cite an arXiv preprint: it names no venue, or its venue is arXiv.
+/// Published papers are left to the bibliographic databases.
+pub fn cites_preprint(entry: &BibEntry) -> bool {
+    let venue = entry.get("journal").or_else(|| entry.get("booktitle")).map(|venue| venue.to_lowercase());
+    venue.is_none_or(|venue| ["arxiv", "corr", "preprint"].iter().any(|word| venue.contains(word)))
+}
+
+/// The export API query for an entry's title and first author, e.g.
+/// `ti:"attention is all you need" AND au:Vaswani`
+pub fn arxiv_search_query(entry: &BibEntry) -> Option<String> {
+    // Math and punctuation mean nothing to the search and may break its syntax
+    let title: Vec<String> = latex_to_unicode(&entry.get("title")?.replace(['{', '}'], ""))
+        .split('$')
+        .step_by(2)
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()).map(str::to_lowercase).collect::<Vec<_>>())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if title.is_empty() {
+        return None;
+    }
+    let mut query = format!("ti:\"{}\"", title.join(" "));
+    let last_name = entry.authors().first().and_then(|person| {
+        person.last.split(|c: char| !c.is_alphanumeric()).rfind(|word| !word.is_empty()).map(str::to_string)
+    });
+    if let Some(last_name) = last_name {
+        query.push_str(&format!(" AND au:{}", last_name));
+    }
+    Some(query)
+}
+
+/// Run a query against the arXiv export API, retrying transient failures. Failed queries give `None`.
+pub async fn query_arxiv_search(query: &str) -> Result<Option<String>, BibExtractError> {
+    // Support configurable base URL for testing
+    let base_url = std::env::var("ARXIV_API_BASE_URL").unwrap_or_else(|_| "https://export.arxiv.org".to_string());
+    let url = format!("{}/api/query", base_url);
+
+    let operation = || async {
+        info!("Searching arXiv for: {}", query);
+        let response = HTTP_CLIENT
+            .get(&url)
+            .query(&[("search_query", query), ("max_results", SEARCH_RESULTS)])
+            .send()
+            .await
+            .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
+
+        if response.status().is_success() {
+            let feed = response.text().await
+                .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
+            Ok(Some(feed))
+        } else {
+            Err(status_error("arXiv", response.status()))
+        }
+    };
+
+    match retry(api_backoff(), operation).await {
+        Ok(result) => Ok(result),
+        Err(_) => {
+            log::warn!("arXiv API search failed after retries for: {}", query);
+            Ok(None)
+        }
+    }
+}
+
+/// Searches arXiv's export API by title and first author for entries that may cite a
+/// preprint (see `cites_preprint`). Matches are scored as DBLP hits are and give the entry
+/// the paper's `eprint` and `archivePrefix`.
+///
+/// The export API lives at `ARXIV_API_BASE_URL` (https://export.arxiv.org by default) and
+/// asks for a few seconds between requests, which the provider leaves. Since the wait is
+/// shared by all entries, the default chain only searches arXiv after every other provider.
+#[derive(Debug)]
+pub struct ArxivSearchProvider {
+    min_interval: Duration,
+    thresholds: MatchThresholds,
+    /// When the last search was sent, shared by all tasks using the provider
+    last_search: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl Default for ArxivSearchProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArxivSearchProvider {
+    /// A provider leaving 3 seconds between searches
+    pub fn new() -> Self {
+        Self {
+            min_interval: Duration::from_secs(3),
+            thresholds: MatchThresholds::default(),
+            last_search: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Leave at least `min_interval` between two searches
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Match search results with `thresholds` instead of the defaults
+    pub fn with_thresholds(mut self, thresholds: MatchThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Wait until the rate limit allows another search
+    async fn wait_turn(&self) {
+        let mut last_search = self.last_search.lock().await;
+        if let Some(last) = *last_search {
+            tokio::time::sleep_until(last + self.min_interval).await;
+        }
+        *last_search = Some(Instant::now());
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for ArxivSearchProvider {
+    fn name(&self) -> &str {
+        "arXiv search"
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let Some(query) = arxiv_search_query(entry).filter(|_| cites_preprint(entry)) else {
+            return Ok(Vec::new());
+        };
+        self.wait_turn().await;
+        let Some(feed) = query_arxiv_search(&query).await? else {
+            return Ok(Vec::new());
+        };
+        let mut candidates: Vec<Candidate> = parse_arxiv_feed(&feed)
+            .into_iter()
+            .filter_map(|result| {
+                let score = match_record(entry, &result.title, Some(&result.year), &result.persons(), &self.thresholds);
+                (score >= self.thresholds.min_score).then(|| Candidate::new(result.to_entry(entry), score, self.name()))
+            })
+            .collect();
+        // Stable, so equal scores keep arXiv's ranking
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use log::info;
+use serde_json::Value;
+
+use crate::error::BibExtractError;
+use crate::latex::local_index::TitleIndex;
+use crate::latex::person::latex_to_unicode;
+use crate::latex::similarity::MatchThresholds;
+use crate::latex::providers::{match_score, Candidate, MetadataProvider};
+use crate::latex::{BibEntry, BibEntryBuilder, Bibliography, Person, PersonList};
+
+/// One paper of the arXiv metadata snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArxivRecord {
+    /// arXiv ID without version, e.g. `1706.03762` or `hep-th/9901001`
+    pub id: String,
+    pub title: String,
+    pub authors: PersonList,
+    /// Year of the first version
+    pub year: Option<String>,
+    /// Categories, primary first
+    pub categories: Vec<String>,
+    pub doi: Option<String>,
+    pub journal_ref: Option<String>,
+}
+
+impl ArxivRecord {
+    /// Read a line of the snapshot, with its `id`, `title`, `authors` (or `authors_parsed`),
+    /// `categories`, `doi`, `journal-ref` and `versions` fields
+    pub fn from_json(paper: &Value) -> Option<Self> {
+        let string = |field: &str| {
+            paper
+                .get(field)
+                .and_then(|v| v.as_str())
+                .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|v| !v.is_empty())
+        };
+        let id = string("id")?;
+        let title = latex_to_unicode(&string("title")?);
+
+        let parsed: Vec<Person> = paper
+            .get("authors_parsed")
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|name| {
+                let part = |i: usize| latex_to_unicode(name.get(i).and_then(|p| p.as_str()).unwrap_or_default().trim());
+                let person = Person { first: part(1), last: part(0), jr: part(2), ..Default::default() };
+                (!person.last.is_empty()).then_some(person)
+            })
+            .collect();
+        let authors = if parsed.is_empty() {
+            PersonList::parse(&string("authors").unwrap_or_default())
+        } else {
+            PersonList { persons: parsed, is_truncated: false }
+        };
+
+        // "Mon, 2 Apr 2007 19:18:42 GMT"
+        let year = paper
+            .get("versions")
+            .and_then(|v| v.get(0))
+            .and_then(|v| v.get("created"))
+            .and_then(|created| created.as_str())
+            .and_then(|created| created.split_whitespace().nth(3).map(str::to_string))
+            .or_else(|| year_from_id(&id));
+
+        Some(ArxivRecord {
+            categories: string("categories").map(|c| c.split(' ').map(str::to_string).collect()).unwrap_or_default(),
+            doi: string("doi"),
+            journal_ref: string("journal-ref"),
+            id,
+            title,
+            authors,
+            year,
+        })
+    }
+
+    /// The record as the entry arXiv's BibTeX export gives for it, plus its DOI and journal reference
+    pub fn to_entry(&self, key: &str) -> BibEntry {
+        let mut builder = BibEntryBuilder::new(key, "misc")
+            .field("title", self.title.as_str())
+            .field("eprint", self.id.as_str())
+            .field("archivePrefix", "arXiv")
+            .field("url", format!("https://arxiv.org/abs/{}", self.id));
+        if !self.authors.is_empty() {
+            builder = builder.field("author", self.authors.to_bibtex());
+        }
+        if let Some(year) = &self.year {
+            builder = builder.field("year", year.as_str());
+        }
+        if let Some(category) = self.categories.first() {
+            builder = builder.field("primaryClass", category.as_str());
+        }
+        if let Some(doi) = &self.doi {
+            builder = builder.field("doi", doi.as_str());
+        }
+        if let Some(journal_ref) = &self.journal_ref {
+            builder = builder.field("note", journal_ref.as_str());
+        }
+        builder.build()
+    }
+}
+
+/// The year of a paper from its ID: `1706.03762` is from 2017, `hep-th/9901001` from 1999
+fn year_from_id(id: &str) -> Option<String> {
+    let digits = id.rsplit('/').next()?;
+    let yy: u32 = digits.get(..2)?.parse().ok()?;
+    // Old-style IDs start in 1991
+    Some(if yy >= 91 { 1900 + yy } else { 2000 + yy }.to_string())
+}
+
+/// An index over arXiv's metadata snapshot, by ID, title and author
+#[derive(Debug, Default)]
+pub struct ArxivIndex {
+    records: Vec<ArxivRecord>,
+    by_id: HashMap<String, usize>,
+    titles: TitleIndex,
+}
+
+impl ArxivIndex {
+    pub fn from_records(records: Vec<ArxivRecord>) -> Self {
+        let mut index = ArxivIndex::default();
+        for record in records {
+            index.insert(record);
+        }
+        index
+    }
+
+    fn insert(&mut self, record: ArxivRecord) {
+        let position = self.records.len();
+        self.by_id.insert(record.id.clone(), position);
+        self.titles.insert(&record.title, record.authors.persons.iter().map(|person| person.last.as_str()));
+        self.records.push(record);
+    }
+
+    /// Index a snapshot file (JSON Lines, gzip-compressed if its name ends in `.gz`).
+    /// Lines that are not papers are skipped.
+    pub fn from_snapshot(path: &Path) -> Result<Self, BibExtractError> {
+        info!("Indexing arXiv metadata snapshot {:?}", path);
+        let file = File::open(path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(BufReader::new(GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+        let mut index = ArxivIndex::default();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&line).ok().as_ref().and_then(ArxivRecord::from_json) {
+                Some(record) => index.insert(record),
+                None => log::warn!("Skipping malformed line of arXiv snapshot {:?}", path),
+            }
+        }
+        info!("Indexed {} arXiv papers", index.len());
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The paper with this ID; a version suffix (`v2`) is ignored
+    pub fn get(&self, arxiv_id: &str) -> Option<&ArxivRecord> {
+        let id = arxiv_id
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .strip_suffix('v')
+            .filter(|id| !id.is_empty())
+            .unwrap_or(arxiv_id);
+        self.by_id.get(id).map(|&position| &self.records[position])
+    }
+
+    /// Papers that may be the entry: those with its title, and those by its first author
+    /// with a similar title
+    pub fn candidates(&self, entry: &BibEntry) -> Vec<&ArxivRecord> {
+        self.titles
+            .candidates(entry)
+            .into_iter()
+            .map(|position| &self.records[position])
+            .collect()
+    }
+}
+
+/// Verifies entries against a local copy of arXiv's metadata snapshot, without network
+/// access: entries with an arXiv ID are looked up by it, the others by title and authors.
+#[derive(Debug, Clone)]
+pub struct ArxivSnapshotProvider {
+    index: Arc<ArxivIndex>,
+}
+
+impl ArxivSnapshotProvider {
+    pub fn new(index: Arc<ArxivIndex>) -> Self {
+        Self { index }
+    }
+
+    /// Index a snapshot file (see `ArxivIndex::from_snapshot`)
+    pub fn open(path: &Path) -> Result<Self, BibExtractError> {
+        Ok(Self::new(Arc::new(ArxivIndex::from_snapshot(path)?)))
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for ArxivSnapshotProvider {
+    /// Named like the online provider, which it replaces
+    fn name(&self) -> &str {
+        "arXiv"
+    }
+
+    async fn lookup_id(&self, entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        Ok(Bibliography::new()
+            .extract_arxiv_id(entry)
+            .and_then(|arxiv_id| self.index.get(&arxiv_id))
+            .map(|record| Candidate::new(record.to_entry(&entry.key), 1.0, self.name())))
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let mut candidates: Vec<Candidate> = self
+            .index
+            .candidates(entry)
+            .into_iter()
+            .map(|record| {
+                let verified = record.to_entry(&entry.key);
+                let score = match_score(entry, &verified);
+                Candidate::new(verified, score, self.name())
+            })
+            .filter(|candidate| candidate.score >= MatchThresholds::default().min_score)
+            .collect();
+        // Stable, so equal scores keep the snapshot's order
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,130 @@
+/// One `\bibitem` of a `thebibliography` block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BblItem<'a> {
+    pub key: &'a str,
+    /// The optional `[label]`, e.g. `Wei et~al.(2022)` for natbib styles
+    pub label: Option<&'a str>,
+    /// The whole item, from `\bibitem` up to the next item or the end of the block
+    pub text: &'a str,
+    /// The item after its `\bibitem[label]{key}` heading
+    pub body: &'a str,
+}
+
+/// Streams the items of the first `thebibliography` block of a `.bbl` file.
+///
+/// Items are found in a single pass over the block: each `\bibitem` heading is read by hand,
+/// nothing is copied, and each item ends where the next well-formed heading starts.
+/// Headings broken over two lines by BibTeX (`\bibitem[label]%` with the key on the next
+/// line) are read as one.
+pub struct BblScanner<'a> {
+    content: &'a str,
+    /// Where the search for the next `\bibitem` resumes
+    pos: usize,
+    /// End of the `thebibliography` block
+    end: usize,
+}
+
+impl<'a> BblScanner<'a> {
+    /// Scan the `thebibliography` block of `content`; yields nothing if there is none
+    pub fn new(content: &'a str) -> Self {
+        const BEGIN: &str = "\\begin{thebibliography}";
+        let Some(begin) = content.find(BEGIN) else {
+            return BblScanner { content, pos: 0, end: 0 };
+        };
+        let pos = begin + BEGIN.len();
+        let end = content[pos..]
+            .find("\\end{thebibliography}")
+            .map_or(content.len(), |end| pos + end);
+        BblScanner { content, pos, end }
+    }
+
+    /// The next well-formed `\bibitem` heading at or after `from`, within the block:
+    /// its position, label, key and length. Malformed headings are read as item text.
+    fn find_item(&self, mut from: usize) -> Option<(usize, Option<&'a str>, &'a str, usize)> {
+        while let Some(offset) = self.content[from..self.end].find("\\bibitem") {
+            let start = from + offset;
+            from = start + "\\bibitem".len();
+            // `\bibitemsep` and friends are other commands
+            if self.content[from..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                continue;
+            }
+            if let Some((label, key, heading)) = parse_bibitem_heading(&self.content[start..self.end]) {
+                return Some((start, label, key, heading));
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for BblScanner<'a> {
+    type Item = BblItem<'a>;
+
+    fn next(&mut self) -> Option<BblItem<'a>> {
+        let (start, label, key, heading) = self.find_item(self.pos)?;
+        let body_start = start + heading;
+        let end = self.find_item(body_start).map_or(self.end, |(next, ..)| next);
+        self.pos = end;
+        Some(BblItem {
+            key,
+            label,
+            text: &self.content[start..end],
+            body: &self.content[body_start..end],
+        })
+    }
+}
+
+/// Read a `\bibitem[label]{key}` heading at the start of `text`.
+/// Returns the label, the key and the length of the heading.
+pub(crate) fn parse_bibitem_heading(text: &str) -> Option<(Option<&str>, &str, usize)> {
+    if !text.starts_with("\\bibitem") {
+        return None;
+    }
+    let bytes = text.as_bytes();
+    let mut pos = "\\bibitem".len();
+
+    let skip_space = |mut pos: usize| {
+        loop {
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            // `%` comments out the line break BibTeX inserts into long headings
+            if bytes.get(pos) == Some(&b'%') {
+                pos = text[pos..].find('\n').map_or(text.len(), |newline| pos + newline + 1);
+                continue;
+            }
+            return pos;
+        }
+    };
+
+    pos = skip_space(pos);
+    let mut label = None;
+    if bytes.get(pos) == Some(&b'[') {
+        // Labels may hold braces and brackets of their own: `[{[}Smith]{2020}]`
+        let mut depth = 0usize;
+        let mut brackets = 0usize;
+        let open = pos;
+        loop {
+            pos += 1;
+            match bytes.get(pos)? {
+                b'{' => depth += 1,
+                b'}' => depth = depth.saturating_sub(1),
+                b'[' if depth == 0 => brackets += 1,
+                b']' if depth == 0 && brackets == 0 => break,
+                b']' if depth == 0 => brackets -= 1,
+                _ => {}
+            }
+        }
+        label = Some(&text[open + 1..pos]);
+        pos = skip_space(pos + 1);
+    }
+
+    if bytes.get(pos) != Some(&b'{') {
+        return None;
+    }
+    let close = pos + text[pos..].find('}')?;
+    let key = text[pos + 1..close].trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((label, key, close + 1))
+}
@@ -0,0 +1,738 @@
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::latex::bbl_scanner::parse_bibitem_heading;
+use crate::latex::diagnostics::ParseWarning;
+use crate::latex::reference_tagger::{tag_reference, ReferenceField};
+use crate::latex::{BibEntry, BibEntryBuilder};
+
+// `\bibitem[label]{key}` heading every thebibliography item
+pub static BIBITEM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\bibitem(?:\[([^\]]*)\])?\{([^}]+)\}").expect("Invalid bibitem regex pattern")
+});
+// `% Generated by IEEEtranN.bst` style headers
+static GENERATED_BY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)generated by\s+([A-Za-z0-9_-]+)\.bst").expect("Invalid style header regex pattern")
+});
+static YEAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(19\d{2}|20\d{2})\b").expect("Invalid year regex pattern")
+});
+// natbib and apalike labels carry the year: `Wei et~al.(2022)` or `Baker, 2022`
+static LABEL_YEAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\(|,\s*)(19\d{2}|20\d{2})[a-z]?\)?").expect("Invalid label year regex pattern")
+});
+static PERSON_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\bibinfo\{person\}\{([^}]+)\}").expect("Invalid ACM person regex pattern")
+});
+static SHOW_TITLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\showarticletitle\{([^}]+)\}").expect("Invalid ACM title regex pattern")
+});
+// IEEEtran quotes titles as ``Title,'' with the separating comma inside the quotes
+static IEEE_TITLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)``(.+?)''").expect("Invalid IEEE title regex pattern")
+});
+static DOI_COMMAND_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\doi\{([^}]+)\}").expect("Invalid DOI regex pattern")
+});
+static DOI_TEXT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:doi:\s*|https?://(?:dx\.)?doi\.org/)(10\.\d{4,9}/[^\s,;{}]+)").expect("Invalid DOI regex pattern")
+});
+static URL_COMMAND_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(?:url|href)\{([^}]+)\}").expect("Invalid URL regex pattern")
+});
+// `\eprint{2101.00001}`, `\eprint[arXiv]{...}` and ACM's `\showeprint[arxiv]{...}`
+static EPRINT_COMMAND_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(?:show)?eprint(?:\[([^\]]*)\])?\{([^}]+)\}").expect("Invalid eprint regex pattern")
+});
+static ARXIV_PREPRINT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)arxiv(?:\s+preprint)?(?:\s*arxiv)?\s*:?\s*(\d{4}\.\d{4,5})(?:v\d+)?").expect("Invalid arXiv preprint regex pattern")
+});
+static VOLUME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bvol(?:ume)?\.?\s*(\d+)|\\textbf\{(\d+)\}").expect("Invalid volume regex pattern")
+});
+static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:no\.|number|issue)\s*(\d+)").expect("Invalid number regex pattern")
+});
+// Page ranges may use thin spaces as thousands separators: `24\,824--24\,837`
+static PAGES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:pp\.|pages|p\.)\s*(\d[\d,]*(?:\s*(?:-+|–)\s*\d[\d,]*)?)").expect("Invalid pages regex pattern")
+});
+// natbib's `35(2):1--10` and `35:1--10`
+static VOLUME_PAGES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\d+)\s*(?:\((\d+)\))?\s*:\s*(\d+(?:\s*(?:-+|–)\s*\d+)?)").expect("Invalid volume regex pattern")
+});
+// Bare page ranges in styles that do not label them (amsplain)
+static PAGE_RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\d+\s*(?:--|–)\s*\d+)\b").expect("Invalid page range regex pattern")
+});
+static IN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[\s.,])in\s*$|(?:^|[\s.,])in\s").expect("Invalid venue regex pattern")
+});
+// A sentence naming the container, `In Proceedings of ...`
+static IN_SENTENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^in(?:\s|$)").expect("Invalid venue regex pattern")
+});
+static PROCEEDINGS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)proceedings|\bproc\.|conference|\bconf\.|workshop|symposium|advances in").expect("Invalid venue regex pattern")
+});
+static PUBLISHER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:press|publish\w*|springer|elsevier|wiley|associates|verlag|inc|ltd|pmlr|morgan|addison-wesley|o'reilly|curran|society|association|ieee|acm)\b")
+        .expect("Invalid publisher regex pattern")
+});
+
+// Cues in the item text that tell the BibTeX entry type
+static MASTERS_THESIS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)master'?s\s+thesis|\bm\.?\s?sc?\.?\s+thesis|diploma\s+thesis").expect("Invalid thesis regex pattern")
+});
+static PHD_THESIS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bph\.?\s?d\.?\s+(?:thesis|dissertation)|doctoral\s+(?:thesis|dissertation)|\bdissertation\b")
+        .expect("Invalid thesis regex pattern")
+});
+static TECH_REPORT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)technical\s+report|\btech\.\s*rep(?:ort)?\b").expect("Invalid report regex pattern")
+});
+static IN_PROCEEDINGS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bin\s+(?:\\emph\{|\\textit\{|\{\\em\s+|\{\\it\s+)?(?:the\s+)?(?:proc\.|proceedings)|\badvances\s+in\b")
+        .expect("Invalid proceedings regex pattern")
+});
+static EDITED_BOOK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bchapter\b|\(eds?\.?\)|\beditors?\b|\beds?\.\s|\bedited\s+by\b").expect("Invalid book regex pattern")
+});
+static ARXIV_CUE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)arxiv\s+preprint|\barxiv:|\\eprint").expect("Invalid arXiv regex pattern")
+});
+
+/// Extracts bibliography entries from the `\bibitem` blocks written by one BibTeX style.
+///
+/// Every style lays out authors, titles and years differently, so each gets its own
+/// parser; parsers for other styles can be added with `register_bbl_style`. `detect`
+/// decides from the whole `.bbl` content (header comment and `\providecommand` preamble)
+/// whether the file was written by the style.
+pub trait BblStyleParser: Send + Sync {
+    /// Name of the style, as used in `.bst` file names
+    fn name(&self) -> &'static str;
+
+    /// Returns true if the `.bbl` content was generated by this style
+    fn detect(&self, content: &str) -> bool;
+
+    /// Parse one `\bibitem` block, from `\bibitem` up to the next item.
+    /// The entry type is inferred by the caller from the fields and the item text.
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry;
+}
+
+/// Parsers for the known styles, in detection order. The generic parser comes last.
+static STYLE_PARSERS: [&dyn BblStyleParser; 6] = [
+    &AcmStyle,
+    &IeeeTranStyle,
+    &AmsPlainStyle,
+    &ApaLikeStyle,
+    &NatbibStyle,
+    &GenericStyle,
+];
+
+/// Parsers added with `register_bbl_style`, in registration order
+static REGISTERED_PARSERS: Lazy<RwLock<Vec<&'static dyn BblStyleParser>>> = Lazy::new(Default::default);
+
+/// Register a parser for a style the built-in parsers do not know, for every `.bbl` file
+/// parsed afterwards. Registered parsers are tried before the built-in ones, in registration
+/// order, and replace an earlier registered parser of the same name.
+pub fn register_bbl_style(parser: &'static dyn BblStyleParser) {
+    let mut registered = REGISTERED_PARSERS.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    registered.retain(|existing| !existing.name().eq_ignore_ascii_case(parser.name()));
+    registered.push(parser);
+}
+
+/// All style parsers in detection order: the registered ones, then the built-in ones,
+/// the generic fallback last
+pub fn style_parsers() -> Vec<&'static dyn BblStyleParser> {
+    let registered = REGISTERED_PARSERS.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    registered.iter().chain(STYLE_PARSERS.iter()).copied().collect()
+}
+
+/// Find the style parser for a `.bbl` file, falling back to the generic heuristics
+pub fn detect_bbl_style(content: &str) -> &'static dyn BblStyleParser {
+    style_parsers()
+        .into_iter()
+        .find(|parser| parser.detect(content))
+        .unwrap_or(&GenericStyle)
+}
+
+/// Look up a style parser by name (e.g. `IEEEtran` or `plainnat`)
+pub fn bbl_style_by_name(name: &str) -> Option<&'static dyn BblStyleParser> {
+    let name = name.trim_end_matches(".bst");
+    style_parsers()
+        .into_iter()
+        .find(|parser| parser.name().eq_ignore_ascii_case(name) || declares_style(name, parser.name()))
+}
+
+/// The `.bst` name from a `% Generated by X.bst` header, if present
+fn generated_by(content: &str) -> Option<&str> {
+    let header = &content[..content.find("\\bibitem").unwrap_or(content.len())];
+    GENERATED_BY_REGEX
+        .captures(header)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str())
+}
+
+/// Whether a `.bst` name is one of the variants handled by a parser
+fn declares_style(bst: &str, parser_name: &str) -> bool {
+    let bst = bst.to_lowercase();
+    match parser_name {
+        "IEEEtran" => bst.starts_with("ieeetran"),
+        "ACM-Reference-Format" => bst.starts_with("acm"),
+        "plainnat" => matches!(bst.as_str(), "plainnat" | "abbrvnat" | "unsrtnat"),
+        "apalike" => bst == "apalike",
+        "amsplain" => matches!(bst.as_str(), "amsplain" | "amsalpha"),
+        _ => false,
+    }
+}
+
+/// Text of an item after its `\bibitem[...]{key}` heading
+fn item_body(item: &str) -> &str {
+    match parse_bibitem_heading(item) {
+        Some((_, _, heading_end)) => &item[heading_end..],
+        None => item,
+    }
+}
+
+/// The optional `[label]` of an item's `\bibitem`
+fn item_label(item: &str) -> Option<&str> {
+    parse_bibitem_heading(item).and_then(|(label, _, _)| label)
+}
+
+/// Content of the brace group opening at byte `open` (which must be a `{`)
+fn brace_group(text: &str, open: usize) -> Option<&str> {
+    let mut depth = 0;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Content of the first `\command{...}` group in `text`
+fn command_argument<'a>(text: &'a str, command: &str) -> Option<&'a str> {
+    let start = text.find(command)?;
+    let open = start + command.len();
+    if !text[open..].starts_with('{') {
+        return None;
+    }
+    brace_group(text, open)
+}
+
+/// Remove emphasis commands and grouping braces, collapse whitespace and trim separators
+fn clean_field(text: &str) -> String {
+    let text = text
+        .replace("\\emph{", "{")
+        .replace("\\textit{", "{")
+        .replace("{\\em ", "{")
+        .replace("{\\it ", "{")
+        .replace(['{', '}'], "")
+        .replace('~', " ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.trim_matches(|c: char| ".,;:!?'\"`*".contains(c) || c.is_whitespace())
+        .to_string()
+}
+
+/// Collapse whitespace in an author list; names are split up later by `PersonList::parse`
+fn clean_authors(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.trim_matches(|c: char| c == ',' || c.is_whitespace()).to_string()
+}
+
+/// Split an item body into its `\newblock` blocks
+fn newblocks(body: &str) -> Vec<&str> {
+    body.split("\\newblock")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// Year from a natbib or apalike label, falling back to the first year in `text`
+fn find_year(label: Option<&str>, text: &str) -> Option<String> {
+    label
+        .and_then(|label| LABEL_YEAR_REGEX.captures(label))
+        .or_else(|| YEAR_REGEX.captures(text))
+        .map(|cap| cap[1].to_string())
+}
+
+/// Remove a trailing ", 2023" that styles append to the last block of misc entries
+fn strip_trailing_year(title: &str) -> &str {
+    let trimmed = title.trim_end_matches(['.', ' ']);
+    match trimmed.rfind(',') {
+        Some(comma) if YEAR_REGEX.is_match(trimmed[comma + 1..].trim()) && trimmed[comma + 1..].trim().len() == 4 => {
+            &trimmed[..comma]
+        }
+        _ => title,
+    }
+}
+
+/// Builder with the fields shared by all styles set when present
+fn build_entry(key: &str, author: Option<String>, title: Option<String>, year: Option<String>, rest: &str) -> BibEntry {
+    let mut builder = BibEntryBuilder::new(key, "misc");
+    for (field, value) in [("author", author), ("title", title), ("year", year)] {
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            builder = builder.field(field, value);
+        }
+    }
+    let mut entry = builder.build();
+    add_details(&mut entry, rest);
+    entry
+}
+
+/// Infer the BibTeX entry type of a parsed bibliography item.
+///
+/// Theses and reports are recognized by name; otherwise the venue decides: a booktitle or
+/// "In Proceedings"/"Advances in" makes a conference paper (a chapter when the book is not a
+/// proceedings volume), a journal an article. Items with only a publisher are books, arXiv
+/// preprints and web pages without a venue are `misc`.
+pub fn infer_entry_type(entry: &BibEntry, item: &str) -> &'static str {
+    let has = |field: &str| entry.get(field).is_some_and(|value| !value.is_empty());
+
+    if MASTERS_THESIS_REGEX.is_match(item) {
+        return "mastersthesis";
+    }
+    if PHD_THESIS_REGEX.is_match(item) || has("school") {
+        return "phdthesis";
+    }
+    if TECH_REPORT_REGEX.is_match(item) || has("institution") {
+        return "techreport";
+    }
+    if let Some(booktitle) = entry.get("booktitle") {
+        // A published or edited book that is not a proceedings volume holds chapters, not papers
+        let proceedings = PROCEEDINGS_REGEX.is_match(booktitle) || IN_PROCEEDINGS_REGEX.is_match(item);
+        let book = has("publisher") || EDITED_BOOK_REGEX.is_match(item);
+        return if !proceedings && book { "incollection" } else { "inproceedings" };
+    }
+    if IN_PROCEEDINGS_REGEX.is_match(item) {
+        return "inproceedings";
+    }
+    if has("journal") {
+        return "article";
+    }
+    if has("eprint") || ARXIV_CUE_REGEX.is_match(item) {
+        return "misc";
+    }
+    if has("publisher") {
+        return "book";
+    }
+    "misc"
+}
+
+/// Normalize a page range: thin spaces and commas dropped, "--" between the bounds
+fn normalize_pages(pages: &str) -> String {
+    let pages = pages.replace("\\,", "").replace([',', ' '], "");
+    let bounds: Vec<&str> = pages
+        .split(['-', '–'])
+        .filter(|bound| !bound.is_empty())
+        .collect();
+    bounds.join("--")
+}
+
+/// The first emphasized group in `text`: its content and the byte range it spans
+fn first_emphasis(text: &str) -> Option<(String, std::ops::Range<usize>)> {
+    ["\\emph{", "\\textit{", "{\\em ", "{\\it "]
+        .iter()
+        .filter_map(|command| text.find(command).map(|start| (start, *command)))
+        .min()
+        .and_then(|(start, command)| {
+            let open = if command.starts_with('{') { start } else { start + command.len() - 1 };
+            let content = brace_group(text, open)?;
+            let content = content.trim_start_matches("\\em ").trim_start_matches("\\it ");
+            Some((clean_field(content), start..open + brace_group(text, open)?.len() + 2))
+        })
+}
+
+/// Extract the venue, volume, number, pages, publisher, DOI, URL and eprint from the part of
+/// a bibliography item that follows its title. Fields the entry already has are kept.
+fn add_details(entry: &mut BibEntry, rest: &str) {
+    let mut details: Vec<(&str, String)> = Vec::new();
+
+    // Identifiers come first so their digits are not read as volumes or pages
+    let mut text = rest.replace("\\,", "").replace("\\_", "_").replace('~', " ");
+    if let Some(cap) = DOI_COMMAND_REGEX.captures(&text).or_else(|| DOI_TEXT_REGEX.captures(&text)) {
+        details.push(("doi", cap[1].trim_end_matches(['.', ',']).to_string()));
+    }
+    if let Some(cap) = URL_COMMAND_REGEX.captures(&text) {
+        details.push(("url", cap[1].trim().to_string()));
+    }
+    if let Some(cap) = EPRINT_COMMAND_REGEX.captures(&text) {
+        let eprint = cap[2].trim();
+        let arxiv = cap.get(1).is_none_or(|kind| kind.as_str().eq_ignore_ascii_case("arxiv"));
+        match ARXIV_PREPRINT_REGEX.captures(eprint) {
+            Some(id) if arxiv => details.push(("eprint", id[1].to_string())),
+            _ => details.push(("eprint", eprint.trim_start_matches("arXiv:").to_string())),
+        }
+        if arxiv {
+            details.push(("archivePrefix", "arXiv".to_string()));
+        }
+    } else if let Some(cap) = ARXIV_PREPRINT_REGEX.captures(&text) {
+        details.push(("eprint", cap[1].to_string()));
+        details.push(("archivePrefix", "arXiv".to_string()));
+    }
+    for regex in [&*DOI_COMMAND_REGEX, &*URL_COMMAND_REGEX, &*EPRINT_COMMAND_REGEX, &*DOI_TEXT_REGEX] {
+        text = regex.replace_all(&text, " ").into_owned();
+    }
+
+    // The emphasized block is the journal or proceedings; "In" or the name tells them apart
+    if let Some((venue, range)) = first_emphasis(&text) {
+        if !venue.is_empty() && !venue.chars().all(|c| c.is_ascii_digit()) {
+            let booktitle = IN_REGEX.is_match(&text[..range.start]) || PROCEEDINGS_REGEX.is_match(&venue);
+            details.push((if booktitle { "booktitle" } else { "journal" }, venue));
+        }
+        text.replace_range(range, " § ");
+    }
+
+    if let Some(cap) = VOLUME_PAGES_REGEX.captures(&text) {
+        details.push(("volume", cap[1].to_string()));
+        if let Some(number) = cap.get(2) {
+            details.push(("number", number.as_str().to_string()));
+        }
+        details.push(("pages", normalize_pages(&cap[3])));
+    }
+    if let Some(cap) = VOLUME_REGEX.captures(&text) {
+        details.push(("volume", cap.get(1).or_else(|| cap.get(2)).map_or("", |m| m.as_str()).to_string()));
+    }
+    if let Some(cap) = NUMBER_REGEX.captures(&text) {
+        details.push(("number", cap[1].to_string()));
+    }
+    if let Some(cap) = PAGES_REGEX.captures(&text).or_else(|| PAGE_RANGE_REGEX.captures(&text)) {
+        details.push(("pages", normalize_pages(&cap[1])));
+    }
+
+    // The publisher is a sentence of its own: `Curran Associates, Inc., 2022.` or `New York: Springer, 2009`
+    let plain = text.replace("\\newblock", ". ");
+    let publisher = plain
+        .split(". ")
+        .map(str::trim)
+        .filter(|sentence| !sentence.contains('§') && !IN_SENTENCE_REGEX.is_match(sentence))
+        .find(|sentence| PUBLISHER_REGEX.is_match(sentence));
+    if let Some(sentence) = publisher {
+        let sentence = sentence.rsplit(':').next().unwrap_or(sentence);
+        let sentence = YEAR_REGEX.replace_all(sentence, "");
+        let publisher = clean_field(&sentence);
+        let publisher = publisher.trim_end_matches(|c: char| c == ',' || c.is_whitespace() || c.is_ascii_digit());
+        if !publisher.is_empty() && !PAGES_REGEX.is_match(publisher) {
+            let publisher = if sentence.trim_end_matches(['.', ',', ' ']).ends_with("Inc.") || publisher.ends_with("Inc") {
+                format!("{}.", publisher.trim_end_matches('.'))
+            } else {
+                publisher.to_string()
+            };
+            details.push(("publisher", publisher));
+        }
+    }
+
+    for (field, value) in details {
+        if !value.is_empty() && entry.get(field).is_none() {
+            entry.set(field, value);
+        }
+    }
+}
+
+/// IEEEtran and IEEEtranN: `A.~Author, B.~Author, and C.~Author, ``Title,'' in ...`
+pub struct IeeeTranStyle;
+
+impl BblStyleParser for IeeeTranStyle {
+    fn name(&self) -> &'static str {
+        "IEEEtran"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        generated_by(content).is_some_and(|bst| declares_style(bst, self.name()))
+            || content.contains("\\BIBentryALTinterwordspacing")
+            || content.contains("\\providecommand{\\BIBdecl}")
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let body = item_body(item).replace("\\BIBentryALTinterwordspacing", "").replace("\\BIBentrySTDinterwordspacing", "");
+        let (author, title, rest) = match IEEE_TITLE_REGEX.captures(&body) {
+            Some(cap) => {
+                let m = cap.get(0).unwrap();
+                (Some(&body[..m.start()]), Some(clean_field(&cap[1])), &body[m.end()..])
+            }
+            None => {
+                // Books have an emphasized title and no quotes
+                match first_emphasis(&body) {
+                    Some((title, range)) => (Some(&body[..range.start]), Some(title), &body[range.end..]),
+                    None => (None, None, body.as_str()),
+                }
+            }
+        };
+        let year = YEAR_REGEX
+            .captures_iter(rest)
+            .last()
+            .map(|cap| cap[1].to_string())
+            .or_else(|| find_year(item_label(item), &body));
+        build_entry(key, author.map(clean_authors), title, year, rest)
+    }
+}
+
+/// ACM-Reference-Format: fields tagged with `\bibfield` and `\bibinfo`
+pub struct AcmStyle;
+
+impl BblStyleParser for AcmStyle {
+    fn name(&self) -> &'static str {
+        "ACM-Reference-Format"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content.contains("ACM-Reference-Format")
+            || generated_by(content).is_some_and(|bst| declares_style(bst, self.name()))
+            || (content.contains("\\bibfield") && content.contains("\\showarticletitle"))
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let author = command_argument(item, "\\bibfield{author}").and_then(|authors| {
+            let persons: Vec<&str> = PERSON_REGEX
+                .captures_iter(authors)
+                .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+                .collect();
+            (!persons.is_empty()).then(|| persons.join(" and "))
+        });
+        let title = SHOW_TITLE_REGEX
+            .captures(item)
+            .map(|cap| cap[1].to_string())
+            .or_else(|| command_argument(item, "\\bibinfo{title}").map(str::to_string))
+            .map(|title| clean_field(&title));
+        let year = command_argument(item, "\\bibinfo{year}")
+            .map(str::to_string)
+            .or_else(|| find_year(None, item));
+        let mut entry = build_entry(key, author, title, year, "");
+
+        // Everything else is tagged as well
+        for field in ["journal", "booktitle", "volume", "number", "pages", "publisher", "address", "edition"] {
+            if let Some(value) = command_argument(item, &format!("\\bibinfo{{{}}}", field)) {
+                let value = clean_field(value);
+                let value = if field == "pages" { normalize_pages(&value) } else { value };
+                entry.set(field, value);
+            }
+        }
+        let rest = SHOW_TITLE_REGEX.find(item).map_or(item, |m| &item[m.end()..]);
+        add_details(&mut entry, rest);
+        entry
+    }
+}
+
+/// plainnat, abbrvnat and unsrtnat: `Authors.\newblock Title.\newblock Venue, 2020.`
+pub struct NatbibStyle;
+
+impl BblStyleParser for NatbibStyle {
+    fn name(&self) -> &'static str {
+        "plainnat"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        if let Some(bst) = generated_by(content) {
+            return declares_style(bst, self.name());
+        }
+        // natbib styles define \natexlab and label items with `Author(Year)`
+        content.contains("\\providecommand{\\natexlab}")
+            && BIBITEM_REGEX
+                .captures(content)
+                .and_then(|cap| cap.get(1))
+                .is_some_and(|label| label.as_str().contains('('))
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let body = item_body(item);
+        let blocks = newblocks(body);
+        let author = blocks.first().map(|block| clean_authors(block));
+        let title = blocks.get(1).map(|block| {
+            let block = if blocks.len() == 2 { strip_trailing_year(block) } else { block };
+            clean_field(block)
+        });
+        let year = find_year(item_label(item), body);
+        build_entry(key, author, title, year, &blocks.get(2..).unwrap_or_default().join(" "))
+    }
+}
+
+/// apalike: `Author, A. and Other, B. (2020).\newblock Title.\newblock Venue.`
+pub struct ApaLikeStyle;
+
+impl BblStyleParser for ApaLikeStyle {
+    fn name(&self) -> &'static str {
+        "apalike"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        if let Some(bst) = generated_by(content) {
+            return declares_style(bst, self.name());
+        }
+        // apalike labels are `Author, Year` and the year follows the authors in parentheses
+        BIBITEM_REGEX
+            .captures(content)
+            .and_then(|cap| cap.get(1))
+            .is_some_and(|label| !label.as_str().contains('(') && LABEL_YEAR_REGEX.is_match(label.as_str()))
+            && !content.contains("\\providecommand{\\natexlab}")
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let body = item_body(item);
+        let blocks = newblocks(body);
+        let first = blocks.first().copied().unwrap_or_default();
+        let (author, year) = match first.rfind('(') {
+            Some(paren) => (&first[..paren], YEAR_REGEX.captures(&first[paren..]).map(|cap| cap[1].to_string())),
+            None => (first, None),
+        };
+        let year = year.or_else(|| find_year(item_label(item), body));
+        let title = blocks.get(1).map(|block| clean_field(block));
+        build_entry(key, Some(clean_authors(author)), title, year, &blocks.get(2..).unwrap_or_default().join(" "))
+    }
+}
+
+/// amsplain and amsalpha: `A.~Author and B.~Author, \emph{Title}, Journal \textbf{1} (2020), 1--10.`
+pub struct AmsPlainStyle;
+
+impl BblStyleParser for AmsPlainStyle {
+    fn name(&self) -> &'static str {
+        "amsplain"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        generated_by(content).is_some_and(|bst| declares_style(bst, self.name()))
+            || content.contains("\\providecommand{\\bysame}")
+            || content.contains("\\providecommand{\\MRhref}")
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let body = item_body(item).trim();
+        let emph = ["\\emph{", "\\textit{"]
+            .iter()
+            .filter_map(|command| body.find(command).map(|start| (start, command.len() - 1)))
+            .min();
+        let (author, title, rest) = match emph {
+            Some((start, len)) => {
+                let title = brace_group(body, start + len);
+                let rest_start = start + len + title.map_or(0, |t| t.len() + 2);
+                (&body[..start], title.map(clean_field), &body[rest_start.min(body.len())..])
+            }
+            None => (body, None, ""),
+        };
+        // `\bysame` repeats the previous item's authors; it is resolved by the caller
+        let author = if author.contains("\\bysame") {
+            "\\bysame".to_string()
+        } else {
+            clean_authors(author)
+        };
+        let year = find_year(None, rest).or_else(|| find_year(None, body));
+        let mut entry = build_entry(key, Some(author), title, year, rest);
+
+        // Journal names are not emphasized: they run up to the bold volume or the year
+        let venue_end = rest.find("\\textbf").or_else(|| rest.find(" (")).unwrap_or(rest.len());
+        let venue = clean_field(&rest[..venue_end]);
+        if entry.get("journal").is_none() && entry.get("booktitle").is_none() && !venue.is_empty() && !venue.contains(',') {
+            entry.set("journal", venue);
+        }
+        entry
+    }
+}
+
+/// Fallback for unknown styles: segments the reference string with `tag_reference`
+pub struct GenericStyle;
+
+impl GenericStyle {
+    /// Items that tag their fields with `\bibfield`/`\showarticletitle` outside an ACM `.bbl`
+    fn parse_bibfield_item(key: &str, item: &str) -> BibEntry {
+        let mut entry_builder = BibEntry::builder(key.to_string(), "misc");
+
+        if let Some(author_content) = command_argument(item, "\\bibfield{author}") {
+            // Extract individual person names from \bibinfo{person}{...} patterns
+            let authors: Vec<&str> = PERSON_REGEX
+                .captures_iter(author_content)
+                .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+                .collect();
+            if !authors.is_empty() {
+                entry_builder = entry_builder.field("author", authors.join(" and "));
+            }
+        }
+
+        if let Some(cap) = YEAR_REGEX.captures(item) {
+            entry_builder = entry_builder.field("year", cap.get(0).map_or("", |m| m.as_str()).to_string());
+        }
+
+        let trim_title = |title: &str| {
+            let clean_title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+            clean_title
+                .trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+                .to_string()
+        };
+
+        // Extract title from \showarticletitle{...} pattern first
+        let mut rest = String::new();
+        if let Some(title_cap) = SHOW_TITLE_REGEX.captures(item) {
+            entry_builder = entry_builder.field("title", trim_title(&title_cap[1]));
+            rest = item[title_cap.get(0).unwrap().end()..].to_string();
+        } else if item.contains("\\newblock") {
+            // blocks[1] is usually the title (blocks[0] is author line)
+            let blocks = newblocks(item);
+            if blocks.len() > 1 {
+                entry_builder = entry_builder.field("title", trim_title(blocks[1]));
+                rest = blocks[2..].join(" ");
+            }
+        }
+
+        let mut entry = entry_builder.build();
+        add_details(&mut entry, &rest);
+        entry
+    }
+}
+
+impl BblStyleParser for GenericStyle {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn detect(&self, _content: &str) -> bool {
+        true
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        if item.contains("\\bibfield{author}") || SHOW_TITLE_REGEX.is_match(item) {
+            return Self::parse_bibfield_item(key, item);
+        }
+
+        let body = item_body(item);
+        let tagged = tag_reference(body);
+        let author = tagged.text(ReferenceField::Author, body).map(clean_authors);
+        let title = tagged.text(ReferenceField::Title, body).map(clean_field);
+        let year = tagged
+            .text(ReferenceField::Year, body)
+            .map(str::to_string)
+            .or_else(|| find_year(item_label(item), body));
+
+        // Venue, volume and identifiers are read from what follows the title
+        let details_start = [ReferenceField::Title, ReferenceField::Author]
+            .iter()
+            .find_map(|field| tagged.span(*field))
+            .map_or(0, |span| span.range.end);
+        let mut entry = build_entry(key, author, title, year, &body[details_start..]);
+
+        if entry.get("journal").is_none() && entry.get("booktitle").is_none() {
+            if let Some(span) = tagged.span(ReferenceField::Container) {
+                let venue = clean_field(&body[span.range.clone()]);
+                let booktitle = IN_REGEX.is_match(&body[details_start.min(span.range.start)..span.range.start])
+                    || PROCEEDINGS_REGEX.is_match(&venue);
+                entry.set(if booktitle { "booktitle" } else { "journal" }, venue);
+            }
+        }
+        if entry.get("pages").is_none() {
+            if let Some(pages) = tagged.text(ReferenceField::Pages, body) {
+                entry.set("pages", normalize_pages(pages));
+            }
+        }
+        if tagged.author_guessed {
+            entry.warnings.push(ParseWarning::AuthorGuessed);
+        }
+        entry
+    }
+}
@@ -0,0 +1,342 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::latex::{BibEntry, BibEntryBuilder, Person, PersonList};
+
+// `\entry{key}{type}{options}` opening a biblatex entry
+static ENTRY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\entry\{([^}]+)\}\{([^}]+)\}\{[^}]*\}").expect("Invalid biblatex entry regex pattern")
+});
+
+// Formatting macros biber leaves in values; a control word swallows the space after it
+static FORMAT_MACRO_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(bibrangedash|bibinitperiod|bibinitdelim|bibinithyphendelim|bibnamedelim[abcdi])(?:\s|\b)")
+        .expect("Invalid biblatex macro regex pattern")
+});
+
+/// Fields biber writes for its own bookkeeping, never part of the BibTeX record
+const INTERNAL_FIELDS: [&str; 6] = ["sortinit", "label", "extra", "uniq", "urlraw", "dateera"];
+
+/// biblatex field names that have a different name in BibTeX
+const FIELD_RENAMES: [(&str, &str); 5] = [
+    ("journaltitle", "journal"),
+    ("location", "address"),
+    ("eprinttype", "archivePrefix"),
+    ("eprintclass", "primaryClass"),
+    ("origlocation", "address"),
+];
+
+/// Entry types BibTeX understands; every other biblatex type is written as `misc`
+const BIBTEX_TYPES: [&str; 13] = [
+    "article",
+    "book",
+    "booklet",
+    "inbook",
+    "incollection",
+    "inproceedings",
+    "manual",
+    "mastersthesis",
+    "misc",
+    "phdthesis",
+    "proceedings",
+    "techreport",
+    "unpublished",
+];
+
+/// Returns true if the content is a `.bbl` file written by biber for biblatex
+pub fn is_biblatex_bbl(content: &str) -> bool {
+    !content.contains("\\begin{thebibliography}")
+        && (content.contains("\\datalist") || content.contains("\\refsection"))
+        && ENTRY_REGEX.is_match(content)
+}
+
+/// Parse the `\entry ... \endentry` blocks of a biblatex `.bbl` file.
+///
+/// biber writes every entry in structured form, so fields map directly onto BibTeX:
+/// `\field` and `\list` values are copied, `\name` lists are written as
+/// "Last, First and ..." lists and `\verb` fields (DOI, eprint, URL) are kept verbatim.
+/// When a file holds several data lists, the first occurrence of each key wins.
+pub fn parse_biblatex_bbl(content: &str) -> Vec<BibEntry> {
+    let mut entries: Vec<BibEntry> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for cap in ENTRY_REGEX.captures_iter(content) {
+        let key = cap[1].trim().to_string();
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        let body_start = cap.get(0).unwrap().end();
+        let body_end = content[body_start..]
+            .find("\\endentry")
+            .map(|end| body_start + end)
+            .unwrap_or(content.len());
+        entries.push(parse_entry(key, &cap[2], &content[body_start..body_end]));
+    }
+
+    entries
+}
+
+/// Convert the body of one `\entry` block into a BibEntry
+fn parse_entry(key: String, biblatex_type: &str, body: &str) -> BibEntry {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut names: HashMap<String, PersonList> = HashMap::new();
+    let mut cursor = Cursor::new(body);
+
+    while let Some(command) = cursor.next_command() {
+        match command {
+            "field" => {
+                let (Some(name), Some(value)) = (cursor.read_group(), cursor.read_group()) else {
+                    continue;
+                };
+                if !INTERNAL_FIELDS.iter().any(|prefix| name.starts_with(prefix)) {
+                    fields.insert(bibtex_field_name(name), clean_value(value));
+                }
+            }
+            "list" => {
+                let (Some(name), Some(_count), Some(items)) =
+                    (cursor.read_group(), cursor.read_group(), cursor.read_group())
+                else {
+                    continue;
+                };
+                let items: Vec<String> = top_level_groups(items).into_iter().map(clean_value).collect();
+                if !items.is_empty() {
+                    fields.insert(bibtex_field_name(name), items.join(" and "));
+                }
+            }
+            "name" => {
+                let (Some(role), Some(_count), Some(_options), Some(list)) =
+                    (cursor.read_group(), cursor.read_group(), cursor.read_group(), cursor.read_group())
+                else {
+                    continue;
+                };
+                let persons = top_level_groups(list).into_iter().filter_map(parse_name).collect();
+                names.entry(role.to_string()).or_default().persons = persons;
+            }
+            "verb" => {
+                if let Some(name) = cursor.read_group() {
+                    let value = cursor.read_verbatim();
+                    if !INTERNAL_FIELDS.contains(&name) && !value.is_empty() {
+                        fields.insert(bibtex_field_name(name), value);
+                    }
+                }
+            }
+            // `\true{moreauthor}` marks a name list truncated with "and others"
+            "true" => {
+                if let Some(role) = cursor.read_group().and_then(|flag| flag.strip_prefix("more")) {
+                    names.entry(role.to_string()).or_default().is_truncated = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (role, persons) in names {
+        if !persons.is_empty() {
+            fields.insert(role, persons.to_bibtex());
+        }
+    }
+
+    // Subtitles are part of the BibTeX title
+    if let Some(subtitle) = fields.remove("subtitle") {
+        if let Some(title) = fields.get_mut("title") {
+            title.push_str(": ");
+            title.push_str(&subtitle);
+        }
+    }
+
+    let entry_type = bibtex_entry_type(biblatex_type, fields.get("type").map(String::as_str));
+    // biblatex names the school of a thesis its institution
+    if entry_type.ends_with("thesis") {
+        if let Some(institution) = fields.remove("institution") {
+            fields.insert("school".to_string(), institution);
+        }
+    }
+    BibEntryBuilder::new(key, entry_type).fields(fields).build()
+}
+
+/// Map a biblatex field name onto its BibTeX name
+fn bibtex_field_name(name: &str) -> String {
+    FIELD_RENAMES
+        .iter()
+        .find(|(biblatex, _)| *biblatex == name)
+        .map(|(_, bibtex)| bibtex.to_string())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Map a biblatex entry type onto the closest BibTeX entry type
+fn bibtex_entry_type(biblatex_type: &str, thesis_type: Option<&str>) -> String {
+    let entry_type = biblatex_type.to_lowercase();
+    match entry_type.as_str() {
+        "report" => "techreport".to_string(),
+        "thesis" => match thesis_type {
+            Some(kind) if kind.contains("ma") => "mastersthesis".to_string(),
+            _ => "phdthesis".to_string(),
+        },
+        "mvbook" => "book".to_string(),
+        "mvproceedings" => "proceedings".to_string(),
+        "mvcollection" | "collection" => "book".to_string(),
+        _ if BIBTEX_TYPES.contains(&entry_type.as_str()) => entry_type,
+        _ => "misc".to_string(),
+    }
+}
+
+/// Turn one `{{options}{parts}}` name item into a Person
+fn parse_name(item: &str) -> Option<Person> {
+    let groups = top_level_groups(item);
+    let parts = match groups.as_slice() {
+        // biber >= 2.x: `{hash=...}{family={...}, given={...}, ...}`
+        [_, parts] if parts.contains('=') => parse_key_values(parts),
+        // Older biber: positional `{hash}{family}{familyi}{given}{giveni}{prefix}{prefixi}{suffix}{suffixi}`
+        [_, rest @ ..] if !rest.is_empty() => {
+            let positional = ["family", "familyi", "given", "giveni", "prefix", "prefixi", "suffix", "suffixi"];
+            positional
+                .iter()
+                .zip(rest.iter())
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        }
+        _ => return None,
+    };
+
+    let part = |name: &str| {
+        parts
+            .get(name)
+            .map(|value| clean_value(value))
+            .filter(|value| !value.is_empty())
+    };
+    Some(Person {
+        first: part("given").unwrap_or_default(),
+        von: part("prefix").unwrap_or_default(),
+        last: part("family")?,
+        jr: part("suffix").unwrap_or_default(),
+    })
+}
+
+/// Parse a `key={value}, key={value}` list as written inside biber name parts
+fn parse_key_values(content: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut cursor = Cursor::new(content);
+    loop {
+        cursor.skip_separators();
+        let start = cursor.pos;
+        while cursor.peek().is_some_and(|b| b != b'=' && b != b',') {
+            cursor.pos += 1;
+        }
+        let key = content[start..cursor.pos].trim().to_string();
+        if cursor.peek() != Some(b'=') {
+            break;
+        }
+        cursor.pos += 1;
+        match cursor.read_group() {
+            Some(value) => {
+                values.insert(key, value.to_string());
+            }
+            None => break,
+        }
+    }
+    values
+}
+
+/// Collect the contents of the top-level `{...}` groups in `content`
+fn top_level_groups(content: &str) -> Vec<&str> {
+    let mut groups = Vec::new();
+    let mut cursor = Cursor::new(content);
+    while let Some(group) = cursor.read_group() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Replace the biblatex formatting macros left in field values and collapse whitespace
+fn clean_value(value: &str) -> String {
+    let value = FORMAT_MACRO_REGEX.replace_all(value, |cap: &regex::Captures| match &cap[1] {
+        "bibrangedash" => "--",
+        "bibinitperiod" => ".",
+        "bibinithyphendelim" => "-",
+        _ => " ",
+    });
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Byte cursor over the body of a biblatex entry, also used for amsrefs records
+pub(crate) struct Cursor<'a> {
+    pub(crate) src: &'a str,
+    pub(crate) pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Cursor { src, pos: 0 }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skip whitespace, separating commas and `%` line comments
+    pub(crate) fn skip_separators(&mut self) {
+        while let Some(b) = self.peek() {
+            match b {
+                b'%' => {
+                    while self.peek().is_some_and(|b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b',' => self.pos += 1,
+                _ if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Move to the next control sequence and return its name
+    fn next_command(&mut self) -> Option<&'a str> {
+        let offset = self.src.get(self.pos..)?.find('\\')?;
+        self.pos += offset + 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        Some(&self.src[start..self.pos])
+    }
+
+    /// Read the next `{...}` group, skipping separators before it
+    pub(crate) fn read_group(&mut self) -> Option<&'a str> {
+        self.skip_separators();
+        if self.peek()? != b'{' {
+            return None;
+        }
+        self.pos += 1;
+        let start = self.pos;
+        let mut depth = 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => self.pos += 1,
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&self.src[start..self.pos - 1]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Read the `\verb <text>` lines of a verbatim field up to `\endverb`
+    fn read_verbatim(&mut self) -> String {
+        let rest = &self.src[self.pos..];
+        let end = rest.find("\\endverb").unwrap_or(rest.len());
+        self.pos += end;
+        rest[..end]
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("\\verb"))
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("")
+    }
+}
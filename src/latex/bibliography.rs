@@ -1,18 +1,55 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::latex::{clean_text, CITE_REGEX, ARXIV_ID_REGEX, ARXIV_KEY_REGEX};
+use crate::latex::bbl_scanner::{BblItem, BblScanner};
+use crate::latex::bbl_style::{detect_bbl_style, infer_entry_type, BblStyleParser};
+use crate::latex::diagnostics::{diagnose_entry, ParseWarning};
+use crate::latex::parser::LATEX_COMMENT_REGEX;
+use crate::latex::{amsrefs, biblatex, bibtex, clean_text, reference_env, PersonList, CITE_REGEX, ARXIV_ID_REGEX, ARXIV_KEY_REGEX, ANY_CITE_REGEX, DOI_REGEX};
 use crate::error::BibExtractError;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
+// A complete reference list environment, as written by hand in the LaTeX source
+static REFERENCE_LIST_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?s)\\begin\{thebibliography\}.*?\\end\{thebibliography\}",
+        r"|\\begin\{biblist\*?\}.*?\\end\{biblist\*?\}",
+        r"|\\begin\{(?:references|thereferences|harvard)\}.*?\\end\{(?:references|thereferences|harvard)\}",
+    ))
+    .expect("Invalid reference list regex pattern")
+});
+
+// Escaped punctuation such as `\_` or `{\_}`, which DOIs and URLs often contain
+static TEX_ESCAPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{?\\([_%&#$])\}?").expect("Invalid TeX escape regex pattern")
+});
+
+/// Where the value of a field was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSource {
+    /// The author's `.bib` database shipped with the source
+    Bib,
+    /// Heuristically extracted from a compiled `.bbl` file or a reference list in the source
+    Bbl,
+    /// Looked up during verification (see the `verified_source` field)
+    Verified,
+}
 
 /// Custom bibliography entry structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct BibEntry {
     pub key: String,
     pub entry_type: String,
     pub fields: HashMap<String, String>,
+    /// Provenance of each field, when known
+    pub sources: HashMap<String, FieldSource>,
+    /// Provenance of the entry type, when known
+    pub type_source: Option<FieldSource>,
+    /// Problems found while parsing the entry
+    pub warnings: Vec<ParseWarning>,
 }
 
 /// Builder for BibEntry to allow for cleaner creation
@@ -57,6 +94,9 @@ impl BibEntryBuilder {
             key: self.key,
             entry_type: self.entry_type,
             fields: self.fields,
+            sources: HashMap::new(),
+            type_source: None,
+            warnings: Vec::new(),
         }
     }
 }
@@ -67,6 +107,9 @@ impl BibEntry {
             key,
             entry_type,
             fields: HashMap::new(),
+            sources: HashMap::new(),
+            type_source: None,
+            warnings: Vec::new(),
         }
     }
     
@@ -82,6 +125,135 @@ impl BibEntry {
     pub fn get(&self, field: &str) -> Option<&String> {
         self.fields.get(field)
     }
+
+    /// Parse the `author` field, which is kept as a BibTeX "and"-list
+    pub fn authors(&self) -> PersonList {
+        self.get("author")
+            .map(|authors| PersonList::parse_bibtex(authors))
+            .unwrap_or_default()
+    }
+
+    /// Write a list of people back to a name field as a BibTeX "and"-list
+    pub fn set_persons(&mut self, field: &str, persons: &PersonList) {
+        if persons.is_empty() {
+            self.fields.remove(field);
+        } else {
+            self.set(field, persons.to_bibtex());
+        }
+    }
+
+    /// Parse confidence between 0 and 1, lowered by each parse warning
+    pub fn confidence(&self) -> f32 {
+        let penalty: f32 = self.warnings.iter().map(|warning| warning.penalty()).sum();
+        (1.0 - penalty).max(0.0)
+    }
+
+    /// Set a field and record where its value came from
+    pub fn set_with_source(&mut self, field: &str, value: String, source: FieldSource) {
+        self.fields.insert(field.to_string(), value);
+        self.sources.insert(field.to_string(), source);
+    }
+
+    /// Get the provenance of a field
+    pub fn source(&self, field: &str) -> Option<FieldSource> {
+        self.sources.get(field).copied()
+    }
+
+    /// Record `source` as the provenance of the entry type and of every field that does not have one yet
+    pub fn mark_source(&mut self, source: FieldSource) {
+        for field in self.fields.keys() {
+            self.sources.entry(field.clone()).or_insert(source);
+        }
+        self.type_source.get_or_insert(source);
+    }
+
+    /// Returns true if both entries describe the same work: identical items or titles
+    pub fn is_same_work(&self, other: &BibEntry) -> bool {
+        let same = |field: &str| match (self.get(field), other.get(field)) {
+            (Some(a), Some(b)) => clean_text(a) == clean_text(b),
+            _ => false,
+        };
+        same("raw") || same("title") || (self.get("title").is_none() && other.get("title").is_none())
+    }
+
+    /// Copy fields missing from this entry out of another entry for the same work
+    pub fn fill_missing_from(&mut self, other: &BibEntry) {
+        for (field, value) in &other.fields {
+            if !self.fields.contains_key(field) {
+                self.fields.insert(field.clone(), value.clone());
+                if let Some(source) = other.source(field) {
+                    self.sources.insert(field.clone(), source);
+                }
+            }
+        }
+    }
+}
+
+/// The citations the entries of a `.bbl` file resolve
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum BblScope {
+    /// Every citation of the document
+    #[default]
+    Document,
+    /// The `\cite<name>` citations of a multibib list, written to `<name>.bbl`
+    List(String),
+    /// The citations in the text of a chapterbib chapter, this included file
+    Chapter(PathBuf),
+}
+
+/// The entries of one `.bbl` file, with its own key map, and the citations they resolve
+#[derive(Debug)]
+pub struct BblUnit {
+    pub path: PathBuf,
+    pub scope: BblScope,
+    pub bibliography: Bibliography,
+}
+
+impl BblUnit {
+    /// Parse a `.bbl` file. Missing files give `None`, and so do unparsable ones, with a warning.
+    pub fn parse(path: PathBuf, scope: BblScope) -> Result<Option<Self>, BibExtractError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).map_err(BibExtractError::IoError)?;
+        match Bibliography::parse_bbl(&content) {
+            Ok(bibliography) => Ok(Some(Self { path, scope, bibliography })),
+            Err(e) => {
+                log::warn!("Failed to parse BBL file {:?}: {}", path, e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// The key an entry of this unit is kept under when an earlier unit declares its key
+    /// for another work: `<file name>:<key>`
+    pub fn scoped_key(&self, key: &str) -> String {
+        let name = self.path.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+        format!("{}:{}", name, key)
+    }
+}
+
+/// A citation key declared for different works by two bibliography files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub key: String,
+    /// The file whose entry keeps the key
+    pub kept: PathBuf,
+    /// The later file declaring the key for another work
+    pub other: PathBuf,
+    /// The key the entry of the later file is kept under, when the file resolves citations
+    /// of its own (see `BblScope`); otherwise that entry is ignored
+    pub renamed: Option<String>,
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Citation key {} is declared by {:?} and, for a different work, by {:?}; ", self.key, self.kept, self.other)?;
+        match &self.renamed {
+            Some(renamed) => write!(f, "the latter is kept as {} for its own citations", renamed),
+            None => write!(f, "keeping the first"),
+        }
+    }
 }
 
 /// Bibliography collection
@@ -118,219 +290,252 @@ impl Bibliography {
         self.entries.values()
     }
     
-    /// Parse a BBL file into Bibliography structure
+    /// Parse a BBL file into Bibliography structure, detecting the bibliography style.
+    /// biblatex and amsrefs bibliographies are read as structured records, and the
+    /// reference environments of journal classes like `thebibliography`.
     pub fn parse_bbl(content: &str) -> Result<Self, BibExtractError> {
-        let mut bibliography = Self::new();
-        if !content.contains("\\begin{thebibliography}") {
+        let structured = if biblatex::is_biblatex_bbl(content) {
+            Some(biblatex::parse_biblatex_bbl(content))
+        } else if amsrefs::is_amsrefs(content) {
+            Some(amsrefs::parse_amsrefs(content))
+        } else {
+            None
+        };
+        if let Some(entries) = structured {
+            let mut bibliography = Self::new();
+            for mut entry in entries {
+                entry.mark_source(FieldSource::Bbl);
+                diagnose_entry(&mut entry);
+                bibliography.insert(entry);
+            }
             return Ok(bibliography);
         }
+        if !content.contains("\\begin{thebibliography}") {
+            if let Some(converted) = reference_env::to_thebibliography(content) {
+                return Self::parse_bbl(&converted);
+            }
+        }
+        let style = detect_bbl_style(content);
+        log::debug!("Parsing BBL file with the {} style parser", style.name());
+        Self::parse_bbl_with_style(content, style)
+    }
 
-        let bib_content = content
-            .split("\\begin{thebibliography}")
-            .nth(1)
-            .unwrap_or("")
-            .split("\\end{thebibliography}")
-            .next()
-            .unwrap_or("");
- 
-        // Normalize the content to handle multi-line bibitem entries
-        // Replace patterns like "\bibitem[...]%\n        {key}" with "\bibitem[...]{key}"
-        let normalized_content = regex::Regex::new(r"\\bibitem(\[[^\]]*\])?%\s*\n\s*")
-            .unwrap()
-            .replace_all(bib_content, r"\bibitem$1");
-        
-        // Handle both single-line and multi-line bibitem formats
-        let bibitem_re = regex::Regex::new(r"\\bibitem(?:\[[^\]]*\])?\{([^}]+)\}").unwrap();
-
-        // Collect positions of all \bibitem occurrences
-        let mut positions = Vec::new();
-        for m in bibitem_re.find_iter(&normalized_content) {
-            positions.push(m.start());
-        }
-
-        // Extract each bibitem block by slicing from start of one to start of next
-        for (i, &start) in positions.iter().enumerate() {
-            let end = positions.get(i + 1).copied().unwrap_or_else(|| normalized_content.len());
-            let item = &normalized_content[start..end];
-
-            // Extract key from the bibitem
-            let key = bibitem_re.captures(item)
-                .and_then(|cap| cap.get(1))
-                .map(|m| m.as_str())
-                .unwrap_or("unknown");
-
-            let mut entry_builder = BibEntry::builder(key.to_string(), "article");
-
-            // For entries with \bibfield{author} structure, extract author differently
-            if item.contains("\\bibfield{author}") {
-                // Extract author from \bibfield{author}{...} pattern with proper brace matching
-                if let Some(start_pos) = item.find("\\bibfield{author}{") {
-                    let content_start = start_pos + "\\bibfield{author}{".len();
-                    let mut brace_count = 1;
-                    let mut end_pos = content_start;
-                    
-                    let chars: Vec<char> = item[content_start..].chars().collect();
-                    for (i, &ch) in chars.iter().enumerate() {
-                        if ch == '{' {
-                            brace_count += 1;
-                        } else if ch == '}' {
-                            brace_count -= 1;
-                            if brace_count == 0 {
-                                end_pos = content_start + i;
-                                break;
-                            }
-                        }
-                    }
-                    
-                    if brace_count == 0 {
-                        let author_content = &item[content_start..end_pos];
-                        
-                        // Extract individual person names from \bibinfo{person}{...} patterns
-                        let person_re = regex::Regex::new(r"\\bibinfo\{person\}\{([^}]+)\}").unwrap();
-                        let mut authors = Vec::new();
-                        for person_cap in person_re.captures_iter(author_content) {
-                            if let Some(person) = person_cap.get(1) {
-                                authors.push(person.as_str());
-                            }
-                        }
-                        
-                        if !authors.is_empty() {
-                            // Join authors with commas and handle "and" connector
-                            let author_string = authors.join(", ");
-                            entry_builder = entry_builder.field("author", author_string);
-                        }
-                    }
-                }
-            } else {
-                // Fallback to original author extraction logic
-                let lines: Vec<&str> = item.trim().lines().collect();
-                if lines.len() > 1 {
-                    // et al handling
-                    let author_line = lines[1].trim();
-                    if author_line.contains("et~al.") || author_line.contains("et al.") {
-                        // If "et al." is present, we take the string until "et al."
-                        let et_al_index = author_line.find("et al.").or_else(|| author_line.find("et~al.")).unwrap_or(author_line.len());
-                        let author = &author_line[..et_al_index].trim();
-                        entry_builder = entry_builder.field("author", author.to_string());
-                    } else {
-                        // Otherwise, we take the full author line
-                        entry_builder = entry_builder.field("author", author_line.to_string());
+    /// Parse the `thebibliography` block of a BBL file with a specific style parser.
+    /// The items are streamed from the block in one pass; see `BblScanner`.
+    pub fn parse_bbl_with_style(content: &str, style: &dyn BblStyleParser) -> Result<Self, BibExtractError> {
+        let mut bibliography = Self::new();
+        let mut previous_author: Option<String> = None;
+        for BblItem { key, text: item, .. } in BblScanner::new(content) {
+            let mut entry = style.parse_item(key, item);
+
+            // `\bysame` stands for the authors of the previous item
+            if entry.get("author").is_some_and(|author| author == "\\bysame") {
+                match &previous_author {
+                    Some(author) => entry.set("author", author.clone()),
+                    None => {
+                        entry.fields.remove("author");
                     }
                 }
             }
+            previous_author = entry.get("author").cloned();
 
-            let year_re = regex::Regex::new(r"\b(19\d{2}|20\d{2})\b").unwrap();
-            if let Some(cap) = year_re.captures(item) {
-                entry_builder = entry_builder.field("year", cap.get(0).map_or("", |m| m.as_str()).to_string());
-            }
+            // Styles do not say what kind of work an item is; the venue and wording do
+            entry.entry_type = infer_entry_type(&entry, item).to_string();
 
-            // Extract title from \showarticletitle{...} pattern first
-            let show_title_re = regex::Regex::new(r"\\showarticletitle\{([^}]+)\}").unwrap();
-            if let Some(title_cap) = show_title_re.captures(item) {
-                let title = title_cap.get(1).map_or("", |m| m.as_str());
-                let clean_title = title.split_whitespace().collect::<Vec<_>>().join(" ");
-                let clean_title = clean_title.trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace());
-                entry_builder = entry_builder.field("title", clean_title);
-            }
-            // if newblock is present, split the item into blocks
-            else if item.contains("\\newblock") {
-                let blocks: Vec<&str> = item.split("\\newblock").map(str::trim).filter(|s| !s.is_empty()).collect();
-                if blocks.len() > 1 {
-                    // blocks[1] is usually the title (blocks[0] is author line)
-                    let raw_title = blocks[1].replace('\n', " ");
-                    let clean_title = raw_title.split_whitespace().collect::<Vec<_>>().join(" ");
-                    let clean_title = clean_title.trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace());
-                    entry_builder = entry_builder.field("title", clean_title);
-                }
-            }
-            else {
-                // if no newblock, try to extract title from quotes
-                // get title from quotations if no newblock                
-                // Use regex to find quoted title
-                let title_re = regex::Regex::new(r#"(?:``|\"|\')([^\`\"']+)(?:\'\'|\"|\')"#).unwrap();
-                if let Some(title_cap) = title_re.captures(item) {
-                    let title = title_cap.get(1).map_or("", |m| m.as_str());
-                    let clean_title = title.split_whitespace().collect::<Vec<_>>().join(" ");
-                    // strip leading/trailing whitespace and punctuation
-                    let clean_title = clean_title.trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace());
-                    entry_builder = entry_builder.field("title", clean_title);
-                }
+            // Author lists are typeset differently by every style
+            if let Some(authors) = entry.get("author").map(|authors| PersonList::parse(authors)) {
+                entry.set_persons("author", &authors);
             }
 
             // add item as raw field so that we can parse for arXiv IDs or DOI later
-            entry_builder = entry_builder.field("raw", item.trim().to_string());
-
-            bibliography.insert(entry_builder.build());
+            entry.set("raw", item.trim().to_string());
+            entry.mark_source(FieldSource::Bbl);
+            diagnose_entry(&mut entry);
+            bibliography.insert(entry);
         }
         
         Ok(bibliography)
     }
     
-    /// Parse all bibliography files from a list and consolidate them
+    /// Parse all bibliography files from a list and consolidate them.
+    /// Conflicting duplicate keys are logged; see `parse_bibliography_files_with_conflicts`.
     pub fn parse_bibliography_files(bbl_files: &[PathBuf]) -> Result<Self, BibExtractError> {
-        let mut consolidated_biblio = Self::new();
+        let (consolidated_biblio, conflicts) = Self::parse_bibliography_files_with_conflicts(bbl_files)?;
+        for conflict in &conflicts {
+            log::warn!("{}", conflict);
+        }
+        Ok(consolidated_biblio)
+    }
 
+    /// Parse all bibliography files from a list and consolidate them, in order.
+    ///
+    /// A key keeps the entry of the first file declaring it. A later file declaring the same
+    /// key for a different work (another title) is reported as a conflict instead of
+    /// overwriting the entry.
+    pub fn parse_bibliography_files_with_conflicts(
+        bbl_files: &[PathBuf],
+    ) -> Result<(Self, Vec<KeyConflict>), BibExtractError> {
         log::info!("Parsing {} BBL files", bbl_files.len());
-
-        // Parse bibliography files if they exist
+        let mut units = Vec::new();
         for bbl_file in bbl_files {
-            if bbl_file.exists() {
-                let content = fs::read_to_string(bbl_file).map_err(|e| BibExtractError::IoError(e))?;
-                // Using custom BBL parser
-                match Self::parse_bbl(&content) {
-                    Ok(bib) => {
-                        // Add all entries to our consolidated bibliography
-                        for entry in bib.iter() {
-                            consolidated_biblio.insert(entry.clone());
+            units.extend(BblUnit::parse(bbl_file.clone(), BblScope::Document)?);
+        }
+        Ok(Self::consolidate_bbl_units(units))
+    }
+
+    /// Consolidate the entries of `.bbl` files, in order.
+    ///
+    /// A key keeps the entry of the first file declaring it. When a later file declares it
+    /// for a different work, the file's entry is kept under `BblUnit::scoped_key` if the file
+    /// resolves citations of its own (a multibib list or a chapterbib chapter), and ignored
+    /// otherwise; both cases are reported as conflicts.
+    pub fn consolidate_bbl_units(units: Vec<BblUnit>) -> (Self, Vec<KeyConflict>) {
+        let mut consolidated_biblio = Self::new();
+        let mut origins: HashMap<String, PathBuf> = HashMap::new();
+        let mut conflicts = Vec::new();
+
+        for mut unit in units {
+            let scoped = unit.scope != BblScope::Document;
+            for mut entry in std::mem::take(&mut unit.bibliography.entries).into_values() {
+                match consolidated_biblio.get(&entry.key) {
+                    Some(existing) => {
+                        if existing.is_same_work(&entry) {
+                            continue;
                         }
-                    },
-                    Err(e) => {
-                        log::warn!("Failed to parse BBL file {:?}: {}", bbl_file, e);
+                        let renamed = scoped.then(|| unit.scoped_key(&entry.key));
+                        conflicts.push(KeyConflict {
+                            key: entry.key.clone(),
+                            kept: origins[&entry.key].clone(),
+                            other: unit.path.clone(),
+                            renamed: renamed.clone(),
+                        });
+                        if let Some(renamed) = renamed {
+                            entry.key = renamed;
+                            consolidated_biblio.insert(entry);
+                        }
+                    }
+                    None => {
+                        origins.insert(entry.key.clone(), unit.path.clone());
+                        consolidated_biblio.insert(entry);
                     }
                 }
             }
         }
 
-        Ok(consolidated_biblio)
+        conflicts.sort_by(|a, b| a.key.cmp(&b.key));
+        (consolidated_biblio, conflicts)
     }
     
+    /// Parse the reference lists written directly in the LaTeX source: `thebibliography`,
+    /// amsrefs `biblist` and the `references` environments of journal classes.
+    ///
+    /// Papers without a `.bbl` file often keep their bibliography in the main file or in an
+    /// `\input`ed one. Commented-out environments are skipped; when several environments
+    /// declare the same key, the first one wins.
+    pub fn parse_embedded_bibliography(latex: &str) -> Result<Self, BibExtractError> {
+        let mut bibliography = Self::new();
+        if !latex.contains("\\begin{") {
+            return Ok(bibliography);
+        }
+
+        // Keep the `%` itself: `\bibitem[...]%` line breaks are part of the item syntax
+        let uncommented = LATEX_COMMENT_REGEX.replace_all(latex, "$1%");
+        for block in REFERENCE_LIST_REGEX.find_iter(&uncommented) {
+            for entry in Self::parse_bbl(block.as_str())?.entries.into_values() {
+                bibliography.entries.entry(entry.key.clone()).or_insert(entry);
+            }
+        }
+        Ok(bibliography)
+    }
+
+    /// Parse a `.bib` database into Bibliography structure
+    pub fn parse_bib(content: &str) -> Result<Self, BibExtractError> {
+        let mut bibliography = Self::new();
+        for mut entry in bibtex::parse_bibtex(content) {
+            for field in ["author", "editor"] {
+                if let Some(persons) = entry.get(field).map(|names| PersonList::parse_bibtex(names)) {
+                    entry.set_persons(field, &persons);
+                }
+            }
+            entry.mark_source(FieldSource::Bib);
+            bibliography.insert(entry);
+        }
+        Ok(bibliography)
+    }
+
+    /// Build the bibliography of a document from its `.bib` databases, `.bbl` files and the
+    /// `thebibliography` environments in its source.
+    ///
+    /// Entries from the `.bib` databases are authoritative. Only the ones cited in `latex`
+    /// (or listed in a `.bbl`) are kept, unless the document uses `\nocite{*}`. `.bbl`
+    /// entries fill in missing fields and entries missing from the databases, and entries
+    /// written by hand in the source fill in keys missing from the `.bbl` files.
+    pub fn parse_bibliography_sources(
+        bib_databases: &[String],
+        bbl_files: &[PathBuf],
+        latex: &str,
+    ) -> Result<Self, BibExtractError> {
+        Self::combine_sources(bib_databases, Self::parse_bibliography_files(bbl_files)?, latex)
+    }
+
+    /// Build the bibliography of a document as `parse_bibliography_sources` does, from its
+    /// already consolidated `.bbl` entries
+    pub fn combine_sources(
+        bib_databases: &[String],
+        mut bbl_bibliography: Bibliography,
+        latex: &str,
+    ) -> Result<Self, BibExtractError> {
+        let embedded = Self::parse_embedded_bibliography(latex)?;
+        if !embedded.entries.is_empty() {
+            log::info!("Found {} entries in thebibliography environments of the source", embedded.entries.len());
+        }
+        for entry in embedded.entries.into_values() {
+            bbl_bibliography.entries.entry(entry.key.clone()).or_insert(entry);
+        }
+        if bib_databases.is_empty() {
+            return Ok(bbl_bibliography);
+        }
+
+        log::info!("Parsing {} BibTeX databases", bib_databases.len());
+        let mut bibliography = Self::new();
+        for content in bib_databases {
+            for entry in Self::parse_bib(content)?.entries.into_values() {
+                // The first database declaring a key wins, as in BibTeX
+                bibliography.entries.entry(entry.key.clone()).or_insert(entry);
+            }
+        }
+
+        let cited = cited_keys(latex);
+        if !cited.contains("*") {
+            bibliography
+                .entries
+                .retain(|key, _| cited.contains(key.as_str()) || bbl_bibliography.entries.contains_key(key));
+        }
+
+        for bbl_entry in bbl_bibliography.entries.into_values() {
+            match bibliography.entries.get_mut(&bbl_entry.key) {
+                Some(entry) => entry.fill_missing_from(&bbl_entry),
+                None => bibliography.insert(bbl_entry),
+            }
+        }
+
+        Ok(bibliography)
+    }
+
     /// Normalize a citation key based on BibEntry data
     pub fn normalize_citation_key(&self, entry: &BibEntry) -> String {
-        // Get the author's last name (first author if multiple)
-        let author = entry.get("author")
-            .map(|authors| {
-                // Extract the first author
-                let first_author = if authors.contains(",") {
-                    authors.split(",").next().unwrap_or(authors)
-                } else if authors.contains(" and ") {
-                    authors.split(" and ").next().unwrap_or(authors)
-                } else {
-                    authors
-                };
-                
-                // Remove "et al." if present
-                let first_author = first_author.split("et al")
-                    .next()
-                    .unwrap_or(first_author)
-                    .trim();
-                
-                // Clean and extract just the last name
-                let clean_first_author = clean_text(first_author);
-                let words: Vec<&str> = clean_first_author.split('_').collect();
-                
-                // Return the last word (likely the last name) or the whole name if only one word
-                if words.len() > 1 {
-                    words.last().unwrap_or(&"unknown").to_string()
-                } else {
-                    clean_first_author
-                }
-            })
+        // Get the first author's last name
+        let author = entry
+            .authors()
+            .first()
+            .map(|person| clean_text(&person.last))
+            .filter(|last| !last.is_empty())
             .unwrap_or_else(|| "unknown".to_string());
         
         // Get the year
         let year = entry.get("year")
             .map(|y| clean_text(y))
-            .unwrap_or_else(String::new);
+            .unwrap_or_default();
         
         // Get significant words from title
         let title_words = entry.get("title")
@@ -343,7 +548,7 @@ impl Bibliography {
                     .map(|s| s.to_string())
                     .collect::<Vec<String>>()
             })
-            .unwrap_or_else(Vec::new);
+            .unwrap_or_default();
         
         // Build the normalized key: lastname_word1_word2_word3_year
         let mut key_parts = vec![author];
@@ -362,6 +567,17 @@ impl Bibliography {
     
     /// Extract arXiv ID from a paper title or entry fields
     pub fn extract_arxiv_id(&self, entry: &BibEntry) -> Option<String> {
+        // Structured eprint fields (biblatex, arXiv BibTeX) carry the bare ID
+        let arxiv_eprint = entry
+            .get("archivePrefix")
+            .or_else(|| entry.get("archiveprefix"))
+            .is_none_or(|prefix| prefix.eq_ignore_ascii_case("arxiv"));
+        if let Some(eprint) = entry.get("eprint").filter(|_| arxiv_eprint) {
+            if let Some(captures) = ARXIV_KEY_REGEX.captures(eprint.trim()) {
+                return Some(captures[1].to_string());
+            }
+        }
+
         // Check if the title or journal field contains "arXiv" followed by an ID pattern
         let fields_to_check = ["title", "journal", "note", "raw"];
         
@@ -386,6 +602,18 @@ impl Bibliography {
         None
     }
     
+    /// Extract a DOI from the entry's `doi` field, its URL or the raw bibitem, without TeX escapes
+    pub fn extract_doi(&self, entry: &BibEntry) -> Option<String> {
+        for field in ["doi", "url", "note", "raw"] {
+            let unescaped = entry.get(field).map(|content| TEX_ESCAPE_REGEX.replace_all(content, "$1"));
+            if let Some(captures) = unescaped.as_deref().and_then(|content| DOI_REGEX.captures(content)) {
+                // A DOI ending a sentence keeps the sentence's period
+                return Some(captures[1].trim_end_matches('.').to_string());
+            }
+        }
+        None
+    }
+
     /// Normalize citation keys in LaTeX content
     pub fn normalize_citations(
         &self,
@@ -423,4 +651,15 @@ impl Bibliography {
         
         Ok((normalized_content, key_map))
     }
-}
\ No newline at end of file
+}
+
+/// Collect every key cited in LaTeX content with any `\cite`-like or `\nocite` command
+pub fn cited_keys(latex: &str) -> HashSet<&str> {
+    ANY_CITE_REGEX
+        .captures_iter(latex)
+        .filter_map(|cap| cap.get(1))
+        .flat_map(|m| m.as_str().split(','))
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .collect()
+}
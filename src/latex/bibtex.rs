@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use crate::latex::{BibEntry, BibEntryBuilder};
+
+/// Standard BibTeX month macros
+const MONTH_MACROS: [(&str, &str); 12] = [
+    ("jan", "January"),
+    ("feb", "February"),
+    ("mar", "March"),
+    ("apr", "April"),
+    ("may", "May"),
+    ("jun", "June"),
+    ("jul", "July"),
+    ("aug", "August"),
+    ("sep", "September"),
+    ("oct", "October"),
+    ("nov", "November"),
+    ("dec", "December"),
+];
+
+/// Parse a whole `.bib` database into entries.
+///
+/// Unlike `bibparser`, this reader tolerates what real-world databases contain: `@string`
+/// macros, `#` concatenation, bare numbers and month names, `@comment`/`@preamble` blocks and
+/// free text between entries. Malformed entries are skipped. Field names are lowercased and
+/// whitespace inside values is collapsed.
+pub fn parse_bibtex(content: &str) -> Vec<BibEntry> {
+    let mut macros: HashMap<String, String> = MONTH_MACROS
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let mut entries = Vec::new();
+    let mut reader = Reader { src: content, pos: 0 };
+
+    loop {
+        // Escape sequences may leave the cursor inside a multi-byte character
+        while reader.pos < content.len() && !content.is_char_boundary(reader.pos) {
+            reader.pos += 1;
+        }
+        let Some(at) = content.get(reader.pos..).and_then(|rest| rest.find('@')) else {
+            break;
+        };
+        reader.pos += at + 1;
+        let start = reader.pos;
+        match reader.read_block(&mut macros) {
+            Some(Block::Entry(entry)) => entries.push(entry),
+            Some(Block::Skipped) => {}
+            // Malformed block: rescan from just after its '@'
+            None => reader.pos = start,
+        }
+    }
+
+    entries
+}
+
+/// Result of reading one `@...` block
+enum Block {
+    Entry(BibEntry),
+    /// `@comment`, `@preamble` or `@string` blocks that produce no entry
+    Skipped,
+}
+
+struct Reader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn read_identifier(&mut self) -> &str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !b.is_ascii_whitespace() && !b"{}()=,#\"@%".contains(&b))
+        {
+            self.pos += 1;
+        }
+        &self.src[start..self.pos]
+    }
+
+    /// Read everything up to the brace matching the one just consumed
+    fn read_braced(&mut self) -> Option<&str> {
+        let start = self.pos;
+        let mut depth = 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => self.pos += 1,
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&self.src[start..self.pos - 1]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Read a quoted value; quotes nested inside braces do not end it
+    fn read_quoted(&mut self) -> Option<&str> {
+        let start = self.pos;
+        let mut depth = 0;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => self.pos += 1,
+                b'{' => depth += 1,
+                b'}' => depth -= 1,
+                b'"' if depth == 0 => return Some(&self.src[start..self.pos - 1]),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Read a value made of `#`-concatenated braced, quoted, numeric or macro parts
+    fn read_value(&mut self, macros: &HashMap<String, String>) -> Option<String> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                b'{' => {
+                    self.pos += 1;
+                    value.push_str(self.read_braced()?);
+                }
+                b'"' => {
+                    self.pos += 1;
+                    value.push_str(self.read_quoted()?);
+                }
+                _ => {
+                    let token = self.read_identifier();
+                    if token.is_empty() {
+                        return None;
+                    }
+                    match macros.get(&token.to_lowercase()) {
+                        Some(expansion) => value.push_str(expansion),
+                        None => value.push_str(token),
+                    }
+                }
+            }
+            self.skip_whitespace();
+            if self.peek() == Some(b'#') {
+                self.pos += 1;
+            } else {
+                return Some(value.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+        }
+    }
+
+    /// Read one `@type{...}` block; the leading '@' has already been consumed
+    fn read_block(&mut self, macros: &mut HashMap<String, String>) -> Option<Block> {
+        self.skip_whitespace();
+        let entry_type = self.read_identifier().to_lowercase();
+        if entry_type.is_empty() {
+            return None;
+        }
+        self.skip_whitespace();
+        let close = match self.peek()? {
+            b'{' => b'}',
+            b'(' => b')',
+            _ => return None,
+        };
+        self.pos += 1;
+
+        match entry_type.as_str() {
+            "comment" | "preamble" => {
+                self.read_braced()?;
+                return Some(Block::Skipped);
+            }
+            "string" => {
+                self.skip_whitespace();
+                let name = self.read_identifier().to_lowercase();
+                self.skip_whitespace();
+                if self.peek() == Some(b'=') {
+                    self.pos += 1;
+                    if let Some(value) = self.read_value(macros) {
+                        macros.insert(name, value);
+                    }
+                }
+                self.skip_whitespace();
+                if self.peek() == Some(close) {
+                    self.pos += 1;
+                }
+                return Some(Block::Skipped);
+            }
+            _ => {}
+        }
+
+        self.skip_whitespace();
+        let key_start = self.pos;
+        while self.peek().is_some_and(|b| b != b',' && b != close && !b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+        let key = self.src[key_start..self.pos].to_string();
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut builder = BibEntryBuilder::new(key, entry_type);
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => {
+                    self.pos += 1;
+                    continue;
+                }
+                b if b == close => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {}
+            }
+
+            let name = self.read_identifier().to_lowercase();
+            if name.is_empty() {
+                return None;
+            }
+            self.skip_whitespace();
+            if self.peek()? != b'=' {
+                return None;
+            }
+            self.pos += 1;
+            let value = self.read_value(macros)?;
+            builder = builder.field(name, value);
+        }
+
+        Some(Block::Entry(builder.build()))
+    }
+}
@@ -39,7 +39,7 @@ pub fn related_work_section(section_title: &str) -> bool {
             return true;
         }
     }
-    return false;
+    false
 }
 
 /// Extract sections from LaTeX content
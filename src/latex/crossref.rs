@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::BibExtractError;
+use crate::latex::http::{self, HTTP_CLIENT};
+use crate::latex::providers::{match_score, Candidate, MetadataProvider};
+use crate::latex::similarity::MatchThresholds;
+use crate::latex::{BibEntry, BibEntryBuilder, Bibliography, Person, PersonList};
+
+/// How many works a bibliographic search asks Crossref for
+const SEARCH_ROWS: &str = "5";
+
+/// Verifies entries through the Crossref REST API: DOIs written in the entry are resolved
+/// to their works record, other entries are found with a `query.bibliographic` search.
+///
+/// The API lives at `CROSSREF_BASE_URL` (https://api.crossref.org by default); setting
+/// `CROSSREF_MAILTO` to a contact address sends requests to Crossref's polite pool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrossrefProvider;
+
+#[async_trait]
+impl MetadataProvider for CrossrefProvider {
+    fn name(&self) -> &str {
+        "Crossref"
+    }
+
+    async fn lookup_id(&self, entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        let Some(doi) = Bibliography::new().extract_doi(entry) else {
+            return Ok(None);
+        };
+        let Some(url) = works_url(&doi) else {
+            log::warn!("Invalid Crossref base URL: {}", base_url());
+            return Ok(None);
+        };
+        let Some(response) = get_json(url.as_str(), &[], &doi).await else {
+            return Ok(None);
+        };
+        Ok(response
+            .get("message")
+            .and_then(|work| crossref_work_to_entry(work, &entry.key))
+            .map(|verified| Candidate::new(verified, 1.0, self.name())))
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let Some(title) = entry.get("title") else {
+            return Ok(Vec::new());
+        };
+        // The bibliographic query weighs everything a reference string would hold
+        let mut query = title.replace(['{', '}'], "");
+        if let Some(first_author) = entry.authors().first() {
+            query = format!("{} {}", first_author.last, query);
+        }
+        if let Some(year) = entry.get("year") {
+            query = format!("{} {}", query, year);
+        }
+
+        let url = format!("{}/works", base_url());
+        let params = [("query.bibliographic", query.as_str()), ("rows", SEARCH_ROWS)];
+        let Some(response) = get_json(&url, &params, &query).await else {
+            return Ok(Vec::new());
+        };
+        let works = response
+            .get("message")
+            .and_then(|m| m.get("items"))
+            .and_then(|items| items.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut candidates: Vec<Candidate> = works
+            .iter()
+            .filter_map(|work| crossref_work_to_entry(work, &entry.key))
+            .map(|verified| {
+                let score = match_score(entry, &verified);
+                Candidate::new(verified, score, self.name())
+            })
+            .filter(|candidate| candidate.score >= MatchThresholds::default().min_score)
+            .collect();
+        // Stable, so equal scores keep Crossref's relevance order
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+}
+
+fn base_url() -> String {
+    std::env::var("CROSSREF_BASE_URL").unwrap_or_else(|_| "https://api.crossref.org".to_string())
+}
+
+/// The works record URL of a DOI. The DOI is a single path segment, so its slashes and any
+/// `?` or `#` are percent-encoded rather than read as URL syntax.
+fn works_url(doi: &str) -> Option<reqwest::Url> {
+    let mut url = reqwest::Url::parse(&base_url()).ok()?;
+    url.path_segments_mut().ok()?.pop_if_empty().extend(["works", doi]);
+    Some(url)
+}
+
+/// GET a Crossref JSON response, retrying transient failures. Unknown DOIs and failed
+/// queries give `None`.
+async fn get_json(url: &str, params: &[(&str, &str)], what: &str) -> Option<Value> {
+    let mailto = std::env::var("CROSSREF_MAILTO").ok();
+    let request = || {
+        let request = HTTP_CLIENT.get(url).query(params);
+        match &mailto {
+            Some(mailto) => request.query(&[("mailto", mailto)]),
+            None => request,
+        }
+    };
+    http::get_json("Crossref", request, what).await
+}
+
+/// Convert a Crossref works record into a BibEntry with the given key
+pub fn crossref_work_to_entry(work: &Value, key: &str) -> Option<BibEntry> {
+    let first_string = |field: &str| {
+        work.get(field)
+            .and_then(|v| v.as_array())
+            .and_then(|values| values.first())
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let string = |field: &str| work.get(field).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+
+    let title = first_string("title")?;
+    let entry_type = work.get("type").and_then(|t| t.as_str()).map_or("misc", crossref_entry_type);
+    let mut builder = BibEntryBuilder::new(key, entry_type).field("title", title);
+
+    if let Some(authors) = work.get("author").and_then(|a| a.as_array()) {
+        let persons: Vec<Person> = authors
+            .iter()
+            .filter_map(|author| {
+                match (author.get("family").and_then(|f| f.as_str()), author.get("given").and_then(|g| g.as_str())) {
+                    (Some(family), Some(given)) => Some(Person::parse(&format!("{}, {}", family, given))),
+                    (Some(family), None) => Some(Person::parse(family)),
+                    // Consortia and other organizations only have a name
+                    _ => author.get("name").and_then(|n| n.as_str()).map(|name| Person {
+                        last: format!("{{{}}}", name),
+                        ..Default::default()
+                    }),
+                }
+            })
+            .collect();
+        if !persons.is_empty() {
+            builder = builder.field("author", PersonList { persons, is_truncated: false }.to_bibtex());
+        }
+    }
+
+    // The issue date is the publication date; print and online dates are fallbacks
+    let year = ["issued", "published-print", "published-online", "published"]
+        .iter()
+        .filter_map(|field| work.get(*field)?.get("date-parts")?.get(0)?.get(0)?.as_i64())
+        .next();
+    if let Some(year) = year {
+        builder = builder.field("year", year.to_string());
+    }
+
+    if let Some(container) = first_string("container-title") {
+        let container_field = match entry_type {
+            "article" => "journal",
+            "inproceedings" | "incollection" => "booktitle",
+            _ => "series",
+        };
+        builder = builder.field(container_field, container);
+    }
+
+    if let Some(volume) = string("volume") {
+        builder = builder.field("volume", volume);
+    }
+    if let Some(issue) = string("issue") {
+        builder = builder.field("number", issue);
+    }
+    if let Some(pages) = string("page") {
+        builder = builder.field("pages", pages.replace('-', "--"));
+    }
+    if let Some(publisher) = string("publisher") {
+        builder = builder.field("publisher", publisher);
+    }
+    if let Some(doi) = string("DOI") {
+        builder = builder.field("doi", doi);
+    }
+    if let Some(url) = string("URL") {
+        builder = builder.field("url", url);
+    }
+
+    Some(builder.build())
+}
+
+/// Map a Crossref work type onto a BibTeX entry type
+pub(crate) fn crossref_entry_type(crossref_type: &str) -> &'static str {
+    match crossref_type {
+        "journal-article" => "article",
+        "proceedings-article" => "inproceedings",
+        "book-chapter" | "book-section" | "book-part" | "reference-entry" => "incollection",
+        "book" | "monograph" | "edited-book" | "reference-book" => "book",
+        "proceedings" => "proceedings",
+        "report" => "techreport",
+        "dissertation" => "phdthesis",
+        _ => "misc",
+    }
+}
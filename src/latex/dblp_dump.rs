@@ -0,0 +1,447 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use log::{info, warn};
+use serde_json::{json, Value};
+
+use crate::error::BibExtractError;
+use crate::latex::local_index::TitleIndex;
+use crate::latex::providers::{Candidate, MetadataProvider};
+use crate::latex::similarity::MatchThresholds;
+use crate::latex::verification::dblp_hit_to_entry;
+use crate::latex::{BibEntry, Bibliography};
+
+/// Publication elements of dblp.xml and the type the DBLP search API reports them as.
+/// `www` (person pages) and `data` records are not publications.
+const RECORD_TYPES: [(&str, &str); 7] = [
+    ("article", "Journal Articles"),
+    ("inproceedings", "Conference and Workshop Papers"),
+    ("proceedings", "Editorship"),
+    ("book", "Books and Theses"),
+    ("incollection", "Parts in Books or Collections"),
+    ("phdthesis", "Books and Theses"),
+    ("mastersthesis", "Books and Theses"),
+];
+
+/// Names of the character entities dblp.dtd defines, for U+00A0 to U+00FF in order
+const LATIN1_ENTITIES: [&str; 96] = [
+    "nbsp", "iexcl", "cent", "pound", "curren", "yen", "brvbar", "sect", "uml", "copy", "ordf", "laquo",
+    "not", "shy", "reg", "macr", "deg", "plusmn", "sup2", "sup3", "acute", "micro", "para", "middot",
+    "cedil", "sup1", "ordm", "raquo", "frac14", "frac12", "frac34", "iquest", "Agrave", "Aacute", "Acirc",
+    "Atilde", "Auml", "Aring", "AElig", "Ccedil", "Egrave", "Eacute", "Ecirc", "Euml", "Igrave", "Iacute",
+    "Icirc", "Iuml", "ETH", "Ntilde", "Ograve", "Oacute", "Ocirc", "Otilde", "Ouml", "times", "Oslash",
+    "Ugrave", "Uacute", "Ucirc", "Uuml", "Yacute", "THORN", "szlig", "agrave", "aacute", "acirc", "atilde",
+    "auml", "aring", "aelig", "ccedil", "egrave", "eacute", "ecirc", "euml", "igrave", "iacute", "icirc",
+    "iuml", "eth", "ntilde", "ograve", "oacute", "ocirc", "otilde", "ouml", "divide", "oslash", "ugrave",
+    "uacute", "ucirc", "uuml", "yacute", "thorn", "yuml",
+];
+
+/// Version of the index file format, written on its first line
+const INDEX_VERSION: u64 = 1;
+
+/// One publication of the DBLP dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DblpRecord {
+    /// DBLP key, e.g. `conf/nips/VaswaniSPUJGKP17`
+    pub key: String,
+    /// Publication type as the DBLP search API reports it, e.g. "Conference and Workshop Papers"
+    pub record_type: String,
+    pub title: String,
+    /// Names as DBLP writes them, homonym numbers included ("Wei Wang 0001")
+    pub authors: Vec<String>,
+    pub year: String,
+    /// Journal or book title
+    pub venue: Option<String>,
+    pub volume: Option<String>,
+    pub doi: Option<String>,
+}
+
+impl DblpRecord {
+    /// The record as the `info` of a DBLP search API hit
+    pub fn to_api_info(&self) -> Value {
+        let mut info = json!({
+            "title": self.title,
+            "year": self.year,
+            "type": self.record_type,
+            "key": self.key,
+            "url": format!("https://dblp.org/rec/{}", self.key),
+            "authors": {"author": self.authors.iter().map(|name| json!({"text": name})).collect::<Vec<_>>()},
+        });
+        for (field, value) in [("venue", &self.venue), ("volume", &self.volume), ("doi", &self.doi)] {
+            if let Some(value) = value {
+                info[field] = json!(value);
+            }
+        }
+        info
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "key": self.key,
+            "type": self.record_type,
+            "title": self.title,
+            "authors": self.authors,
+            "year": self.year,
+            "venue": self.venue,
+            "volume": self.volume,
+            "doi": self.doi,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let string = |field: &str| value.get(field).and_then(|v| v.as_str()).map(str::to_string);
+        Some(DblpRecord {
+            key: string("key")?,
+            record_type: string("type")?,
+            title: string("title")?,
+            authors: value
+                .get("authors")?
+                .as_array()?
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect(),
+            year: string("year")?,
+            venue: string("venue"),
+            volume: string("volume"),
+            doi: string("doi"),
+        })
+    }
+}
+
+/// Stream the publication records of a dblp.xml dump, calling `on_record` for each.
+/// Returns the number of records read.
+///
+/// The dump has one element per line, which lets it be read line by line without an XML
+/// parser; elements spanning several lines are gathered until their closing tag.
+pub fn parse_dblp_xml<R: BufRead>(mut reader: R, mut on_record: impl FnMut(DblpRecord)) -> Result<usize, BibExtractError> {
+    let mut count = 0;
+    let mut bytes = Vec::new();
+    // The element, API type, key and content so far of the record being read
+    let mut current: Option<(&str, &str, String, String)> = None;
+
+    loop {
+        bytes.clear();
+        if reader.read_until(b'\n', &mut bytes)? == 0 {
+            break;
+        }
+        // dblp.xml is declared ISO-8859-1, although entities keep it ASCII in practice
+        let line = match std::str::from_utf8(&bytes) {
+            Ok(line) => line.to_string(),
+            Err(_) => bytes.iter().map(|&b| b as char).collect(),
+        };
+
+        let rest = match current.as_mut() {
+            Some((_, _, _, content)) => {
+                content.push_str(&line);
+                None
+            }
+            None => record_start(&line).map(|(element, api_type, key, rest)| {
+                current = Some((element, api_type, key, String::new()));
+                rest
+            }),
+        };
+        if let (Some(rest), Some((_, _, _, content))) = (rest, current.as_mut()) {
+            content.push_str(&rest);
+        }
+
+        if let Some((element, api_type, key, content)) = current.as_ref() {
+            let end_tag = format!("</{}>", element);
+            if let Some(end) = content.find(&end_tag) {
+                if let Some(record) = build_record(api_type, key, &content[..end]) {
+                    on_record(record);
+                    count += 1;
+                }
+                current = None;
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// A record start tag at the start of `line`: its element, API type, key and what follows it
+fn record_start(line: &str) -> Option<(&'static str, &'static str, String, String)> {
+    let line = line.trim_start();
+    let tag = line.strip_prefix('<')?;
+    let (element, api_type) = RECORD_TYPES.iter().find(|(element, _)| {
+        tag.starts_with(element) && tag[element.len()..].starts_with([' ', '>'])
+    })?;
+    let tag_end = line.find('>')?;
+    let attributes = &line[..tag_end];
+    let key = attribute(attributes, "key")?;
+    // Informal publications (CoRR preprints and the like) are articles for BibTeX as well
+    let api_type = if *element == "article" && (attribute(attributes, "publtype").as_deref() == Some("informal") || key.starts_with("journals/corr/")) {
+        "Informal and Other Publications"
+    } else {
+        api_type
+    };
+    Some((element, api_type, key, line[tag_end + 1..].to_string()))
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let end = start + tag[start..].find('"')?;
+    Some(tag[start..end].to_string())
+}
+
+/// Build a record from the child elements of a publication element
+fn build_record(api_type: &str, key: &str, content: &str) -> Option<DblpRecord> {
+    let mut title = None;
+    let mut year = None;
+    let mut authors = Vec::new();
+    let mut editors = Vec::new();
+    let mut venue = None;
+    let mut volume = None;
+    let mut doi = None;
+
+    let mut rest = content;
+    while let Some(open) = rest.find('<') {
+        let tag = &rest[open + 1..];
+        let name_len = tag.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(tag.len());
+        let name = &tag[..name_len];
+        let (Some(tag_end), false) = (tag.find('>'), name.is_empty()) else {
+            rest = tag;
+            continue;
+        };
+        let inner_start = tag_end + 1;
+        let Some(inner_len) = tag[inner_start..].find(&format!("</{}>", name)) else {
+            rest = &tag[inner_start..];
+            continue;
+        };
+        let text = element_text(&tag[inner_start..inner_start + inner_len]);
+        match name {
+            "author" => authors.push(text),
+            "editor" => editors.push(text),
+            "title" => title = Some(text),
+            "year" => year = Some(text),
+            "journal" | "booktitle" if venue.is_none() => venue = Some(text),
+            "volume" => volume = Some(text),
+            "ee" if doi.is_none() => doi = text.strip_prefix("https://doi.org/").map(str::to_string),
+            _ => {}
+        }
+        rest = &tag[inner_start + inner_len + name.len() + 3..];
+    }
+
+    Some(DblpRecord {
+        key: key.to_string(),
+        record_type: api_type.to_string(),
+        title: title.filter(|title| !title.is_empty())?,
+        // Proceedings volumes have editors only
+        authors: if authors.is_empty() { editors } else { authors },
+        year: year?,
+        venue,
+        volume,
+        doi,
+    })
+}
+
+/// The text of an element: markup such as `<i>` or `<sub>` removed, entities decoded,
+/// whitespace collapsed
+pub(crate) fn element_text(inner: &str) -> String {
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '<' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            '&' => match inner[i..].find(';').and_then(|end| decode_entity(&inner[i + 1..i + end]).map(|c| (end, c))) {
+                Some((end, decoded)) => {
+                    text.push(decoded);
+                    while chars.next().is_some_and(|(j, _)| j < i + end) {}
+                }
+                None => text.push('&'),
+            },
+            _ => text.push(c),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode an XML or dblp.dtd entity name (`amp`, `uuml`, `#228`, `#xE4`)
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            if let Some(number) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                return u32::from_str_radix(number, 16).ok().and_then(char::from_u32);
+            }
+            if let Some(number) = name.strip_prefix('#') {
+                return number.parse().ok().and_then(char::from_u32);
+            }
+            let position = LATIN1_ENTITIES.iter().position(|entity| *entity == name)?;
+            char::from_u32(0xA0 + position as u32)
+        }
+    }
+}
+
+/// A title and author index over the publications of a DBLP dump
+#[derive(Debug, Default)]
+pub struct DblpIndex {
+    records: Vec<DblpRecord>,
+    titles: TitleIndex,
+}
+
+impl DblpIndex {
+    pub fn from_records(records: Vec<DblpRecord>) -> Self {
+        let mut index = DblpIndex::default();
+        for record in records {
+            index.insert(record);
+        }
+        index
+    }
+
+    fn insert(&mut self, record: DblpRecord) {
+        self.titles.insert(&record.title, record.authors.iter().map(String::as_str));
+        self.records.push(record);
+    }
+
+    /// Build the index from a dblp.xml dump, gzip-compressed if its name ends in `.gz`
+    pub fn from_xml(path: &Path) -> Result<Self, BibExtractError> {
+        info!("Indexing DBLP dump {:?}", path);
+        let file = File::open(path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(BufReader::new(GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+        let mut index = DblpIndex::default();
+        let count = parse_dblp_xml(reader, |record| index.insert(record))?;
+        info!("Indexed {} DBLP records", count);
+        Ok(index)
+    }
+
+    /// Write the records to an index file (JSON Lines) that `load` reads back
+    pub fn save(&self, path: &Path) -> Result<(), BibExtractError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "{}", json!({"dblp_index": INDEX_VERSION}))?;
+        for record in &self.records {
+            writeln!(writer, "{}", record.to_json())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read an index file written by `save`
+    pub fn load(path: &Path) -> Result<Self, BibExtractError> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+        let header: Value = serde_json::from_str(&lines.next().transpose()?.unwrap_or_default())?;
+        if header.get("dblp_index").and_then(|v| v.as_u64()) != Some(INDEX_VERSION) {
+            return Err(BibExtractError::ApiError(format!("{:?} is not a DBLP index", path)));
+        }
+        let mut index = DblpIndex::default();
+        for line in lines {
+            let record = DblpRecord::from_json(&serde_json::from_str(&line?)?)
+                .ok_or_else(|| BibExtractError::ApiError(format!("Malformed record in DBLP index {:?}", path)))?;
+            index.insert(record);
+        }
+        Ok(index)
+    }
+
+    /// Open a dblp.xml(.gz) dump through its index file (`<dump>.index.jsonl`), which is built
+    /// and written the first time and whenever the dump is newer
+    pub fn open(dump_path: &Path) -> Result<Self, BibExtractError> {
+        let index_path = Self::index_path(dump_path);
+        let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        if let (Some(index_time), Some(dump_time)) = (modified(&index_path), modified(dump_path)) {
+            if index_time >= dump_time {
+                match Self::load(&index_path) {
+                    Ok(index) => return Ok(index),
+                    Err(e) => warn!("Rebuilding DBLP index {:?}: {}", index_path, e),
+                }
+            }
+        }
+        let index = Self::from_xml(dump_path)?;
+        if let Err(e) = index.save(&index_path) {
+            warn!("Could not write DBLP index {:?}: {}", index_path, e);
+        }
+        Ok(index)
+    }
+
+    /// Where `open` keeps the index of a dump
+    pub fn index_path(dump_path: &Path) -> PathBuf {
+        let mut path = dump_path.as_os_str().to_owned();
+        path.push(".index.jsonl");
+        PathBuf::from(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Records that may be the entry: those with its title, and those by its first author
+    /// with a similar title
+    pub fn candidates(&self, entry: &BibEntry) -> Vec<&DblpRecord> {
+        self.titles
+            .candidates(entry)
+            .into_iter()
+            .map(|position| &self.records[position])
+            .collect()
+    }
+}
+
+/// Verifies entries against a local DBLP dump, without network access. Matches are scored
+/// and converted exactly as DBLP search API hits are, so the entries get the same fields.
+#[derive(Debug, Clone)]
+pub struct DblpDumpProvider {
+    index: Arc<DblpIndex>,
+    thresholds: MatchThresholds,
+}
+
+impl DblpDumpProvider {
+    pub fn new(index: Arc<DblpIndex>) -> Self {
+        Self { index, thresholds: MatchThresholds::default() }
+    }
+
+    /// Match records with `thresholds` instead of the defaults
+    pub fn with_thresholds(mut self, thresholds: MatchThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Open a dblp.xml(.gz) dump, building its index file if needed (see `DblpIndex::open`)
+    pub fn open(dump_path: &Path) -> Result<Self, BibExtractError> {
+        Ok(Self::new(Arc::new(DblpIndex::open(dump_path)?)))
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for DblpDumpProvider {
+    /// Named like the online provider, which it replaces
+    fn name(&self) -> &str {
+        "DBLP"
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let hits: Vec<Value> = self
+            .index
+            .candidates(entry)
+            .into_iter()
+            .map(|record| json!({"info": record.to_api_info()}))
+            .collect();
+        if hits.is_empty() {
+            return Ok(Vec::new());
+        }
+        let results = json!({"result": {"hits": {"@total": hits.len().to_string(), "hit": hits}}});
+        Ok(Bibliography::new()
+            .best_matches_in_dblp_with(&results, entry, &self.thresholds)
+            .into_iter()
+            .map(|(score, hit)| Candidate::new(dblp_hit_to_entry(&hit, entry), score, self.name()))
+            .collect())
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::latex::{BibEntry, Bibliography, FieldSource};
+
+// Titles that are really the venue the title extraction ran into
+static VENUE_TITLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:in\s|proceedings\b|proc\.|advances\s+in\b|journal\s+of\b|transactions\s+on\b|arxiv\s+preprint\b)")
+        .expect("Invalid venue title regex pattern")
+});
+
+/// Something that went wrong or was guessed while parsing an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseWarning {
+    MissingTitle,
+    /// The extracted title is the journal or proceedings name
+    TitleLooksLikeVenue,
+    MissingAuthor,
+    /// The author list was taken from the first line of the item without structure to go by
+    AuthorGuessed,
+    MissingYear,
+}
+
+impl ParseWarning {
+    /// How much the warning lowers the parse confidence of an entry
+    pub fn penalty(self) -> f32 {
+        match self {
+            ParseWarning::MissingTitle => 0.5,
+            ParseWarning::TitleLooksLikeVenue => 0.4,
+            ParseWarning::MissingAuthor => 0.3,
+            ParseWarning::AuthorGuessed => 0.2,
+            ParseWarning::MissingYear => 0.15,
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParseWarning::MissingTitle => "missing title",
+            ParseWarning::TitleLooksLikeVenue => "title looks like a venue",
+            ParseWarning::MissingAuthor => "missing author",
+            ParseWarning::AuthorGuessed => "author line guessed",
+            ParseWarning::MissingYear => "year not found",
+        };
+        f.write_str(message)
+    }
+}
+
+/// What to do with entries whose parse confidence is below the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LowConfidence {
+    /// Keep the entry and list its warnings in a `parse_warnings` field
+    Flag,
+    /// Remove the entry from the bibliography
+    Drop,
+}
+
+/// Check the fields of a parsed entry and record what is missing or suspicious.
+///
+/// Warnings from earlier checks are replaced, so the check can run again after verification
+/// has filled in fields. An `AuthorGuessed` warning left by the style parser is kept until
+/// the author list is verified.
+pub fn diagnose_entry(entry: &mut BibEntry) {
+    let has = |field: &str| entry.get(field).is_some_and(|value| !value.trim().is_empty());
+    let author_guessed = entry.warnings.contains(&ParseWarning::AuthorGuessed)
+        && entry.source("author") != Some(FieldSource::Verified);
+
+    let mut warnings = Vec::new();
+    match entry.get("title") {
+        Some(title) if !title.trim().is_empty() => {
+            let venue = ["journal", "booktitle"]
+                .iter()
+                .filter_map(|field| entry.get(field))
+                .any(|venue| venue.eq_ignore_ascii_case(title));
+            if venue || VENUE_TITLE_REGEX.is_match(title.trim()) {
+                warnings.push(ParseWarning::TitleLooksLikeVenue);
+            }
+        }
+        _ => warnings.push(ParseWarning::MissingTitle),
+    }
+    if !has("author") {
+        warnings.push(ParseWarning::MissingAuthor);
+    } else if author_guessed {
+        warnings.push(ParseWarning::AuthorGuessed);
+    }
+    if !has("year") {
+        warnings.push(ParseWarning::MissingYear);
+    }
+    entry.warnings = warnings;
+}
+
+/// How cleanly the bibliography of one paper was parsed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseSummary {
+    pub total: usize,
+    /// Entries without any warning
+    pub clean: usize,
+    /// Number of entries with each warning
+    pub warnings: BTreeMap<ParseWarning, usize>,
+    /// Mean parse confidence over all entries
+    pub mean_confidence: f32,
+}
+
+impl fmt::Display for ParseSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} entries parsed cleanly (mean confidence {:.2})",
+            self.clean, self.total, self.mean_confidence
+        )?;
+        if !self.warnings.is_empty() {
+            let counts: Vec<String> = self
+                .warnings
+                .iter()
+                .map(|(warning, count)| format!("{} {}", count, warning))
+                .collect();
+            write!(f, ": {}", counts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Bibliography {
+    /// Count the entries that parsed cleanly and the warnings of the others
+    pub fn parse_summary(&self) -> ParseSummary {
+        let mut summary = ParseSummary {
+            total: self.entries.len(),
+            ..Default::default()
+        };
+        let mut confidence = 0.0;
+        for entry in self.iter() {
+            if entry.warnings.is_empty() {
+                summary.clean += 1;
+            }
+            for warning in &entry.warnings {
+                *summary.warnings.entry(*warning).or_default() += 1;
+            }
+            confidence += entry.confidence();
+        }
+        if summary.total > 0 {
+            summary.mean_confidence = confidence / summary.total as f32;
+        }
+        summary
+    }
+
+    /// Flag or drop the entries whose parse confidence is below `threshold`.
+    /// Returns the number of entries affected.
+    pub fn apply_confidence_threshold(&mut self, threshold: f32, policy: LowConfidence) -> usize {
+        let low: Vec<String> = self
+            .iter()
+            .filter(|entry| entry.confidence() < threshold)
+            .map(|entry| entry.key.clone())
+            .collect();
+        for key in &low {
+            match policy {
+                LowConfidence::Drop => {
+                    self.entries.remove(key);
+                }
+                LowConfidence::Flag => {
+                    if let Some(entry) = self.entries.get_mut(key) {
+                        let warnings: Vec<String> = entry.warnings.iter().map(ToString::to_string).collect();
+                        let flag = format!("confidence {:.2}: {}", entry.confidence(), warnings.join("; "));
+                        entry.set("parse_warnings", flag);
+                    }
+                }
+            }
+        }
+        low.len()
+    }
+}
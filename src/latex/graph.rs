@@ -0,0 +1,313 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use crate::latex::ArxivPaper;
+
+/// Kind of node in a citation graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// One of the input papers that was downloaded and processed
+    Paper,
+    /// A work cited by one of the input papers
+    Work,
+}
+
+impl NodeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeKind::Paper => "paper",
+            NodeKind::Work => "work",
+        }
+    }
+}
+
+/// A node in the citation graph (input paper or cited work)
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub kind: NodeKind,
+    pub label: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// A citation edge, annotated with the section in which the citation occurs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub section: String,
+}
+
+/// Output formats supported by `CitationGraph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Json,
+    GraphMl,
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(GraphFormat::Json),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            other => Err(format!("unknown graph format '{}' (expected json, graphml or dot)", other)),
+        }
+    }
+}
+
+/// Citation graph linking input papers to the works they cite
+#[derive(Debug, Default)]
+pub struct CitationGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    node_index: HashMap<String, usize>,
+}
+
+impl CitationGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a citation graph from a set of processed papers
+    pub fn from_papers(papers: &[ArxivPaper]) -> Self {
+        let mut graph = Self::new();
+
+        // Add the input papers first so that cited works can link back to them
+        let mut paper_ids: HashSet<&str> = HashSet::new();
+        for paper in papers {
+            let mut attributes = BTreeMap::new();
+            attributes.insert("arxiv_id".to_string(), paper.id.clone());
+            attributes.insert("title".to_string(), paper.title.clone());
+            attributes.insert("author".to_string(), paper.authors.clone());
+            graph.add_node(GraphNode {
+                id: paper.id.clone(),
+                kind: NodeKind::Paper,
+                label: paper.title.clone(),
+                attributes,
+            });
+            paper_ids.insert(&paper.id);
+        }
+
+        for paper in papers {
+            for section in &paper.sections {
+                for key in &section.citations {
+                    let target = match paper.bibliography.get(key) {
+                        Some(entry) => {
+                            let arxiv_id = paper.bibliography.extract_arxiv_id(entry);
+
+                            // A cited work that is itself an input paper links to the paper node
+                            if let Some(paper_id) = arxiv_id.as_ref().filter(|id| paper_ids.contains(id.as_str())) {
+                                paper_id.clone()
+                            } else {
+                                let node_id = paper.bibliography.normalize_citation_key(entry);
+                                let mut attributes = BTreeMap::new();
+                                for field in ["title", "author", "year", "doi", "url", "verified_source"] {
+                                    if let Some(value) = entry.get(field) {
+                                        attributes.insert(field.to_string(), value.replace(['{', '}'], ""));
+                                    }
+                                }
+                                if let Some(arxiv_id) = arxiv_id {
+                                    attributes.insert("arxiv_id".to_string(), arxiv_id);
+                                }
+                                let label = entry.get("title").cloned().unwrap_or_else(|| node_id.clone());
+                                graph.add_node(GraphNode {
+                                    id: node_id.clone(),
+                                    kind: NodeKind::Work,
+                                    label,
+                                    attributes,
+                                });
+                                node_id
+                            }
+                        }
+                        None => {
+                            // Keep unresolved citations so the graph still reflects the source.
+                            // Keys are only meaningful within a paper, so each paper has its own node.
+                            let node_id = format!("{}:{}", paper.id, key);
+                            let mut attributes = BTreeMap::new();
+                            attributes.insert("unresolved".to_string(), "true".to_string());
+                            graph.add_node(GraphNode {
+                                id: node_id.clone(),
+                                kind: NodeKind::Work,
+                                label: key.clone(),
+                                attributes,
+                            });
+                            node_id
+                        }
+                    };
+
+                    graph.add_edge(GraphEdge {
+                        source: paper.id.clone(),
+                        target,
+                        section: section.title.clone(),
+                    });
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Add a node, merging attributes if a node with the same ID already exists
+    pub fn add_node(&mut self, node: GraphNode) {
+        if let Some(&index) = self.node_index.get(&node.id) {
+            let existing = &mut self.nodes[index];
+            for (name, value) in node.attributes {
+                existing.attributes.entry(name).or_insert(value);
+            }
+        } else {
+            self.node_index.insert(node.id.clone(), self.nodes.len());
+            self.nodes.push(node);
+        }
+    }
+
+    /// Add an edge unless the same citation (source, target, section) is already recorded
+    pub fn add_edge(&mut self, edge: GraphEdge) {
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.node_index.get(id).map(|&index| &self.nodes[index])
+    }
+
+    /// Serialize the graph in the requested format
+    pub fn export(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Json => self.to_json_string(),
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    /// Convert the graph to a JSON value with `nodes` and `edges` arrays
+    pub fn to_json(&self) -> Value {
+        let nodes: Vec<Value> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                json!({
+                    "id": node.id,
+                    "kind": node.kind.as_str(),
+                    "label": node.label,
+                    "attributes": node.attributes,
+                })
+            })
+            .collect();
+        let edges: Vec<Value> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "source": edge.source,
+                    "target": edge.target,
+                    "section": edge.section,
+                })
+            })
+            .collect();
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json()).unwrap_or_default()
+    }
+
+    /// Convert the graph to GraphML
+    pub fn to_graphml(&self) -> String {
+        // Every attribute name used by any node becomes a GraphML key
+        let mut attribute_names: Vec<&String> = self.nodes.iter().flat_map(|n| n.attributes.keys()).collect();
+        attribute_names.sort();
+        attribute_names.dedup();
+
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        for name in &attribute_names {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"string\"/>",
+                escape_xml(name)
+            );
+        }
+        out.push_str("  <key id=\"section\" for=\"edge\" attr.name=\"section\" attr.type=\"string\"/>\n");
+        out.push_str("  <graph id=\"citations\" edgedefault=\"directed\">\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", escape_xml(&node.id));
+            let _ = writeln!(out, "      <data key=\"kind\">{}</data>", node.kind.as_str());
+            let _ = writeln!(out, "      <data key=\"label\">{}</data>", escape_xml(&node.label));
+            for (name, value) in &node.attributes {
+                let _ = writeln!(out, "      <data key=\"{}\">{}</data>", escape_xml(name), escape_xml(value));
+            }
+            out.push_str("    </node>\n");
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                escape_xml(&edge.source),
+                escape_xml(&edge.target)
+            );
+            let _ = writeln!(out, "      <data key=\"section\">{}</data>", escape_xml(&edge.section));
+            out.push_str("    </edge>\n");
+        }
+
+        out.push_str("  </graph>\n");
+        out.push_str("</graphml>\n");
+        out
+    }
+
+    /// Convert the graph to Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph citations {\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Paper => "box",
+                NodeKind::Work => "ellipse",
+            };
+            let mut attrs = vec![
+                format!("label=\"{}\"", escape_dot(&node.label)),
+                format!("shape={}", shape),
+            ];
+            for (name, value) in &node.attributes {
+                attrs.push(format!("{}=\"{}\"", name, escape_dot(value)));
+            }
+            let _ = writeln!(out, "  \"{}\" [{}];", escape_dot(&node.id), attrs.join(", "));
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(&edge.source),
+                escape_dot(&edge.target),
+                escape_dot(&edge.section)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Escape a string for use in XML text and attribute values
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Escape a string for use inside a double-quoted DOT ID
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " ")
+}
@@ -0,0 +1,71 @@
+use backoff::{future::retry, ExponentialBackoff};
+use log::info;
+use once_cell::sync::Lazy;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::error::BibExtractError;
+
+/// The client of every download and API call, so that they share its connection pool
+pub(crate) static HTTP_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// How API calls are retried: exponential backoff, giving up after `API_TIMEOUT_SECS`
+/// seconds (30 by default; tests set less)
+pub(crate) fn api_backoff() -> ExponentialBackoff {
+    let max_timeout = std::env::var("API_TIMEOUT_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(30);
+
+    ExponentialBackoff {
+        initial_interval: Duration::from_millis(100),
+        max_interval: Duration::from_secs(5),
+        max_elapsed_time: Some(Duration::from_secs(max_timeout)),
+        ..Default::default()
+    }
+}
+
+/// The error for an unsuccessful response of `service`. Rate limits and server errors are
+/// worth another try; other statuses, such as a rejected query, are not.
+pub(crate) fn status_error(service: &str, status: StatusCode) -> backoff::Error<BibExtractError> {
+    log::warn!("{} API returned status {}", service, status);
+    let error = BibExtractError::ApiError(format!("{} API returned status {}", service, status));
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        backoff::Error::transient(error)
+    } else {
+        backoff::Error::permanent(error)
+    }
+}
+
+/// The JSON body of a response of `service`: `None` for records it does not know (404)
+pub(crate) async fn read_json(service: &str, response: Response) -> Result<Option<Value>, backoff::Error<BibExtractError>> {
+    match response.status() {
+        status if status.is_success() => {
+            let json_response: Value = response.json().await
+                .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
+            Ok(Some(json_response))
+        }
+        StatusCode::NOT_FOUND => Ok(None),
+        status => Err(status_error(service, status)),
+    }
+}
+
+/// Send the request `request` builds to `service` and read its JSON body, retrying
+/// transient failures (see `api_backoff`). Unknown records and failed requests give `None`.
+pub(crate) async fn get_json(service: &str, request: impl Fn() -> RequestBuilder, what: &str) -> Option<Value> {
+    let operation = || async {
+        info!("Querying {} for: {}", service, what);
+        let response = request().send().await
+            .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
+        read_json(service, response).await
+    };
+
+    match retry(api_backoff(), operation).await {
+        Ok(result) => result,
+        Err(e) => {
+            log::warn!("{} API query failed for {}: {}", service, what, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::latex::person::{fold_accents, latex_to_unicode};
+use crate::latex::similarity::{fold_text, STOPWORDS};
+use crate::latex::BibEntry;
+
+/// Most records a search hands to the scorer
+const MAX_CANDIDATES: usize = 100;
+
+/// Title and author lookup shared by the indexes of local metadata files (DBLP dump, arXiv
+/// snapshot, ACL Anthology). Records are known by their position in the owning index.
+#[derive(Debug, Default)]
+pub(crate) struct TitleIndex {
+    /// Normalized title of each record
+    titles: Vec<String>,
+    by_title: HashMap<String, Vec<usize>>,
+    by_author: HashMap<String, Vec<usize>>,
+}
+
+impl TitleIndex {
+    /// Add the next record, by its title and author names
+    pub(crate) fn insert<'a>(&mut self, title: &str, authors: impl IntoIterator<Item = &'a str>) {
+        let position = self.titles.len();
+        let title = normalize_title(title);
+        self.by_title.entry(title.clone()).or_default().push(position);
+        self.titles.push(title);
+        let last_names: HashSet<String> = authors.into_iter().filter_map(last_name_key).collect();
+        for last_name in last_names {
+            self.by_author.entry(last_name).or_default().push(position);
+        }
+    }
+
+    /// Records that may be the entry: those with its title, then those by its first author
+    /// whose title contains or is contained in the entry's, or shares more than two of its
+    /// words, most similar first
+    pub(crate) fn candidates(&self, entry: &BibEntry) -> Vec<usize> {
+        let Some(title) = entry.get("title").map(|title| normalize_title(title)) else {
+            return Vec::new();
+        };
+        let mut positions: Vec<usize> = self.by_title.get(&title).cloned().unwrap_or_default();
+        let exact: HashSet<usize> = positions.iter().copied().collect();
+
+        let first_author = entry.authors().first().and_then(|person| last_name_key(&person.last));
+        if let Some(by_author) = first_author.and_then(|last_name| self.by_author.get(&last_name)) {
+            let words = content_words(&title);
+            let padded = format!(" {} ", title);
+            let mut similar: Vec<(f32, usize)> = by_author
+                .iter()
+                .filter(|position| !exact.contains(position))
+                .filter_map(|&position| {
+                    let record_title = &self.titles[position];
+                    let record_words = content_words(record_title);
+                    let shared = words.intersection(&record_words).count();
+                    let contained = !record_title.is_empty()
+                        && (padded.contains(&format!(" {} ", record_title)) || format!(" {} ", record_title).contains(&padded));
+                    if !contained && shared <= 2 {
+                        return None;
+                    }
+                    // Share of the distinct words of both titles they have in common
+                    let overlap = shared as f32 / words.union(&record_words).count().max(1) as f32;
+                    Some((overlap, position))
+                })
+                .collect();
+            // Stable, so equal overlaps keep the file order
+            similar.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+            positions.extend(similar.into_iter().map(|(_, position)| position));
+        }
+        positions.truncate(MAX_CANDIDATES);
+        positions
+    }
+}
+
+/// Words of a normalized title that tell it apart, without stopwords
+fn content_words(title: &str) -> HashSet<&str> {
+    title.split_whitespace().filter(|word| !STOPWORDS.contains(word)).collect()
+}
+
+/// Lowercase words of a title, without punctuation, markup, accents or math
+pub(crate) fn normalize_title(title: &str) -> String {
+    fold_text(title)
+}
+
+/// The lowercase last word of a name, without accents or DBLP's homonym number
+pub(crate) fn last_name_key(name: &str) -> Option<String> {
+    fold_accents(&latex_to_unicode(name))
+        .split_whitespace()
+        .rev()
+        .find(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+}
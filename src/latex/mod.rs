@@ -1,24 +1,70 @@
-use log::{info};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+pub mod acl_anthology;
+pub mod amsrefs;
+pub mod arxiv_search;
+pub mod arxiv_snapshot;
+pub mod bbl_scanner;
+pub mod bbl_style;
+pub mod biblatex;
 pub mod bibliography;
+pub mod bibtex;
 pub mod citation;
+pub mod crossref;
+pub mod dblp_dump;
+pub mod diagnostics;
+pub mod graph;
+pub(crate) mod http;
+mod local_index;
+pub mod openalex;
 pub mod parser;
+pub mod person;
+pub mod providers;
+pub mod reference_env;
+pub mod reference_tagger;
+pub mod semantic_scholar;
+pub mod similarity;
+pub mod stats;
 pub mod verification;
 
 // Re-export commonly used types
-pub use bibliography::{Bibliography, BibEntry, BibEntryBuilder};
+pub use acl_anthology::{AclAnthologyProvider, AnthologyIndex};
+pub use arxiv_snapshot::{ArxivIndex, ArxivRecord, ArxivSnapshotProvider};
+pub use bbl_scanner::{BblItem, BblScanner};
+pub use bbl_style::{bbl_style_by_name, detect_bbl_style, infer_entry_type, register_bbl_style, BblStyleParser};
+pub use bibliography::{BblScope, BblUnit, Bibliography, BibEntry, BibEntryBuilder, FieldSource, KeyConflict};
 pub use citation::ExtractedSection;
-pub use parser::{download_arxiv_source_async, find_bbl_files, extract_all_latex_from_files, resolve_input_path};
+pub use crossref::CrossrefProvider;
+pub use dblp_dump::{DblpDumpProvider, DblpIndex, DblpRecord};
+pub use diagnostics::{LowConfidence, ParseSummary, ParseWarning};
+pub use graph::{CitationGraph, GraphFormat};
+pub use arxiv_search::ArxivSearchProvider;
+pub use openalex::OpenAlexProvider;
+pub use stats::CitationStats;
+pub use person::{Person, PersonList};
+pub use providers::{apply_verified_entry, match_score, Candidate, MergePolicy, MetadataProvider, Verifier};
+pub use semantic_scholar::SemanticScholarProvider;
+pub use similarity::MatchThresholds;
+pub use reference_tagger::{tag_reference, ReferenceField, TaggedReference, TaggedSpan};
+pub use verification::{ArxivProvider, DblpProvider};
+pub use parser::{download_arxiv_source_async, download_arxiv_source_with, find_bbl_files, find_bib_databases, find_document_bbl_files, extract_all_latex_from_files, extract_all_latex_with_spans, parse_document_bbl_files, resolve_input_path, IncludedFile};
 
 // Commonly used regex patterns compiled once
+// `\cite`, its natbib variants, and the commands multibib defines for each list (`\citeltr`)
 pub static CITE_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\\(?:cite|citep|citet|citealp|citeauthor)\{([^}]+)\}").expect("Invalid citation regex pattern")
+    Regex::new(r"\\cite[a-z]*\{([^}]+)\}").expect("Invalid citation regex pattern")
+});
+// Any citation command, including natbib/biblatex variants with optional arguments and \nocite
+pub static ANY_CITE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(?:no)?cite[a-zA-Z]*\*?(?:\[[^\]]*\])*\{([^}]+)\}").expect("Invalid citation regex pattern")
 });
 pub static ARXIV_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"arXiv:?\s*([0-9]+\.[0-9]+)").expect("Invalid arXiv ID regex pattern")
 });
+pub static DOI_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r##"\b(10\.[0-9]{4,9}/[^\s,;{}"#?<>]+)"##).expect("Invalid DOI regex pattern")
+});
 pub static ARXIV_KEY_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^([0-9]{4}\.[0-9]+)$").expect("Invalid arXiv key regex pattern")
 });
@@ -47,6 +93,11 @@ pub struct ArxivPaper {
 impl ArxivPaper {
     /// Verify bibliography entries using async processing for both sources (DBLP and arXiv simultaneously)
     pub async fn verify_bibliography(&mut self) -> anyhow::Result<usize> {
+        self.verify_bibliography_with(&Verifier::default()).await
+    }
+
+    /// Verify bibliography entries concurrently against the providers of `verifier`
+    pub async fn verify_bibliography_with(&mut self, verifier: &Verifier) -> anyhow::Result<usize> {
         use log::info;
         use std::sync::{Arc, Mutex};
         use std::collections::HashMap;
@@ -60,8 +111,13 @@ impl ArxivPaper {
         let verified_entries = Arc::new(Mutex::new(HashMap::new()));
         let verification_count = Arc::new(Mutex::new(0usize));
         
+        // Batch lookups of providers that support them, before the per-entry tasks start
+        let entries: Vec<BibEntry> = self.bibliography.iter().cloned().collect();
+        verifier.prefetch(&entries).await;
+
         // Process entries concurrently using tokio tasks with concurrency limit
         let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(8)); // Limit concurrent API calls
+        let verifier = Arc::new(verifier.clone());
         let verification_tasks: Vec<_> = keys
             .into_iter()
             .map(|key| {
@@ -69,6 +125,7 @@ impl ArxivPaper {
                 let verification_count = Arc::clone(&verification_count);
                 let entry = self.bibliography.get(&key).cloned();
                 let semaphore = Arc::clone(&semaphore);
+                let verifier = Arc::clone(&verifier);
                 
                 tokio::spawn(async move {
                     // Acquire semaphore permit to limit concurrency
@@ -77,10 +134,7 @@ impl ArxivPaper {
                     if let Some(entry) = entry {
                         let mut entry_clone = entry.clone();
                         
-                        // Create a temporary bibliography instance to avoid borrowing issues
-                        let temp_bib = Bibliography::new();
-                        
-                        match temp_bib.verify_entry(&mut entry_clone).await {
+                        match verifier.verify_entry(&mut entry_clone).await {
                             Ok(true) => {
                                 // Successfully verified
                                 let mut count = verification_count.lock().unwrap();
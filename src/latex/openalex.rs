@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::BibExtractError;
+use crate::latex::crossref::crossref_entry_type;
+use crate::latex::http::{self, HTTP_CLIENT};
+use crate::latex::providers::{match_score, Candidate, MetadataProvider};
+use crate::latex::similarity::{author_similarity, MatchThresholds};
+use crate::latex::{BibEntry, BibEntryBuilder, Person, PersonList};
+
+/// How many works a title search asks OpenAlex for
+const SEARCH_RESULTS: &str = "5";
+
+/// Verifies entries through a title search of OpenAlex works, the fallback for entries
+/// that arXiv and DBLP cannot match.
+///
+/// The search is restricted to a year either side of the entry's year, and works without
+/// any of the entry's authors are passed over. The API lives at `OPENALEX_BASE_URL`
+/// (https://api.openalex.org by default); setting `OPENALEX_MAILTO` to a contact address
+/// sends requests to OpenAlex's polite pool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenAlexProvider;
+
+#[async_trait]
+impl MetadataProvider for OpenAlexProvider {
+    fn name(&self) -> &str {
+        "OpenAlex"
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let Some(title) = entry.get("title") else {
+            return Ok(Vec::new());
+        };
+        let query = title.replace(['{', '}'], "");
+        // Preprints and their published versions often differ by a year
+        let year_filter = entry
+            .get("year")
+            .and_then(|year| year.trim().parse::<u32>().ok())
+            .map(|year| format!("publication_year:{}-{}", year.saturating_sub(1), year + 1));
+
+        let url = format!("{}/works", base_url());
+        let mut params = vec![("search", query.as_str()), ("per-page", SEARCH_RESULTS)];
+        if let Some(year_filter) = &year_filter {
+            params.push(("filter", year_filter.as_str()));
+        }
+        let Some(response) = get_json(&url, &params, &query).await else {
+            return Ok(Vec::new());
+        };
+
+        let authors = entry.authors();
+        let mut candidates: Vec<Candidate> = response
+            .get("results")
+            .and_then(|results| results.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|work| openalex_work_to_entry(work, &entry.key))
+            .filter(|verified| {
+                // A work with the same title by other people is another work
+                let work_authors = verified.authors();
+                authors.is_empty()
+                    || work_authors.is_empty()
+                    || author_similarity(&authors.persons, &work_authors.persons) > 0.0
+            })
+            .map(|verified| {
+                let score = match_score(entry, &verified);
+                Candidate::new(verified, score, self.name())
+            })
+            .filter(|candidate| candidate.score >= MatchThresholds::default().min_score)
+            .collect();
+        // Stable, so equal scores keep OpenAlex's relevance order
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+}
+
+fn base_url() -> String {
+    std::env::var("OPENALEX_BASE_URL").unwrap_or_else(|_| "https://api.openalex.org".to_string())
+}
+
+/// GET an OpenAlex JSON response, retrying transient failures. Failed queries give `None`.
+async fn get_json(url: &str, params: &[(&str, &str)], what: &str) -> Option<Value> {
+    let mailto = std::env::var("OPENALEX_MAILTO").ok();
+    let request = || {
+        let request = HTTP_CLIENT.get(url).query(params);
+        match &mailto {
+            Some(mailto) => request.query(&[("mailto", mailto)]),
+            None => request,
+        }
+    };
+    http::get_json("OpenAlex", request, what).await
+}
+
+/// Convert an OpenAlex work into a BibEntry with the given key
+pub fn openalex_work_to_entry(work: &Value, key: &str) -> Option<BibEntry> {
+    let title = non_empty(work.get("title")).or_else(|| non_empty(work.get("display_name")))?;
+    let source = work.get("primary_location").and_then(|location| location.get("source"));
+    let source_type = non_empty(source.and_then(|s| s.get("type")));
+    let entry_type = openalex_entry_type(
+        non_empty(work.get("type_crossref")),
+        non_empty(work.get("type")),
+        source_type,
+    );
+    let mut builder = BibEntryBuilder::new(key, entry_type).field("title", title);
+
+    if let Some(authorships) = work.get("authorships").and_then(|a| a.as_array()) {
+        let persons: Vec<Person> = authorships
+            .iter()
+            .filter_map(|authorship| non_empty(authorship.get("author").and_then(|a| a.get("display_name"))))
+            .map(Person::parse)
+            .collect();
+        if !persons.is_empty() {
+            builder = builder.field("author", PersonList { persons, is_truncated: false }.to_bibtex());
+        }
+    }
+    if let Some(year) = work.get("publication_year").and_then(|y| y.as_i64()) {
+        builder = builder.field("year", year.to_string());
+    }
+
+    // Repositories (arXiv, institutional archives) host the work but are not its venue
+    if let Some(venue) = non_empty(source.and_then(|s| s.get("display_name"))).filter(|_| source_type != Some("repository")) {
+        let venue_field = match entry_type {
+            "article" => Some("journal"),
+            "inproceedings" | "incollection" => Some("booktitle"),
+            _ => None,
+        };
+        if let Some(venue_field) = venue_field {
+            builder = builder.field(venue_field, venue);
+        }
+    }
+
+    let biblio = work.get("biblio");
+    if let Some(volume) = non_empty(biblio.and_then(|b| b.get("volume"))) {
+        builder = builder.field("volume", volume);
+    }
+    if let Some(issue) = non_empty(biblio.and_then(|b| b.get("issue"))) {
+        builder = builder.field("number", issue);
+    }
+    match (non_empty(biblio.and_then(|b| b.get("first_page"))), non_empty(biblio.and_then(|b| b.get("last_page")))) {
+        (Some(first), Some(last)) if first != last => builder = builder.field("pages", format!("{}--{}", first, last)),
+        (Some(first), _) => builder = builder.field("pages", first),
+        _ => {}
+    }
+
+    if let Some(doi) = non_empty(work.get("doi")) {
+        let doi = doi.trim_start_matches("https://doi.org/");
+        builder = builder.field("doi", doi);
+    }
+    // Open-access copies are the most useful links; the landing page is the fallback
+    let url = non_empty(work.get("open_access").and_then(|oa| oa.get("oa_url")))
+        .or_else(|| non_empty(work.get("primary_location").and_then(|location| location.get("landing_page_url"))));
+    if let Some(url) = url {
+        builder = builder.field("url", url);
+    }
+
+    Some(builder.build())
+}
+
+/// A string value, trimmed, unless it is empty
+fn non_empty(value: Option<&Value>) -> Option<&str> {
+    value.and_then(|v| v.as_str()).map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Map an OpenAlex work onto a BibTeX entry type: its Crossref type when it has one, else
+/// its OpenAlex type, with articles at conferences as proceedings papers
+fn openalex_entry_type(type_crossref: Option<&str>, work_type: Option<&str>, source_type: Option<&str>) -> &'static str {
+    let entry_type = match (type_crossref, work_type) {
+        (Some(type_crossref), _) => crossref_entry_type(type_crossref),
+        (None, Some("article" | "review" | "letter" | "editorial")) => "article",
+        (None, Some("book-chapter" | "reference-entry")) => "incollection",
+        (None, Some("book")) => "book",
+        (None, Some("dissertation")) => "phdthesis",
+        (None, Some("report")) => "techreport",
+        _ => "misc",
+    };
+    if entry_type == "article" && source_type == Some("conference") {
+        "inproceedings"
+    } else {
+        entry_type
+    }
+}
@@ -5,22 +5,47 @@ use crate::error::BibExtractError;
 
 use std::fs;
 use std::io::{self, Read, Write, Seek, SeekFrom};
+use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 use walkdir::WalkDir;
 use zip::ZipArchive;
 use flate2::read::GzDecoder;
 use tar::Archive;
-use reqwest::Client;
+use crate::latex::http::HTTP_CLIENT;
 use once_cell::sync::Lazy;
 
-use crate::latex::{Bibliography, ArxivPaper, citation};
+use crate::latex::bibliography::{BblScope, BblUnit};
+use crate::latex::{Bibliography, ArxivPaper, Verifier, citation, ANY_CITE_REGEX};
 
-// Use a single, lazily-initialized reqwest::Client for all API calls
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+static BIBLIOGRAPHY_DECL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(?:bibliography|addbibresource(?:\[[^\]]*\])?)\{([^}]+)\}").expect("Invalid bibliography declaration regex pattern")
+});
+static FILECONTENTS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)\\begin\{filecontents\*?\}(?:\[[^\]]*\])?\{([^}]+)\}(.*?)\\end\{filecontents\*?\}").expect("Invalid filecontents regex pattern")
+});
+// multibib: `\newcites{ltr,web}{...}` writes `ltr.bbl` and `web.bbl`
+static NEWCITES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\newcites\{([^}]+)\}").expect("Invalid newcites regex pattern")
+});
+pub static LATEX_COMMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)(^|[^\\])%.*$").expect("Invalid LaTeX comment regex pattern")
+});
+
+/// An included file and where its text, with the files it includes, ends up in the content
+/// of the main file
+pub type IncludedFile = (PathBuf, Range<usize>);
 
 /// Download and process an arXiv paper (async version)
 pub async fn download_arxiv_source_async(paper_id: &str) -> Result<ArxivPaper, BibExtractError> {
+    download_arxiv_source_with(paper_id, &Verifier::new()).await
+}
+
+/// Download and process an arXiv paper, taking its title and authors from `verifier`'s
+/// providers when arXiv's BibTeX export is unavailable
+pub async fn download_arxiv_source_with(paper_id: &str, verifier: &Verifier) -> Result<ArxivPaper, BibExtractError> {
     // Support configurable base URL for testing
     let base_url = std::env::var("ARXIV_BASE_URL").unwrap_or_else(|_| "https://arxiv.org".to_string());
     let url = format!("{}/e-print/{}", base_url, paper_id);
@@ -36,19 +61,19 @@ pub async fn download_arxiv_source_async(paper_id: &str) -> Result<ArxivPaper, B
     }
 
     // Create temp directory to extract files
-    let temp_dir = TempDir::new().map_err(|e| BibExtractError::IoError(e))?;
+    let temp_dir = TempDir::new().map_err(BibExtractError::IoError)?;
     let temp_path = temp_dir.path();
 
     // Save the downloaded source to a temporary file
-    let mut source_file = tempfile::tempfile().map_err(|e| BibExtractError::IoError(e))?;
-    let content = response.bytes().await.map_err(|e| BibExtractError::NetworkError(e))?;
+    let mut source_file = tempfile::tempfile().map_err(BibExtractError::IoError)?;
+    let content = response.bytes().await.map_err(BibExtractError::NetworkError)?;
     
     if content.is_empty() {
         return Err(BibExtractError::ApiError(format!("Received empty content from arXiv for paper ID: {}", paper_id)));
     }
     
-    source_file.write_all(&content).map_err(|e| BibExtractError::IoError(e))?;
-    source_file.seek(std::io::SeekFrom::Start(0)).map_err(|e| BibExtractError::IoError(e))?;
+    source_file.write_all(&content).map_err(BibExtractError::IoError)?;
+    source_file.seek(std::io::SeekFrom::Start(0)).map_err(BibExtractError::IoError)?;
     
     // Extract the archive
     extract_archive(source_file, temp_path)?;
@@ -57,35 +82,37 @@ pub async fn download_arxiv_source_async(paper_id: &str) -> Result<ArxivPaper, B
     let main_tex_file = find_main_tex_file(temp_path)?;
     
     // Extract all LaTeX content
-    let (full_content, _) = extract_all_latex_from_files(temp_path, &main_tex_file)?;
+    let (full_content, included_files) = extract_all_latex_with_spans(temp_path, &main_tex_file)?;
+    let included_paths: Vec<PathBuf> = included_files.iter().map(|(path, _)| path.clone()).collect();
     
-    // Find all .bbl files in the workspace
-    let bbl_files = find_bbl_files(temp_path)?;
+    // Find the .bbl files written for the main document
+    let bbl_files = find_document_bbl_files(temp_path, &main_tex_file, &included_paths, &full_content)?;
     
-    // Parse bibliography
-    let bibliography = Bibliography::parse_bibliography_files(&bbl_files)?;
+    // Find the .bib databases declared by the document
+    let bib_databases = find_bib_databases(temp_path, &main_tex_file, &full_content)?;
+
+    // Parse bibliography, preferring the .bib databases over the .bbl heuristics; keys
+    // declared again by multibib lists or chapterbib chapters are renamed in their citations
+    let (bbl_bibliography, full_content) = parse_document_bbl_files(&bbl_files, &full_content, &included_files)?;
+    let bibliography = Bibliography::combine_sources(&bib_databases, bbl_bibliography, &full_content)?;
     
     // Extract sections from the full content
     let sections = citation::extract_sections_from_latex(&full_content, &bibliography)?;
 
     // Get title and authors from arXiv/bibtex/id
-    let bibtex_url = format!("{}/bibtex/{}", base_url, paper_id);
-    let bibtex_response = HTTP_CLIENT.get(&bibtex_url).send().await.map_err(|e| BibExtractError::NetworkError(e))?;
-    if !bibtex_response.status().is_success() {
-        return Err(BibExtractError::ApiError(format!("Failed to download BibTeX: HTTP {}", bibtex_response.status())));
-    }
-    let bibtex_content = bibtex_response.text().await.map_err(|e| BibExtractError::NetworkError(e))?;
-    
-    // Extract title and authors from the BibTeX content
-    let title_re = Regex::new(r"title\s*=\s*\{([\s\S]*?)\}").unwrap();
-    let authors_re = Regex::new(r"author\s*=\s*\{([\s\S]*?)\}").unwrap();
-    let title = title_re.captures(&bibtex_content)
-        .and_then(|cap| cap.get(1))
-        .map_or("Unknown Title".to_string(), |m| m.as_str().trim().to_string());
-
-    let authors = authors_re.captures(&bibtex_content)
-        .and_then(|cap| cap.get(1))
-        .map_or("Unknown Authors".to_string(), |m| m.as_str().trim().to_string());
+    let (title, authors) = match fetch_title_and_authors(&base_url, paper_id).await {
+        Ok(metadata) => metadata,
+        Err(e) => match verifier.lookup_arxiv_paper(paper_id).await {
+            Some(entry) => {
+                info!("Using provider metadata for the title and authors of paper {}", paper_id);
+                (
+                    entry.get("title").map_or("Unknown Title".to_string(), String::clone),
+                    entry.get("author").map_or("Unknown Authors".to_string(), String::clone),
+                )
+            }
+            None => return Err(e),
+        },
+    };
 
     // Shorten the title and authors if they are too long
     let max_length = 100;
@@ -102,14 +129,37 @@ pub async fn download_arxiv_source_async(paper_id: &str) -> Result<ArxivPaper, B
 
     Ok(ArxivPaper {
         id: paper_id.to_string(),
-        title: title,
-        authors: authors,
+        title,
+        authors,
         sections,
         bibliography,
         _temp_dir: temp_dir,
     })
 }
 
+/// Title and authors of an arXiv paper from arXiv's BibTeX export
+async fn fetch_title_and_authors(base_url: &str, paper_id: &str) -> Result<(String, String), BibExtractError> {
+    let bibtex_url = format!("{}/bibtex/{}", base_url, paper_id);
+    let bibtex_response = HTTP_CLIENT.get(&bibtex_url).send().await.map_err(BibExtractError::NetworkError)?;
+    if !bibtex_response.status().is_success() {
+        return Err(BibExtractError::ApiError(format!("Failed to download BibTeX: HTTP {}", bibtex_response.status())));
+    }
+    let bibtex_content = bibtex_response.text().await.map_err(BibExtractError::NetworkError)?;
+    
+    // Extract title and authors from the BibTeX content
+    let title_re = Regex::new(r"title\s*=\s*\{([\s\S]*?)\}").unwrap();
+    let authors_re = Regex::new(r"author\s*=\s*\{([\s\S]*?)\}").unwrap();
+    let title = title_re.captures(&bibtex_content)
+        .and_then(|cap| cap.get(1))
+        .map_or("Unknown Title".to_string(), |m| m.as_str().trim().to_string());
+
+    let authors = authors_re.captures(&bibtex_content)
+        .and_then(|cap| cap.get(1))
+        .map_or("Unknown Authors".to_string(), |m| m.as_str().trim().to_string());
+
+    Ok((title, authors))
+}
+
 
 /// Extract archive (supports ZIP and TAR.GZ)
 fn extract_archive<R: Read + io::Seek>(mut archive: R, output_dir: &Path) -> Result<(), BibExtractError> {
@@ -118,36 +168,36 @@ fn extract_archive<R: Read + io::Seek>(mut archive: R, output_dir: &Path) -> Res
         Ok(mut zip) => {
             info!("Extracting ZIP archive");
             for i in 0..zip.len() {
-                let mut file = zip.by_index(i).map_err(|e| BibExtractError::ZipError(e))?;
+                let mut file = zip.by_index(i).map_err(BibExtractError::ZipError)?;
                 let outpath = match file.enclosed_name() {
                     Some(path) => output_dir.join(path),
                     None => continue,
                 };
 
                 if file.name().ends_with('/') {
-                    fs::create_dir_all(&outpath).map_err(|e| BibExtractError::IoError(e))?;
+                    fs::create_dir_all(&outpath).map_err(BibExtractError::IoError)?;
                 } else {
                     if let Some(p) = outpath.parent() {
                         if !p.exists() {
-                            fs::create_dir_all(p).map_err(|e| BibExtractError::IoError(e))?;
+                            fs::create_dir_all(p).map_err(BibExtractError::IoError)?;
                         }
                     }
-                    let mut outfile = fs::File::create(&outpath).map_err(|e| BibExtractError::IoError(e))?;
-                    io::copy(&mut file, &mut outfile).map_err(|e| BibExtractError::IoError(e))?;
+                    let mut outfile = fs::File::create(&outpath).map_err(BibExtractError::IoError)?;
+                    io::copy(&mut file, &mut outfile).map_err(BibExtractError::IoError)?;
                 }
             }
-            return Ok(());
+            Ok(())
         },
         Err(_) => {
             // Rewind the file
-            archive.seek(SeekFrom::Start(0)).map_err(|e| BibExtractError::IoError(e))?;
+            archive.seek(SeekFrom::Start(0)).map_err(BibExtractError::IoError)?;
             
             // Try as tar.gz
             info!("Trying to extract as TAR.GZ archive");
             let gz = GzDecoder::new(archive);
             let mut tar = Archive::new(gz);
-            tar.unpack(output_dir).map_err(|e| BibExtractError::IoError(e))?;
-            return Ok(());
+            tar.unpack(output_dir).map_err(BibExtractError::IoError)?;
+            Ok(())
         }
     }
 }
@@ -159,7 +209,7 @@ pub fn find_bbl_files(dir: &Path) -> Result<Vec<PathBuf>, BibExtractError> {
         .filter_map(Result::ok)
         .filter(|entry| {
             entry.path().is_file() && 
-            entry.path().extension().map_or(false, |ext| ext == "bbl")
+            entry.path().extension().is_some_and(|ext| ext == "bbl")
         })
         .map(|entry| entry.path().to_path_buf())
         .collect();
@@ -167,6 +217,205 @@ pub fn find_bbl_files(dir: &Path) -> Result<Vec<PathBuf>, BibExtractError> {
     Ok(bbl_files)
 }
 
+/// Find the `.bbl` files that belong to the document compiled from `main_tex_file`.
+///
+/// BibTeX writes `<jobname>.bbl`, so the main file's base name selects the document's own
+/// bibliography. multibib (`\newcites`) and chapterbib (one `.bbl` per included file) add
+/// further files, and `.bbl` files named after a `\bibliography` database are accepted too.
+/// When a name occurs more than once, the copy next to the main file wins. Stale builds and
+/// the bibliographies of other documents (supplements, responses to reviewers) are ignored.
+/// If no file matches, a single `.bbl` is still used and several are all returned.
+pub fn find_document_bbl_files(
+    base_dir: &Path,
+    main_tex_file: &Path,
+    included_files: &[PathBuf],
+    content: &str,
+) -> Result<Vec<PathBuf>, BibExtractError> {
+    let bbl_files = find_bbl_files(base_dir)?;
+
+    let stem = |path: &Path| path.file_stem().map(|stem| stem.to_string_lossy().to_string());
+    let uncommented = LATEX_COMMENT_REGEX.replace_all(content, "$1");
+    let mut names: Vec<String> = stem(main_tex_file).into_iter().collect();
+    let declared = NEWCITES_REGEX
+        .captures_iter(&uncommented)
+        .chain(BIBLIOGRAPHY_DECL_REGEX.captures_iter(&uncommented))
+        .flat_map(|cap| cap[1].split(',').filter_map(|name| stem(Path::new(name.trim()))).collect::<Vec<_>>());
+    let included = included_files.iter().filter_map(|path| stem(path));
+    for name in declared.chain(included) {
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let main_dir = main_tex_file.parent().unwrap_or(base_dir);
+    let mut selected = Vec::new();
+    for name in &names {
+        let mut candidates: Vec<&PathBuf> = bbl_files.iter().filter(|path| stem(path).as_deref() == Some(name)).collect();
+        // Closest to the main file first, then shallowest in the source tree
+        candidates.sort_by_key(|path| (path.parent() != Some(main_dir), path.components().count()));
+        if let Some(path) = candidates.first() {
+            selected.push((*path).clone());
+        }
+    }
+
+    if selected.is_empty() {
+        if bbl_files.len() > 1 {
+            log::warn!("No .bbl file matches the document {:?}; using all {}", main_tex_file, bbl_files.len());
+        }
+        return Ok(bbl_files);
+    }
+    for ignored in bbl_files.iter().filter(|path| !selected.contains(path)) {
+        info!("Ignoring {:?}, which does not belong to the document {:?}", ignored, main_tex_file);
+    }
+    Ok(selected)
+}
+
+/// Citation commands multibib defines for each list, as `\cite<list>`
+const MULTIBIB_COMMANDS: [&str; 8] = ["cite", "nocite", "citep", "citet", "citealp", "citealt", "citeauthor", "citeyear"];
+
+/// Parse the `.bbl` files of a document, each with its own key map, and consolidate them.
+///
+/// The files of multibib lists (`\newcites`) and chapterbib chapters (named after an included
+/// file) resolve only their own citations, so a key they declare for another work than an
+/// earlier file does is renamed in them (see `Bibliography::consolidate_bbl_units`) and in
+/// the citations they resolve. Returns the entries and `content` with those citations renamed.
+pub fn parse_document_bbl_files(
+    bbl_files: &[PathBuf],
+    content: &str,
+    included_files: &[IncludedFile],
+) -> Result<(Bibliography, String), BibExtractError> {
+    let stem = |path: &Path| path.file_stem().map(|stem| stem.to_string_lossy().to_string());
+    let uncommented = LATEX_COMMENT_REGEX.replace_all(content, "$1");
+    let lists: Vec<String> = NEWCITES_REGEX
+        .captures_iter(&uncommented)
+        .flat_map(|cap| cap[1].split(',').map(|name| name.trim().to_string()).collect::<Vec<_>>())
+        .collect();
+
+    log::info!("Parsing {} BBL files", bbl_files.len());
+    let mut units = Vec::new();
+    for bbl_file in bbl_files {
+        let name = stem(bbl_file);
+        let scope = if let Some(list) = lists.iter().find(|list| Some(*list) == name.as_ref()) {
+            BblScope::List(list.clone())
+        } else if let Some((chapter, _)) = included_files.iter().find(|(path, _)| stem(path) == name) {
+            BblScope::Chapter(chapter.clone())
+        } else {
+            BblScope::Document
+        };
+        units.extend(BblUnit::parse(bbl_file.clone(), scope)?);
+    }
+    let scopes: HashMap<PathBuf, BblScope> = units.iter().map(|unit| (unit.path.clone(), unit.scope.clone())).collect();
+
+    let (bibliography, conflicts) = Bibliography::consolidate_bbl_units(units);
+    let mut renames: HashMap<&BblScope, HashMap<&str, &str>> = HashMap::new();
+    for conflict in &conflicts {
+        log::warn!("{}", conflict);
+        if let Some(renamed) = &conflict.renamed {
+            renames.entry(&scopes[&conflict.other]).or_default().insert(&conflict.key, renamed);
+        }
+    }
+    if renames.is_empty() {
+        return Ok((bibliography, content.to_string()));
+    }
+
+    let chapters: Vec<&IncludedFile> = included_files
+        .iter()
+        .filter(|(path, _)| scopes.values().any(|scope| *scope == BblScope::Chapter(path.clone())))
+        .collect();
+    let scoped = ANY_CITE_REGEX.replace_all(content, |cap: &regex::Captures| {
+        let citation = cap.get(0).unwrap();
+        let keys = cap.get(1).unwrap();
+        let command: String = citation.as_str()[1..].chars().take_while(char::is_ascii_alphabetic).collect();
+        let list = lists
+            .iter()
+            .find(|list| command.strip_suffix(list.as_str()).is_some_and(|base| MULTIBIB_COMMANDS.contains(&base)))
+            .map(|list| BblScope::List(list.clone()));
+        // Citations in a nested chapter belong to it
+        let chapter = chapters
+            .iter()
+            .filter(|(_, span)| span.contains(&citation.start()))
+            .min_by_key(|(_, span)| span.len())
+            .map(|(path, _)| BblScope::Chapter(path.clone()));
+        let Some(renamed) = list.or(chapter).and_then(|scope| renames.get(&scope)) else {
+            return citation.as_str().to_string();
+        };
+        let renamed_keys: Vec<String> = keys
+            .as_str()
+            .split(',')
+            .map(|key| match renamed.get(key.trim()) {
+                Some(renamed) => key.replacen(key.trim(), renamed, 1),
+                None => key.to_string(),
+            })
+            .collect();
+        format!(
+            "{}{}{}",
+            &content[citation.start()..keys.start()],
+            renamed_keys.join(","),
+            &content[keys.end()..citation.end()]
+        )
+    });
+    Ok((bibliography, scoped.into_owned()))
+}
+
+/// Find the `.bib` databases declared with `\bibliography` or `\addbibresource` and return their contents.
+///
+/// Databases are looked up next to the main file, then in the source root, then anywhere in
+/// the source tree. Databases embedded in `filecontents` environments are used when no file
+/// with that name exists.
+pub fn find_bib_databases(base_dir: &Path, main_tex_file: &Path, content: &str) -> Result<Vec<String>, BibExtractError> {
+    let embedded: Vec<(String, String)> = FILECONTENTS_REGEX
+        .captures_iter(content)
+        .map(|cap| (cap[1].trim().to_string(), cap[2].to_string()))
+        .collect();
+
+    let uncommented = LATEX_COMMENT_REGEX.replace_all(content, "$1");
+    let mut names: Vec<String> = Vec::new();
+    for cap in BIBLIOGRAPHY_DECL_REGEX.captures_iter(&uncommented) {
+        for name in cap[1].split(',') {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            let name = if name.ends_with(".bib") { name.to_string() } else { format!("{}.bib", name) };
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    let main_dir = main_tex_file.parent().unwrap_or(base_dir);
+    let mut databases = Vec::new();
+    for name in &names {
+        let on_disk = [main_dir.join(name), base_dir.join(name)]
+            .into_iter()
+            .find(|path| path.is_file())
+            .or_else(|| {
+                let file_name = Path::new(name).file_name()?;
+                WalkDir::new(base_dir)
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .find(|entry| entry.path().is_file() && entry.file_name() == file_name)
+                    .map(|entry| entry.path().to_path_buf())
+            });
+
+        if let Some(path) = on_disk {
+            info!("Using BibTeX database {:?}", path);
+            // Older databases are often Latin-1; their accented letters are lost, not the file
+            databases.push(String::from_utf8_lossy(&fs::read(&path)?).into_owned());
+        } else if let Some((_, body)) = embedded
+            .iter()
+            .find(|(embedded_name, _)| embedded_name == name || format!("{}.bib", embedded_name) == *name)
+        {
+            info!("Using BibTeX database {} from a filecontents environment", name);
+            databases.push(body.clone());
+        } else {
+            info!("BibTeX database {} is declared but not shipped with the source", name);
+        }
+    }
+
+    Ok(databases)
+}
+
 /// Find the main LaTeX file in a directory
 pub fn find_main_tex_file(dir: &Path) -> Result<PathBuf, BibExtractError> {
     // Look for common main file names
@@ -185,14 +434,14 @@ pub fn find_main_tex_file(dir: &Path) -> Result<PathBuf, BibExtractError> {
         .filter_map(Result::ok)
         .filter(|entry| {
             entry.path().is_file() && 
-            entry.path().extension().map_or(false, |ext| ext == "tex")
+            entry.path().extension().is_some_and(|ext| ext == "tex")
         })
         .map(|entry| entry.path().to_path_buf())
         .collect();
     
     // Check for files with \documentclass
     for file in &tex_files {
-        if let Ok(content) = fs::read_to_string(file).map_err(|e| BibExtractError::IoError(e)) {
+        if let Ok(content) = fs::read_to_string(file).map_err(BibExtractError::IoError) {
             if content.contains(r"\documentclass") {
                 return Ok(file.clone());
             }
@@ -212,12 +461,23 @@ pub fn extract_all_latex_from_files(
     base_dir: &Path,
     main_tex_file: &Path,
 ) -> Result<(String, Vec<PathBuf>), BibExtractError> {
+    let (content, included_files) = extract_all_latex_with_spans(base_dir, main_tex_file)?;
+    Ok((content, included_files.into_iter().map(|(path, _)| path).collect()))
+}
+
+/// Extract all LaTeX content like `extract_all_latex_from_files`, along with where the text
+/// of each included file, with the files it includes, ends up in the content
+pub fn extract_all_latex_with_spans(
+    base_dir: &Path,
+    main_tex_file: &Path,
+) -> Result<(String, Vec<IncludedFile>), BibExtractError> {
     let mut included_files = Vec::new();
     let mut processed_files = Vec::new();
     
     let content = extract_latex_content(
         base_dir,
         main_tex_file,
+        0,
         &mut included_files,
         &mut processed_files,
     )?;
@@ -225,11 +485,13 @@ pub fn extract_all_latex_from_files(
     Ok((content, included_files))
 }
 
-/// Recursive helper function to extract LaTeX content
+/// Recursive helper function to extract LaTeX content, which starts at `offset` in the
+/// content of the main file
 fn extract_latex_content(
     base_dir: &Path,
     tex_file: &Path,
-    included_files: &mut Vec<PathBuf>,
+    offset: usize,
+    included_files: &mut Vec<IncludedFile>,
     processed_files: &mut Vec<PathBuf>,
 ) -> Result<String, BibExtractError> {
     // Avoid processing the same file twice
@@ -240,13 +502,15 @@ fn extract_latex_content(
     // Mark this file as processed
     processed_files.push(tex_file.to_path_buf());
     
-    // Add to included_files (excluding the main file which is the first one processed)
-    if processed_files.len() > 1 {
-        included_files.push(tex_file.to_path_buf());
-    }
+    // Add to included_files (excluding the main file which is the first one processed);
+    // its span is known once its content is
+    let included_index = (processed_files.len() > 1).then(|| {
+        included_files.push((tex_file.to_path_buf(), offset..offset));
+        included_files.len() - 1
+    });
     
     // Read the file content
-    let content = fs::read_to_string(tex_file).map_err(|e| BibExtractError::IoError(e))?;
+    let content = fs::read_to_string(tex_file).map_err(BibExtractError::IoError)?;
     
     // Look for \input and \include commands
     let mut result = String::new();
@@ -268,6 +532,7 @@ fn extract_latex_content(
             let included_content = extract_latex_content(
                 base_dir,
                 &input_path,
+                offset + result.len(),
                 included_files,
                 processed_files,
             )?;
@@ -279,6 +544,10 @@ fn extract_latex_content(
     // Add any remaining content
     result.push_str(&content[last_end..]);
     
+    if let Some(index) = included_index {
+        included_files[index].1 = offset..offset + result.len();
+    }
+    
     Ok(result)
 }
 
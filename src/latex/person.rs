@@ -0,0 +1,420 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::fmt;
+
+// "et al." in any of the spellings styles produce, possibly emphasized
+static ET_AL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\\emph\{|\{\\em\s+|\\textit\{)?\bet(?:~|\s)*al\b\.?\}?|\band\s+others\b")
+        .expect("Invalid et al. regex pattern")
+});
+// A single initial such as `J.`, `J` or `J.-P.`
+static INITIAL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\p{Lu}\.?(?:-\p{Lu}\.?)*$").expect("Invalid initial regex pattern")
+});
+// Accents written with a symbol: \'e, \'{e}, \"{\i}
+static SYMBOL_ACCENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\\(['`^"~=.])\s*(?:\{\s*(\\?[A-Za-z])\s*\}|(\\?[A-Za-z]))"#).expect("Invalid accent regex pattern")
+});
+// Accents written with a letter, which needs a space or braces: \v{c}, \H o
+static LETTER_ACCENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\([uvHckr])(?:\s*\{\s*(\\?[A-Za-z])\s*\}|\s+(\\?[A-Za-z]))").expect("Invalid accent regex pattern")
+});
+static SPECIAL_LETTER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(ss|aa|AA|ae|AE|oe|OE|o|O|l|L|i|j)(?:\{\}|\s+|\b)").expect("Invalid special letter regex pattern")
+});
+// A letter left alone in braces after accent replacement: Erd{ő}s
+static BRACED_LETTER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{(\p{L})\}").expect("Invalid braced letter regex pattern")
+});
+
+/// Precomposed characters for each accent, as parallel strings of base letters and results
+const ACCENTS: [(char, &str, &str); 13] = [
+    ('\'', "acegilnorsuyzACEGILNORSUYZ", "áćéǵíĺńóŕśúýźÁĆÉǴÍĹŃÓŔŚÚÝŹ"),
+    ('`', "aeinouAEINOU", "àèìǹòùÀÈÌǸÒÙ"),
+    ('^', "aceghijosuwyACEGHIJOSUWY", "âĉêĝĥîĵôŝûŵŷÂĈÊĜĤÎĴÔŜÛŴŶ"),
+    ('"', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+    ('~', "ainouAINOU", "ãĩñõũÃĨÑÕŨ"),
+    ('=', "aeiouyAEIOUY", "āēīōūȳĀĒĪŌŪȲ"),
+    ('.', "acegozACEGIOZ", "ȧċėġȯżȦĊĖĠİȮŻ"),
+    ('u', "aegiouAEGIOU", "ăĕğĭŏŭĂĔĞĬŎŬ"),
+    ('v', "acdeghijklnorstuzACDEGHIKLNORSTUZ", "ǎčďěǧȟǐǰǩľňǒřšťǔžǍČĎĚǦȞǏǨĽŇǑŘŠŤǓŽ"),
+    ('H', "ouOU", "őűŐŰ"),
+    ('c', "cegklnrstCEGKLNRST", "çȩģķļņŗşţÇȨĢĶĻŅŖŞŢ"),
+    ('k', "aeiouAEIOU", "ąęįǫųĄĘĮǪŲ"),
+    ('r', "auAU", "åůÅŮ"),
+];
+
+/// A person's name split into the four BibTeX name parts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Person {
+    pub first: String,
+    pub von: String,
+    pub last: String,
+    pub jr: String,
+}
+
+/// An ordered list of people, e.g. the authors of an entry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersonList {
+    pub persons: Vec<Person>,
+    /// The list ended with "et al." or "and others"
+    pub is_truncated: bool,
+}
+
+impl Person {
+    /// Parse a single BibTeX name: "First von Last", "von Last, First" or "von Last, Jr, First"
+    pub fn parse(name: &str) -> Self {
+        let name = latex_to_unicode(name);
+        let parts: Vec<&str> = split_top_level(&name, ',').into_iter().map(str::trim).collect();
+        let mut person = Person::default();
+
+        let (first, von_last, jr) = match parts.as_slice() {
+            [name] => {
+                let words = words(name);
+                // First is everything before the first lowercase word; the last word is always Last
+                let split = words[..words.len().saturating_sub(1)]
+                    .iter()
+                    .position(|word| is_lowercase_word(word))
+                    .unwrap_or(words.len().saturating_sub(1));
+                (words[..split].join(" "), words[split..].to_vec(), String::new())
+            }
+            [von_last, first] => (first.to_string(), words(von_last), String::new()),
+            [von_last, jr, first, ..] => (first.to_string(), words(von_last), jr.to_string()),
+            [] => return person,
+        };
+
+        // von is the longest run of words up to the last lowercase word, never the final word
+        let von_end = von_last[..von_last.len().saturating_sub(1)]
+            .iter()
+            .rposition(|word| is_lowercase_word(word))
+            .map_or(0, |i| i + 1);
+        person.first = first;
+        person.von = von_last[..von_end].join(" ");
+        person.last = von_last[von_end..].join(" ");
+        person.jr = jr;
+        person
+    }
+
+    /// "von Last" as used in citations
+    pub fn surname(&self) -> String {
+        [self.von.as_str(), self.last.as_str()]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The name in BibTeX form: "von Last, Jr, First"
+    pub fn to_bibtex(&self) -> String {
+        let mut name = self.surname();
+        if !self.jr.is_empty() {
+            name.push_str(", ");
+            name.push_str(&self.jr);
+        }
+        if !self.first.is_empty() {
+            name.push_str(", ");
+            name.push_str(&self.first);
+        }
+        name
+    }
+}
+
+impl fmt::Display for Person {
+    /// The name in reading order: "First von Last, Jr"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut name = [self.first.as_str(), self.von.as_str(), self.last.as_str()]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !self.jr.is_empty() {
+            name.push_str(", ");
+            name.push_str(&self.jr);
+        }
+        write!(f, "{}", name)
+    }
+}
+
+impl PersonList {
+    /// Parse a BibTeX "and"-separated name list; a final "others" marks the list as truncated
+    pub fn parse_bibtex(names: &str) -> Self {
+        let mut list = PersonList::default();
+        // Tolerate a free-text "et al." as well
+        let names = match ET_AL_REGEX.find(names) {
+            Some(m) => {
+                list.is_truncated = true;
+                names[..m.start()].trim_end_matches([',', ' '])
+            }
+            None => names,
+        };
+        for name in split_and(names) {
+            if name.eq_ignore_ascii_case("others") {
+                list.is_truncated = true;
+            } else {
+                list.persons.push(Person::parse(name));
+            }
+        }
+        list
+    }
+
+    /// Parse an author list as typeset in a bibliography.
+    ///
+    /// Handles "A and B", "A, B, and C", "Last, F. and Last, G.", "Last, F., Last, G." and
+    /// lists ending in "et al.". Use `parse_bibtex` for fields already in BibTeX form.
+    pub fn parse(text: &str) -> Self {
+        let (text, is_truncated) = match ET_AL_REGEX.find(text) {
+            Some(m) => (&text[..m.start()], true),
+            None => (text, false),
+        };
+        let text = latex_to_unicode(&strip_formatting(text));
+        let text = trim_list_end(&text);
+
+        let parts: Vec<&str> = split_and(text)
+            .into_iter()
+            .map(|part| part.trim().trim_end_matches(',').trim())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        let names: Vec<String> = if parts.iter().all(|part| comma_count(part) == 0) {
+            parts.iter().map(|part| part.to_string()).collect()
+        } else if parts.len() > 1 && parts.iter().all(|part| comma_count(part) == 0 || is_inverted_name(part)) {
+            // BibTeX-like "Last, First and Last, First"
+            parts.iter().map(|part| part.to_string()).collect()
+        } else {
+            let segments: Vec<&str> = parts
+                .iter()
+                .flat_map(|part| split_top_level(part, ','))
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            let inverted = segments.len().is_multiple_of(2)
+                && segments
+                    .chunks(2)
+                    .all(|pair| is_surname(pair[0]) && looks_like_given_names(pair[1]));
+            if inverted {
+                segments.chunks(2).map(|pair| format!("{}, {}", pair[0], pair[1])).collect()
+            } else {
+                segments.iter().map(|segment| segment.to_string()).collect()
+            }
+        };
+
+        PersonList {
+            persons: names.iter().map(|name| Person::parse(name)).filter(|p| !p.last.is_empty()).collect(),
+            is_truncated,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.persons.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.persons.len()
+    }
+
+    pub fn first(&self) -> Option<&Person> {
+        self.persons.first()
+    }
+
+    /// The list as a BibTeX "and"-list, ending in "and others" when truncated
+    pub fn to_bibtex(&self) -> String {
+        let mut names: Vec<String> = self.persons.iter().map(Person::to_bibtex).collect();
+        if self.is_truncated {
+            names.push("others".to_string());
+        }
+        names.join(" and ")
+    }
+}
+
+/// Replace LaTeX accent commands and special letters with Unicode characters and ties with spaces
+pub fn latex_to_unicode(text: &str) -> String {
+    let compose = |accent: char, base: &str| -> Option<char> {
+        let base = match base {
+            "\\i" => 'i',
+            "\\j" => 'j',
+            _ => base.chars().next()?,
+        };
+        let (_, bases, results) = ACCENTS.iter().find(|(a, _, _)| *a == accent)?;
+        let index = bases.chars().position(|c| c == base)?;
+        results.chars().nth(index)
+    };
+    let replace_accent = |cap: &Captures| {
+        let accent = cap[1].chars().next().unwrap_or(' ');
+        let base = cap.get(2).or_else(|| cap.get(3)).map_or("", |m| m.as_str());
+        match compose(accent, base) {
+            Some(c) => c.to_string(),
+            None => base.trim_start_matches('\\').to_string(),
+        }
+    };
+
+    let text = SYMBOL_ACCENT_REGEX.replace_all(text, replace_accent);
+    let text = LETTER_ACCENT_REGEX.replace_all(&text, replace_accent);
+    let text = SPECIAL_LETTER_REGEX.replace_all(&text, |cap: &Captures| {
+        match &cap[1] {
+            "ss" => "ß",
+            "aa" => "å",
+            "AA" => "Å",
+            "ae" => "æ",
+            "AE" => "Æ",
+            "oe" => "œ",
+            "OE" => "Œ",
+            "o" => "ø",
+            "O" => "Ø",
+            "l" => "ł",
+            "L" => "Ł",
+            "i" => "i",
+            _ => "j",
+        }
+        .to_string()
+    });
+    let text = BRACED_LETTER_REGEX.replace_all(&text, "$1");
+    text.replace('~', " ").replace("\\&", "&")
+}
+
+/// Replace accented letters by their base letters, e.g. "Gödel" becomes "Godel"
+pub fn fold_accents(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'ø' => folded.push('o'),
+            'Ø' => folded.push('O'),
+            'ł' => folded.push('l'),
+            'Ł' => folded.push('L'),
+            _ => {
+                let base = ACCENTS.iter().find_map(|(_, bases, results)| {
+                    results.chars().position(|r| r == c).and_then(|index| bases.chars().nth(index))
+                });
+                folded.push(base.unwrap_or(c));
+            }
+        }
+    }
+    folded
+}
+
+/// Remove emphasis commands and grouping macros around names
+fn strip_formatting(text: &str) -> String {
+    let text = text
+        .replace("\\bgroup", "")
+        .replace("\\egroup", "")
+        .replace("\\emph{", "{")
+        .replace("\\textsc{", "{")
+        .replace("{\\em ", "{")
+        .replace("{\\sc ", "{");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Drop trailing separators and the final period, unless it ends an initial
+fn trim_list_end(text: &str) -> &str {
+    let text = text.trim_end_matches([',', ';', ' ']);
+    let text = text.trim_end().trim_end_matches(" and").trim_end_matches([',', ' ']);
+    let last_word = text.rsplit(' ').next().unwrap_or("");
+    if text.ends_with('.') && !INITIAL_REGEX.is_match(last_word.rsplit(',').next().unwrap_or("")) {
+        text.trim_end_matches('.')
+    } else {
+        text
+    }
+}
+
+/// Split on `sep` outside braces
+fn split_top_level(text: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ if c == sep && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Split a name list on " and " (and "&") outside braces
+fn split_and(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => depth -= 1,
+            b if depth == 0 && b.is_ascii_whitespace() => {
+                let rest = &bytes[i + 1..];
+                let separator = if rest.len() > 4 && rest[..3].eq_ignore_ascii_case(b"and") && rest[3].is_ascii_whitespace() {
+                    Some(4)
+                } else if rest.starts_with(b"& ") {
+                    Some(2)
+                } else {
+                    None
+                };
+                if let Some(len) = separator {
+                    parts.push(text[start..i].trim());
+                    i += 1 + len;
+                    start = i;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(text[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// Whitespace-separated words outside braces
+fn words(text: &str) -> Vec<String> {
+    split_top_level(text, ' ')
+        .into_iter()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A von particle starts with a lowercase letter outside braces
+fn is_lowercase_word(word: &str) -> bool {
+    if word.starts_with('{') {
+        return false;
+    }
+    word.chars().find(|c| c.is_alphabetic()).is_some_and(char::is_lowercase)
+}
+
+fn comma_count(text: &str) -> usize {
+    split_top_level(text, ',').len() - 1
+}
+
+/// "von Last, First" with a plausible surname before the single comma
+fn is_inverted_name(text: &str) -> bool {
+    let parts = split_top_level(text, ',');
+    parts.len() == 2 && is_surname(parts[0]) && !parts[1].trim().is_empty()
+}
+
+/// A single capitalized word, optionally preceded by lowercase von particles
+fn is_surname(text: &str) -> bool {
+    let words = words(text);
+    match words.split_last() {
+        Some((last, von)) => !is_lowercase_word(last) && von.iter().all(|word| is_lowercase_word(word)),
+        None => false,
+    }
+}
+
+/// Given names as typeset by author-year styles always contain an initial
+fn looks_like_given_names(text: &str) -> bool {
+    words(text).iter().any(|word| INITIAL_REGEX.is_match(word))
+}
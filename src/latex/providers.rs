@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::{info, warn};
+
+use crate::error::BibExtractError;
+use crate::latex::arxiv_search::ArxivSearchProvider;
+use crate::latex::diagnostics::diagnose_entry;
+use crate::latex::crossref::CrossrefProvider;
+use crate::latex::openalex::OpenAlexProvider;
+use crate::latex::semantic_scholar::SemanticScholarProvider;
+use crate::latex::similarity::{match_record, MatchThresholds};
+use crate::latex::verification::{ArxivProvider, DblpProvider};
+use crate::latex::{BibEntry, BibEntryBuilder, FieldSource};
+
+/// A record a metadata provider found for a bibliography entry
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    /// The provider's version of the entry
+    pub entry: BibEntry,
+    /// How well the record matches the entry, from 0 to 1; identifier lookups score 1.
+    /// The score of the candidate applied is recorded as `verification_score`.
+    pub score: f32,
+    /// Name of the provider that found it, recorded as `verified_source`
+    pub provider: String,
+}
+
+impl Candidate {
+    pub fn new(entry: BibEntry, score: f32, provider: impl Into<String>) -> Self {
+        Self { entry, score, provider: provider.into() }
+    }
+}
+
+/// A source of verified bibliographic metadata (arXiv, DBLP, a local database...).
+///
+/// Providers implement either or both lookups; the defaults find nothing.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Name of the provider, e.g. "DBLP"
+    fn name(&self) -> &str;
+
+    /// Look the entry up by an identifier written in it (arXiv ID, DOI...)
+    async fn lookup_id(&self, _entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        Ok(None)
+    }
+
+    /// Search for the entry by its title and authors, best candidates first
+    async fn search(&self, _entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        Ok(Vec::new())
+    }
+
+    /// Fetch what the lookups of a whole bibliography will need ahead of time, e.g. with a
+    /// batch endpoint. Called once before the entries are verified one by one.
+    async fn prefetch(&self, _entries: &[BibEntry]) -> Result<(), BibExtractError> {
+        Ok(())
+    }
+}
+
+/// How the matches of several providers are combined into the entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Use the match of the first provider, in priority order, that finds one
+    #[default]
+    FirstMatch,
+    /// Query every provider and use the highest-scoring match; ties go to the higher priority
+    BestScore,
+    /// Query every provider; later matches fill the fields the first one lacks
+    FillMissing,
+}
+
+/// Verifies bibliography entries against an ordered list of metadata providers
+#[derive(Clone)]
+pub struct Verifier {
+    providers: Vec<Arc<dyn MetadataProvider>>,
+    policy: MergePolicy,
+    min_score: f32,
+}
+
+impl std::fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Verifier")
+            .field("providers", &self.provider_names())
+            .field("policy", &self.policy)
+            .field("min_score", &self.min_score)
+            .finish()
+    }
+}
+
+/// Names of the providers of `Verifier::default`, in its order
+const DEFAULT_ORDER: [&str; 6] = ["arXiv", "DBLP", "Semantic Scholar", "Crossref", "OpenAlex", "arXiv search"];
+
+impl Default for Verifier {
+    /// arXiv, DBLP, Crossref, then OpenAlex, keeping the first match. Semantic Scholar comes
+    /// after DBLP when `SEMANTIC_SCHOLAR_API_KEY` is set, since its shared unauthenticated
+    /// rate limit would slow down every run. Entries none of them verify are searched for on
+    /// arXiv by title last, as its rate limit is slower still.
+    fn default() -> Self {
+        Self::with_default_providers(std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok())
+    }
+}
+
+impl Verifier {
+    /// A verifier without providers, which verifies nothing until some are registered
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            policy: MergePolicy::default(),
+            min_score: 0.0,
+        }
+    }
+
+    /// The providers of `Verifier::default`, with Semantic Scholar only when given an API key
+    /// rather than when `SEMANTIC_SCHOLAR_API_KEY` is set
+    pub fn with_default_providers(semantic_scholar_key: Option<String>) -> Self {
+        let mut verifier = Self::new().with_provider(ArxivProvider).with_provider(DblpProvider::new());
+        if let Some(key) = semantic_scholar_key.filter(|key| !key.is_empty()) {
+            verifier = verifier.with_provider(SemanticScholarProvider::new().with_api_key(key));
+        }
+        verifier
+            .with_provider(CrossrefProvider)
+            .with_provider(OpenAlexProvider)
+            .with_provider(ArxivSearchProvider::new())
+    }
+
+    /// Register a provider with the lowest priority so far
+    pub fn with_provider(mut self, provider: impl MetadataProvider + 'static) -> Self {
+        self.register(Arc::new(provider));
+        self
+    }
+
+    /// Set how the matches of several providers are combined
+    pub fn with_policy(mut self, policy: MergePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Ignore candidates scoring below `min_score`
+    pub fn with_min_score(mut self, min_score: f32) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Register a provider with the lowest priority so far
+    pub fn register(&mut self, provider: Arc<dyn MetadataProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Put `provider` in the place of the provider called `name`. If there is none, it goes
+    /// where the default chain has that provider, so that a verifier built up from
+    /// `Verifier::new` keeps the default order; providers outside it go last.
+    pub fn replace(&mut self, name: &str, provider: Arc<dyn MetadataProvider>) {
+        if let Some(position) = self.providers.iter().position(|registered| registered.name().eq_ignore_ascii_case(name)) {
+            self.providers[position] = provider;
+            return;
+        }
+        let default_rank = |name: &str| {
+            DEFAULT_ORDER.iter().position(|default| default.eq_ignore_ascii_case(name)).unwrap_or(DEFAULT_ORDER.len())
+        };
+        let rank = default_rank(name);
+        let position = if rank == DEFAULT_ORDER.len() {
+            self.providers.len()
+        } else {
+            // Right after the last provider that comes before it by default
+            self.providers
+                .iter()
+                .rposition(|registered| default_rank(registered.name()) <= rank)
+                .map_or(0, |position| position + 1)
+        };
+        self.providers.insert(position, provider);
+    }
+
+    /// Unregister the providers called `name`
+    pub fn remove(&mut self, name: &str) {
+        self.providers.retain(|provider| !provider.name().eq_ignore_ascii_case(name));
+    }
+
+    /// Reorder the providers: those named come first, in the given order, the others keep
+    /// their relative order after them. Unknown names are ignored.
+    pub fn set_priority(&mut self, names: &[&str]) {
+        let rank = |provider: &Arc<dyn MetadataProvider>| {
+            names.iter().position(|name| name.eq_ignore_ascii_case(provider.name())).unwrap_or(names.len())
+        };
+        self.providers.sort_by_key(rank);
+    }
+
+    /// Names of the registered providers, in priority order
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers.iter().map(|provider| provider.name()).collect()
+    }
+
+    pub fn policy(&self) -> MergePolicy {
+        self.policy
+    }
+
+    /// Let every provider prefetch what it needs to verify `entries`
+    pub async fn prefetch(&self, entries: &[BibEntry]) {
+        for provider in &self.providers {
+            if let Err(e) = provider.prefetch(entries).await {
+                warn!("{} prefetch failed: {}", provider.name(), e);
+            }
+        }
+    }
+
+    /// The best candidate of one provider: its identifier lookup, or else its top search result
+    async fn best_candidate(&self, provider: &dyn MetadataProvider, entry: &BibEntry) -> Option<Candidate> {
+        let found = match provider.lookup_id(entry).await {
+            Ok(Some(candidate)) => Ok(vec![candidate]),
+            Ok(None) => provider.search(entry).await,
+            Err(e) => Err(e),
+        };
+        match found {
+            Ok(candidates) => candidates
+                .into_iter()
+                .filter(|candidate| candidate.score >= self.min_score)
+                .fold(None, |best: Option<Candidate>, candidate| match best {
+                    Some(best) if best.score >= candidate.score => Some(best),
+                    _ => Some(candidate),
+                }),
+            Err(e) => {
+                // One failing source should not stop the others
+                warn!("{} lookup failed for {}: {}", provider.name(), entry.key, e);
+                None
+            }
+        }
+    }
+
+    /// The matches for an entry, in priority order, as far as the merge policy needs them
+    pub async fn candidates(&self, entry: &BibEntry) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        for provider in &self.providers {
+            if let Some(candidate) = self.best_candidate(provider.as_ref(), entry).await {
+                info!("{} matched {} (score {:.2})", candidate.provider, entry.key, candidate.score);
+                candidates.push(candidate);
+                if self.policy == MergePolicy::FirstMatch {
+                    break;
+                }
+            }
+        }
+        candidates
+    }
+
+    /// The record of an arXiv paper, from the first provider that resolves its ID. Used for
+    /// the title and authors of downloaded papers when arXiv's BibTeX export is unavailable.
+    pub async fn lookup_arxiv_paper(&self, arxiv_id: &str) -> Option<BibEntry> {
+        let entry = BibEntryBuilder::new(arxiv_id, "misc")
+            .field("eprint", arxiv_id)
+            .field("archivePrefix", "arXiv")
+            .build();
+        for provider in &self.providers {
+            match provider.lookup_id(&entry).await {
+                Ok(Some(candidate)) => return Some(candidate.entry),
+                Ok(None) => {}
+                Err(e) => warn!("{} lookup failed for arXiv paper {}: {}", provider.name(), arxiv_id, e),
+            }
+        }
+        None
+    }
+
+    /// Verify an entry in place. Returns whether any provider matched it.
+    pub async fn verify_entry(&self, entry: &mut BibEntry) -> Result<bool, BibExtractError> {
+        let candidates = self.candidates(entry).await;
+        Ok(self.merge(entry, candidates))
+    }
+
+    /// Apply the matches to the entry according to the merge policy
+    pub fn merge(&self, entry: &mut BibEntry, mut candidates: Vec<Candidate>) -> bool {
+        if candidates.is_empty() {
+            return false;
+        }
+        if self.policy == MergePolicy::BestScore {
+            // Stable, so ties keep the priority order
+            candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+            candidates.truncate(1);
+        }
+        let mut candidates = candidates.into_iter();
+        let Some(Candidate { entry: mut verified, score, provider }) = candidates.next() else {
+            return false;
+        };
+        let mut sources = vec![provider];
+        if self.policy == MergePolicy::FillMissing {
+            for candidate in candidates {
+                let mut filled = false;
+                for (field, value) in candidate.entry.fields {
+                    if !is_verification_field(&field) && !verified.fields.contains_key(&field) {
+                        verified.fields.insert(field, value);
+                        filled = true;
+                    }
+                }
+                if filled {
+                    sources.push(candidate.provider);
+                }
+            }
+        }
+        apply_verified_entry(entry, verified, &sources.join(", "));
+        entry.set_with_source("verification_score", format!("{:.2}", score), FieldSource::Verified);
+        true
+    }
+}
+
+/// Copy the fields of a verified record into an entry, recording `source` as `verified_source`
+pub fn apply_verified_entry(entry: &mut BibEntry, verified: BibEntry, source: &str) {
+    for (field, value) in verified.fields {
+        // Fields from the author's own .bib database are authoritative
+        if field != "raw" && !is_verification_field(&field) && entry.source(&field) != Some(FieldSource::Bib) {
+            entry.set_with_source(&field, value, FieldSource::Verified);
+        }
+    }
+    // Entry types from the .bib database are the author's; inferred ones give way
+    if entry.type_source != Some(FieldSource::Bib) {
+        entry.entry_type = verified.entry_type;
+        entry.type_source = Some(FieldSource::Verified);
+    }
+    entry.set_with_source("verified_source", source.to_string(), FieldSource::Verified);
+    // Verified fields may fill in what the parser missed
+    if !entry.warnings.is_empty() {
+        diagnose_entry(entry);
+    }
+}
+
+/// Fields recording a verification (`verified_source`, and `verification_score`, the score of the
+/// match applied), which describe the entry rather than the record
+fn is_verification_field(field: &str) -> bool {
+    field == "verified_source" || field == "verification_score"
+}
+
+/// How well a record found by a title search matches the entry, from 0 to 1, scored as
+/// `similarity::match_record` scores it with the default thresholds
+pub fn match_score(entry: &BibEntry, candidate: &BibEntry) -> f32 {
+    let Some(title) = candidate.get("title") else {
+        return 0.0;
+    };
+    let year = candidate.get("year").map(String::as_str);
+    match_record(entry, title, year, &candidate.authors().persons, &MatchThresholds::default())
+}
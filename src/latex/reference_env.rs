@@ -0,0 +1,47 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// Reference list environments of journal classes that are not `thebibliography`
+static REFERENCE_ENV_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)\\begin\{(references|thereferences|harvard)\}(?:\{[^}]*\})?(.*?)\\end\{(?:references|thereferences|harvard)\}")
+        .expect("Invalid reference environment regex pattern")
+});
+
+// The item commands used inside them
+static REFERENCE_ITEM_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"\\harvarditem(?:\[[^\]]*\])?\{([^}]*)\}\{([^}]*)\}\{([^}]+)\}",
+        r"|\\reference\b(?:\[([^\]]*)\])?(?:\{([^}]*)\})?",
+        r"|\\item\b(?:\[([^\]]*)\])?",
+    ))
+    .expect("Invalid reference item regex pattern")
+});
+
+/// Rewrite the reference environments of journal classes as one `thebibliography` block.
+///
+/// aastex-style `\reference{key}`, IOP `\harvarditem[Short]{Authors}{Year}{key}` and bare
+/// `\item` entries become `\bibitem`s, so the style parsers read them like any `.bbl`.
+/// Items that declare no key are numbered `ref1`, `ref2`, ... in order.
+/// Returns None if the content has no such environment.
+pub fn to_thebibliography(content: &str) -> Option<String> {
+    let mut items = String::new();
+    let mut count = 0;
+    for env in REFERENCE_ENV_REGEX.captures_iter(content) {
+        let body = REFERENCE_ITEM_REGEX.replace_all(&env[2], |cap: &regex::Captures| {
+            count += 1;
+            if let Some(key) = cap.get(3) {
+                return format!("\\bibitem[{}({})]{{{}}}", &cap[1], &cap[2], key.as_str().trim());
+            }
+            let label = cap.get(4).or_else(|| cap.get(6)).map(|label| format!("[{}]", label.as_str()));
+            let key = cap
+                .get(5)
+                .map(|key| key.as_str().trim().to_string())
+                .filter(|key| !key.is_empty())
+                .unwrap_or_else(|| format!("ref{}", count));
+            format!("\\bibitem{}{{{}}}", label.unwrap_or_default(), key)
+        });
+        items.push_str(&body);
+        items.push('\n');
+    }
+    (!items.is_empty()).then(|| format!("\\begin{{thebibliography}}{{}}\n{}\\end{{thebibliography}}", items))
+}
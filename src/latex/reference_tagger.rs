@@ -0,0 +1,686 @@
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// Page ranges, most specific first: `pp. 1--10`, natbib's `35:1--10`, then bare `1--10`
+static TAGGER_PAGES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:pp?\.|pages)\s*(\d(?:[\d,]|\\,)*(?:\s*(?:-+|–)\s*\d(?:[\d,]|\\,)*)?)")
+        .expect("Invalid tagger pages regex pattern")
+});
+static TAGGER_COLON_PAGES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\d\s*(?:\(\d+\))?\s*:\s*(\d+\s*(?:-+|–)\s*\d+)").expect("Invalid tagger pages regex pattern")
+});
+static TAGGER_PAGE_RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\d+\s*(?:--|–)\s*\d+)\b").expect("Invalid tagger pages regex pattern")
+});
+static TAGGER_YEAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:19|20)\d{2}\b").expect("Invalid tagger year regex pattern")
+});
+
+/// Lowercase words that may appear inside a list of names
+const NAME_WORDS: [&str; 21] = [
+    "and", "et", "al", "van", "von", "der", "den", "de", "du", "la", "le", "di", "da", "del", "dos", "des", "y",
+    "others", "jr", "sr", "ter",
+];
+
+/// Abbreviations whose period does not end a sentence
+const ABBREVIATIONS: [&str; 53] = [
+    "al", "proc", "conf", "int", "intl", "j", "vol", "vols", "no", "nos", "pp", "p", "ed", "eds", "trans", "univ", "bull",
+    "dept", "inc", "ltd", "co", "corp", "jr", "sr", "st", "vs", "natl", "assoc", "soc", "symp", "rev", "lett",
+    "phys", "math", "comput", "sci", "res", "eng", "tech", "rep", "mag", "ann", "amer", "acad", "appl", "jan",
+    "feb", "mar", "apr", "jun", "jul", "aug", "sep",
+];
+const MONTH_ABBREVIATIONS: [&str; 4] = ["sept", "oct", "nov", "dec"];
+
+/// Words that mark a journal or proceedings name
+const VENUE_WORDS: [&str; 21] = [
+    "proceedings", "proc", "conference", "conf", "journal", "transactions", "trans", "symposium", "workshop",
+    "letters", "lett", "review", "advances", "annals", "bulletin", "bull", "magazine", "arxiv", "preprint",
+    "meeting", "congress",
+];
+
+/// Words of sentences that name a publisher, school or report series rather than a venue
+const NON_VENUE_WORDS: [&str; 15] = [
+    "press", "publishers", "publishing", "springer", "elsevier", "wiley", "verlag", "associates", "inc", "ltd",
+    "thesis", "dissertation", "report", "university", "available",
+];
+
+/// Words that end the venue name and start volume, issue or page details
+const DETAIL_WORDS: [&str; 9] = ["vol", "volume", "pp", "pages", "page", "no", "number", "issue", "chapter"];
+
+/// The field a span of a reference string holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceField {
+    Author,
+    Year,
+    Title,
+    /// The journal, proceedings or book the work appeared in
+    Container,
+    Pages,
+}
+
+/// A tagged byte range of the reference string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedSpan {
+    pub field: ReferenceField,
+    pub range: Range<usize>,
+}
+
+/// The fields found in one reference string, in reading order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaggedReference {
+    pub spans: Vec<TaggedSpan>,
+    /// The author span was taken by position alone, without looking like a list of names
+    pub author_guessed: bool,
+}
+
+impl TaggedReference {
+    /// The first span tagged with `field`
+    pub fn span(&self, field: ReferenceField) -> Option<&TaggedSpan> {
+        self.spans.iter().find(|span| span.field == field)
+    }
+
+    /// The raw text of the first span tagged with `field`, sliced from the tagged string
+    pub fn text<'a>(&self, field: ReferenceField, reference: &'a str) -> Option<&'a str> {
+        self.span(field).map(|span| &reference[span.range.clone()])
+    }
+
+    fn push(&mut self, field: ReferenceField, range: Range<usize>) {
+        if !range.is_empty() {
+            self.spans.push(TaggedSpan { field, range });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emphasis {
+    Plain,
+    Emphasized,
+    Quoted,
+}
+
+/// A word or punctuation mark of the reference string with its typesetting
+#[derive(Debug, Clone)]
+struct Token {
+    /// The word with LaTeX markup removed, or the punctuation mark
+    text: String,
+    range: Range<usize>,
+    emphasis: Emphasis,
+    /// Index of the `\newblock` block the token is in
+    block: usize,
+    punct: bool,
+}
+
+impl Token {
+    fn is_word(&self) -> bool {
+        !self.punct
+    }
+
+    fn is(&self, punct: char) -> bool {
+        self.punct && self.text.starts_with(punct)
+    }
+
+    fn lower(&self) -> String {
+        self.text.to_lowercase()
+    }
+
+    fn is_year(&self) -> bool {
+        let digits = self.text.trim_end_matches(|c: char| c.is_ascii_lowercase());
+        digits.len() == 4 && TAGGER_YEAR_REGEX.is_match(digits) && digits.len() + 1 >= self.text.len()
+    }
+
+    fn is_number(&self) -> bool {
+        self.is_word() && self.text.chars().next().is_some_and(|c| c.is_ascii_digit()) && !self.text.chars().any(char::is_alphabetic)
+    }
+
+    fn is_capitalized(&self) -> bool {
+        self.text.chars().find(|c| c.is_alphabetic()).is_some_and(char::is_uppercase)
+    }
+
+    fn is_initial(&self) -> bool {
+        self.is_word() && self.text.chars().count() == 1 && self.text.chars().all(char::is_alphabetic)
+    }
+}
+
+/// Split a LaTeX reference string into words and punctuation.
+///
+/// Braces, accents and formatting commands are dropped; `\emph`, `{\em ...}` and the like
+/// mark their words as emphasized, ``` ``...'' ``` and “...” as quoted. URLs, DOIs and
+/// eprints are skipped, as their dots and digits would read as sentences and numbers.
+fn tokenize(text: &str) -> Vec<Token> {
+    struct Word {
+        text: String,
+        start: usize,
+        end: usize,
+        emphasis: Emphasis,
+    }
+
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut word: Option<Word> = None;
+    // Whether each open brace group is emphasized
+    let mut groups: Vec<bool> = Vec::new();
+    let mut emphasize_next_group = false;
+    let mut quoted = false;
+    let mut block = 0;
+
+    let flush = |word: &mut Option<Word>, tokens: &mut Vec<Token>, block: usize| {
+        if let Some(word) = word.take() {
+            let lower = word.text.to_lowercase();
+            // `\emph{et~al.}` belongs to the author list, not to a venue
+            let emphasis = if word.emphasis == Emphasis::Emphasized && (lower == "et" || lower == "al") {
+                Emphasis::Plain
+            } else {
+                word.emphasis
+            };
+            tokens.push(Token { text: word.text, range: word.start..word.end, emphasis, block, punct: false });
+        }
+    };
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let emphasis = if quoted {
+            Emphasis::Quoted
+        } else if groups.iter().any(|emphasized| *emphasized) {
+            Emphasis::Emphasized
+        } else {
+            Emphasis::Plain
+        };
+        match c {
+            '\\' => {
+                let name_start = i + 1;
+                let mut name_end = name_start;
+                while let Some(&(j, next)) = chars.peek() {
+                    if !next.is_ascii_alphabetic() {
+                        break;
+                    }
+                    name_end = j + 1;
+                    chars.next();
+                }
+                if name_end == name_start {
+                    // Control symbols: accents and `\,` continue the word, `\ ` ends it
+                    if let Some((_, symbol)) = chars.next() {
+                        if symbol.is_whitespace() {
+                            flush(&mut word, &mut tokens, block);
+                        }
+                    }
+                    continue;
+                }
+                match &text[name_start..name_end] {
+                    "newblock" => {
+                        flush(&mut word, &mut tokens, block);
+                        block += 1;
+                    }
+                    "emph" | "textit" | "textsl" => {
+                        flush(&mut word, &mut tokens, block);
+                        emphasize_next_group = true;
+                    }
+                    "em" | "it" | "sl" | "itshape" => {
+                        flush(&mut word, &mut tokens, block);
+                        if let Some(group) = groups.last_mut() {
+                            *group = true;
+                        }
+                    }
+                    "url" | "doi" | "href" | "eprint" | "showeprint" | "showDOI" | "showURL" | "path" => {
+                        flush(&mut word, &mut tokens, block);
+                        skip_argument(&mut chars);
+                    }
+                    // One-letter accents like `\H{o}` and `\v{c}` are part of the word
+                    name if name.len() == 1 => {}
+                    _ => flush(&mut word, &mut tokens, block),
+                }
+            }
+            '{' => {
+                let emphasized = std::mem::take(&mut emphasize_next_group);
+                if emphasized {
+                    flush(&mut word, &mut tokens, block);
+                }
+                groups.push(emphasized);
+            }
+            '}' => {
+                if groups.pop() == Some(true) {
+                    flush(&mut word, &mut tokens, block);
+                }
+            }
+            '`' if chars.peek().is_some_and(|(_, next)| *next == '`') => {
+                chars.next();
+                flush(&mut word, &mut tokens, block);
+                quoted = true;
+            }
+            '\'' if chars.peek().is_some_and(|(_, next)| *next == '\'') => {
+                chars.next();
+                flush(&mut word, &mut tokens, block);
+                quoted = false;
+            }
+            '“' | '”' | '"' => {
+                flush(&mut word, &mut tokens, block);
+                quoted = match c {
+                    '“' => true,
+                    '”' => false,
+                    _ => !quoted,
+                };
+            }
+            '%' => {
+                // Comments run to the end of the line
+                flush(&mut word, &mut tokens, block);
+                while chars.peek().is_some_and(|(_, next)| *next != '\n') {
+                    chars.next();
+                }
+            }
+            '$' => {}
+            c if c.is_alphanumeric() || c == '-' || c == '–' || c == '\'' || c == '&' || c == '/' || c == '+' => {
+                match word.as_mut() {
+                    Some(word) if word.emphasis == emphasis => {
+                        word.text.push(c);
+                        word.end = i + c.len_utf8();
+                    }
+                    _ => {
+                        flush(&mut word, &mut tokens, block);
+                        word = Some(Word { text: c.to_string(), start: i, end: i + c.len_utf8(), emphasis });
+                    }
+                }
+            }
+            c if c.is_whitespace() || c == '~' => flush(&mut word, &mut tokens, block),
+            c => {
+                flush(&mut word, &mut tokens, block);
+                // The period of an `\emph{et~al.}` stays with the names
+                let emphasis = match tokens.last() {
+                    Some(last) if c == '.' && last.lower() == "al" => last.emphasis,
+                    _ => emphasis,
+                };
+                tokens.push(Token { text: c.to_string(), range: i..i + c.len_utf8(), emphasis, block, punct: true });
+            }
+        }
+    }
+    flush(&mut word, &mut tokens, block);
+    tokens
+}
+
+/// Skip an optional `[...]` and the `{...}` argument of a command
+fn skip_argument(chars: &mut std::iter::Peekable<std::str::CharIndices>) {
+    if chars.peek().is_some_and(|(_, c)| *c == '[') {
+        for (_, c) in chars.by_ref() {
+            if c == ']' {
+                break;
+            }
+        }
+    }
+    if chars.peek().is_some_and(|(_, c)| *c == '{') {
+        let mut depth = 0;
+        for (_, c) in chars.by_ref() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Whether the punctuation token at `k` ends a sentence of plain text
+fn ends_sentence(tokens: &[Token], k: usize, text: &str) -> bool {
+    let token = &tokens[k];
+    if token.emphasis != Emphasis::Plain || !(token.is('.') || token.is('?') || token.is('!')) {
+        return false;
+    }
+    // Dots inside identifiers and numbers (`2207.05608`) are not followed by a space
+    if !text[token.range.end..]
+        .chars()
+        .next()
+        .is_none_or(|next| next.is_whitespace() || next == '~' || next == '\\' || next == '}')
+    {
+        return false;
+    }
+    if !token.is('.') {
+        return true;
+    }
+    let Some(previous) = k.checked_sub(1).map(|p| &tokens[p]).filter(|previous| previous.is_word()) else {
+        return true;
+    };
+    let lower = previous.lower();
+    // "et al." ends the author list when a capitalized sentence follows
+    if lower == "al" {
+        return tokens.get(k + 1).is_some_and(|next| next.is_word() && next.is_capitalized());
+    }
+    !(previous.is_initial() || ABBREVIATIONS.contains(&lower.as_str()) || MONTH_ABBREVIATIONS.contains(&lower.as_str()))
+}
+
+/// Whether the period of an initial at `k` ends the author list that starts at `start`:
+/// `Smith J, Doe A. Title of the article.` or `Doe, A. 2020.`
+fn ends_author_list(tokens: &[Token], start: usize, k: usize) -> bool {
+    let token = &tokens[k];
+    if token.emphasis != Emphasis::Plain || !token.is('.') || k == start || !tokens[k - 1].is_initial() {
+        return false;
+    }
+    if !looks_like_names(&tokens[start..k]) {
+        return false;
+    }
+    // The next sentence starts with a year or reads like a title, with lowercase words in it
+    let sentence: Vec<&Token> = tokens[k + 1..]
+        .iter()
+        .take_while(|next| next.block == token.block && next.emphasis == Emphasis::Plain && !next.is('.'))
+        .filter(|next| next.is_word())
+        .collect();
+    let lowercase = sentence
+        .iter()
+        .filter(|word| !word.is_capitalized() && !word.is_number() && !NAME_WORDS.contains(&word.lower().as_str()))
+        .count();
+    sentence.first().is_some_and(|first| first.is_year()) || lowercase >= 2
+}
+
+/// A run of tokens with the same typesetting, ended by a sentence or `\newblock`
+#[derive(Debug, Clone)]
+struct Segment {
+    tokens: Range<usize>,
+    emphasis: Emphasis,
+}
+
+/// Group tokens into segments: blocks, quoted and emphasized runs, sentences and `(2020)`
+fn segment(tokens: &[Token], text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let push = |segments: &mut Vec<Segment>, range: Range<usize>| {
+        if !range.is_empty() {
+            segments.push(Segment { emphasis: tokens[range.start].emphasis, tokens: range });
+        }
+    };
+
+    let mut start = 0;
+    let mut k = 0;
+    while k < tokens.len() {
+        let token = &tokens[k];
+        if k > start && (token.emphasis != tokens[start].emphasis || token.block != tokens[start].block) {
+            push(&mut segments, start..k);
+            start = k;
+        }
+        // A parenthesized year stands on its own, with the punctuation that follows it
+        if token.emphasis == Emphasis::Plain
+            && token.is('(')
+            && tokens.get(k + 1).is_some_and(Token::is_year)
+            && tokens.get(k + 2).is_some_and(|close| close.is(')'))
+        {
+            push(&mut segments, start..k);
+            let mut end = k + 3;
+            if tokens.get(end).is_some_and(|next| next.is('.') || next.is(',')) {
+                end += 1;
+            }
+            push(&mut segments, k..end);
+            start = end;
+            k = end;
+            continue;
+        }
+        if ends_sentence(tokens, k, text) || (segments.is_empty() && ends_author_list(tokens, start, k)) {
+            push(&mut segments, start..k + 1);
+            start = k + 1;
+        }
+        k += 1;
+    }
+    push(&mut segments, start..tokens.len());
+    split_leading_year(&mut segments, tokens);
+    segments
+}
+
+/// Author-year styles without `\newblock` write `Doe, J. 2020. Title` or `Doe J 2020 Title`:
+/// split the first segment at a year that follows a list of names
+fn split_leading_year(segments: &mut Vec<Segment>, tokens: &[Token]) {
+    let Some(first) = segments.first().cloned() else {
+        return;
+    };
+    if first.emphasis != Emphasis::Plain {
+        return;
+    }
+    let Some(year) = first.tokens.clone().find(|&k| tokens[k].is_year()) else {
+        return;
+    };
+    if year == first.tokens.start || !looks_like_names(&tokens[first.tokens.start..year]) {
+        return;
+    }
+    let mut year_end = year + 1;
+    if tokens.get(year_end).is_some_and(|next| next.is('.') || next.is(',')) && year_end < first.tokens.end {
+        year_end += 1;
+    }
+    let mut split = vec![
+        Segment { tokens: first.tokens.start..year, emphasis: Emphasis::Plain },
+        Segment { tokens: year..year_end, emphasis: Emphasis::Plain },
+    ];
+    if year_end < first.tokens.end {
+        split.push(Segment { tokens: year_end..first.tokens.end, emphasis: Emphasis::Plain });
+    }
+    segments.splice(0..1, split);
+}
+
+/// Capitalized words, initials and name particles separated by commas or "and"
+fn looks_like_names(tokens: &[Token]) -> bool {
+    let words: Vec<&Token> = tokens.iter().filter(|token| token.is_word()).collect();
+    if words.is_empty() || words.iter().any(|word| word.text.chars().any(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    if tokens.iter().any(|token| token.is(':') || token.is('?') || token.is('!')) {
+        return false;
+    }
+    let first = words[0].lower();
+    if first == "in" || VENUE_WORDS.contains(&first.as_str()) {
+        return false;
+    }
+    let namey = words
+        .iter()
+        .filter(|word| word.is_capitalized() || word.is_initial() || NAME_WORDS.contains(&word.lower().as_str()))
+        .count();
+    let separated = tokens.iter().any(|token| token.is(',') || token.is('&'))
+        || words.iter().any(|word| word.lower() == "and" || word.is_initial());
+    namey * 4 >= words.len() * 3 && (separated || words.len() <= 3)
+}
+
+fn is_year_only(tokens: &[Token]) -> bool {
+    let mut words = tokens.iter().filter(|token| token.is_word());
+    words.next().is_some_and(Token::is_year) && words.next().is_none()
+}
+
+/// Whether a segment reads as a venue rather than a title: "In ...", "Proceedings of ..."
+fn starts_like_venue(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .find(|token| token.is_word())
+        .is_some_and(|first| first.lower() == "in" || VENUE_WORDS.contains(&first.lower().as_str()))
+}
+
+/// Whether the token at `k` is a year rather than part of an identifier like `arXiv:2010.12345`
+fn is_year_at(tokens: &[Token], k: usize) -> bool {
+    let adjacent = |a: &Token, b: &Token| a.range.end == b.range.start;
+    let token = &tokens[k];
+    let after_colon = k.checked_sub(1).is_some_and(|p| tokens[p].is(':') && adjacent(&tokens[p], token));
+    let before_digits = matches!(
+        tokens.get(k + 1..k + 3),
+        Some([dot, digits]) if dot.is('.') && adjacent(token, dot) && adjacent(dot, digits) && digits.is_number()
+    );
+    token.is_year() && !after_colon && !before_digits
+}
+
+/// Byte range covered by a run of tokens
+fn byte_range(tokens: &[Token]) -> Range<usize> {
+    match (tokens.first(), tokens.last()) {
+        (Some(first), Some(last)) => first.range.start..last.range.end,
+        _ => 0..0,
+    }
+}
+
+/// Byte range of a run of tokens without leading and trailing punctuation
+fn word_range(tokens: &[Token]) -> Range<usize> {
+    let start = tokens.iter().position(Token::is_word);
+    let end = tokens.iter().rposition(Token::is_word);
+    match (start, end) {
+        (Some(start), Some(end)) => byte_range(&tokens[start..=end]),
+        _ => 0..0,
+    }
+}
+
+/// Byte range of an author list, keeping the period of a final initial or "et al." and the
+/// braces that close around it
+fn author_range(tokens: &[Token], reference: &str) -> Range<usize> {
+    let end = tokens.iter().rposition(|token| token.is_word() || token.is('.'));
+    let Some(end) = end else {
+        return 0..0;
+    };
+    let mut range = word_range(&tokens[..=end]);
+    if !range.is_empty() {
+        range.end = tokens[end].range.end;
+        range.end += reference[range.end..].chars().take_while(|c| *c == '}').count();
+    }
+    range
+}
+
+/// The venue name in a plain segment: after "In" and any editor list, up to the volume or year
+fn container_range(tokens: &[Token]) -> Range<usize> {
+    let mut start = 0;
+    if tokens.iter().any(|token| matches!(token.lower().as_str(), "editor" | "editors" | "eds" | "ed")) {
+        // `In A. Editor, editors, Venue` or `In A. Editor (Eds.), Venue`
+        let editors = tokens.iter().position(|token| matches!(token.lower().as_str(), "editor" | "editors" | "eds" | "ed"));
+        start = editors.map_or(0, |k| k + 1);
+        while tokens.get(start).is_some_and(|token| token.punct) {
+            start += 1;
+        }
+    } else if tokens.iter().find(|token| token.is_word()).is_some_and(|first| first.lower() == "in") {
+        start = tokens.iter().position(|token| token.lower() == "in").map_or(0, |k| k + 1);
+    }
+    let rest = &tokens[start.min(tokens.len())..];
+    if rest.iter().any(|token| NON_VENUE_WORDS.contains(&token.lower().as_str())) {
+        return 0..0;
+    }
+    let end = rest
+        .iter()
+        .position(|token| token.is_number() || token.is('(') || DETAIL_WORDS.contains(&token.lower().as_str()))
+        .unwrap_or(rest.len());
+    // Identifier labels like `arXiv:2207.05608` or `doi:10.1000/1` are not part of the name
+    let end = match end.checked_sub(2).map(|label| (label, &rest[label], &rest[label + 1])) {
+        Some((label, word, colon)) if word.is_word() && colon.is(':') && colon.range.start == word.range.end => label,
+        _ => end,
+    };
+    word_range(&rest[..end])
+}
+
+/// Tag the author, year, title, container and page spans of a reference string.
+///
+/// The string is split into segments at `\newblock`, at the ends of sentences and where the
+/// typesetting changes between plain, quoted and emphasized text. The segments are then read
+/// in the order every bibliography style follows: the authors (a list of names), an optional
+/// year, the title (a quoted segment if there is one), the container (the first emphasized
+/// or "In ..." segment after the title), and volume, page and year details. The spans index
+/// into `reference`.
+pub fn tag_reference(reference: &str) -> TaggedReference {
+    let tokens = tokenize(reference);
+    let segments = segment(&tokens, reference);
+    let tokens_of = |segment: &Segment| &tokens[segment.tokens.clone()];
+    let mut tagged = TaggedReference::default();
+    let mut next = 0;
+
+    // Authors: a list of names first, or failing that whatever precedes a later block
+    if let Some(first) = segments.first().filter(|first| first.emphasis == Emphasis::Plain) {
+        let first_tokens = tokens_of(first);
+        if !is_year_only(first_tokens) && !starts_like_venue(first_tokens) {
+            let later_block = segments
+                .iter()
+                .skip(1)
+                .any(|segment| tokens[segment.tokens.start].block > first_tokens[0].block || segment.emphasis == Emphasis::Quoted);
+            if looks_like_names(first_tokens) {
+                tagged.push(ReferenceField::Author, author_range(first_tokens, reference));
+                next = 1;
+            } else if later_block {
+                tagged.push(ReferenceField::Author, author_range(first_tokens, reference));
+                tagged.author_guessed = true;
+                next = 1;
+            }
+        }
+    }
+
+    // Author-year styles put the year right after the names
+    while let Some(segment) = segments.get(next).filter(|segment| is_year_only(tokens_of(segment))) {
+        if let Some(year) = tokens_of(segment).iter().find(|token| token.is_year()) {
+            tagged.push(ReferenceField::Year, year.range.start..year.range.start + 4);
+        }
+        next += 1;
+    }
+
+    // Title: the quoted segment, or the next one unless it already names the venue
+    let quoted = segments[next.min(segments.len())..]
+        .iter()
+        .position(|segment| segment.emphasis == Emphasis::Quoted && tokens_of(segment).iter().any(Token::is_word))
+        .map(|offset| next + offset);
+    let title = quoted.or_else(|| {
+        segments
+            .get(next)
+            .filter(|segment| !starts_like_venue(tokens_of(segment)))
+            .map(|_| next)
+    });
+    let mut details_start = segments.get(next).map_or(reference.len(), |segment| tokens[segment.tokens.start].range.start);
+    if let Some(title) = title {
+        let mut title_tokens = tokens_of(&segments[title]);
+        // natbib appends the year of misc entries to the title: `Title, 2023.`
+        let words: Vec<usize> = (0..title_tokens.len()).filter(|&k| title_tokens[k].is_word()).collect();
+        if let [.., before, last] = words[..] {
+            if title_tokens[last].is_year() && title_tokens[before + 1..last].iter().any(|token| token.is(',')) {
+                tagged.push(ReferenceField::Year, title_tokens[last].range.start..title_tokens[last].range.start + 4);
+                title_tokens = &title_tokens[..=before];
+            }
+        }
+        // A question or exclamation mark belongs to the title
+        let mut range = word_range(title_tokens);
+        if let Some(mark) = title_tokens.iter().find(|token| token.range.start >= range.end && (token.is('?') || token.is('!'))) {
+            range.end = mark.range.end;
+        }
+        tagged.push(ReferenceField::Title, range);
+        details_start = title_tokens.last().map_or(details_start, |token| token.range.end);
+        next = title + 1;
+    }
+
+    // Container: the first emphasized segment or plain venue name after the title
+    for segment in &segments[next.min(segments.len())..] {
+        let segment_tokens = tokens_of(segment);
+        if is_year_only(segment_tokens) {
+            continue;
+        }
+        let range = match segment.emphasis {
+            Emphasis::Emphasized if segment_tokens.iter().any(|token| token.is_word() && !token.is_number()) => {
+                word_range(segment_tokens)
+            }
+            Emphasis::Plain => container_range(segment_tokens),
+            _ => 0..0,
+        };
+        if !range.is_empty() {
+            tagged.push(ReferenceField::Container, range);
+            break;
+        }
+    }
+
+    // Pages and the year from the details after the title
+    let details = &reference[details_start.min(reference.len())..];
+    let pages = TAGGER_PAGES_REGEX
+        .captures(details)
+        .or_else(|| TAGGER_COLON_PAGES_REGEX.captures(details))
+        .or_else(|| TAGGER_PAGE_RANGE_REGEX.captures(details))
+        .and_then(|cap| cap.get(1));
+    if let Some(pages) = pages {
+        tagged.push(ReferenceField::Pages, details_start + pages.start()..details_start + pages.end());
+    }
+    if tagged.span(ReferenceField::Year).is_none() {
+        // The last year of the details, which follows any conference dates
+        let in_pages = |token: &Token| {
+            pages.is_some_and(|pages| (details_start + pages.start()..details_start + pages.end()).contains(&token.range.start))
+        };
+        let year = (0..tokens.len())
+            .rev()
+            .filter(|&k| is_year_at(&tokens, k))
+            .map(|k| &tokens[k])
+            .find(|token| token.range.start >= details_start && !in_pages(token))
+            .or_else(|| (0..tokens.len()).find(|&k| is_year_at(&tokens, k)).map(|k| &tokens[k]));
+        if let Some(year) = year {
+            tagged.push(ReferenceField::Year, year.range.start..year.range.start + 4);
+        }
+    }
+
+    tagged.spans.sort_by_key(|span| span.range.start);
+    tagged
+}
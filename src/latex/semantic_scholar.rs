@@ -0,0 +1,328 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use backoff::future::retry;
+use log::info;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::RequestBuilder;
+use serde_json::{json, Value};
+use tokio::time::Instant;
+
+use crate::error::BibExtractError;
+use crate::latex::http::{api_backoff, read_json, HTTP_CLIENT};
+use crate::latex::providers::{match_score, Candidate, MetadataProvider};
+use crate::latex::similarity::MatchThresholds;
+use crate::latex::{BibEntry, BibEntryBuilder, Bibliography, Person, PersonList};
+
+static CORPUS_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)CorpusId:\s*([0-9]+)").expect("Invalid CorpusId regex pattern")
+});
+
+/// The paper fields requested from the Graph API
+const PAPER_FIELDS: &str = "title,authors,year,venue,journal,publicationTypes,externalIds,url";
+
+/// Most IDs the batch endpoint accepts in one request
+const BATCH_SIZE: usize = 500;
+
+/// Verifies entries through the Semantic Scholar Graph API: arXiv IDs, DOIs and CorpusIds
+/// are resolved with the batch endpoint, other entries with a title match search.
+///
+/// The API lives at `SEMANTIC_SCHOLAR_BASE_URL` (https://api.semanticscholar.org by
+/// default). The key in `SEMANTIC_SCHOLAR_API_KEY`, if any, is sent with every request.
+/// Requests are spaced by the rate limit (one per second by default), across all the
+/// tasks sharing the provider.
+pub struct SemanticScholarProvider {
+    api_key: Option<String>,
+    min_interval: Duration,
+    /// When the last request was sent, shared by all tasks using the provider
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+    /// Papers already fetched by ID (e.g. `ARXIV:2104.08653`); `None` when Semantic Scholar
+    /// does not know the ID or the request for it failed
+    papers: Mutex<HashMap<String, Option<Value>>>,
+}
+
+impl Default for SemanticScholarProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SemanticScholarProvider {
+    /// A provider using the API key in `SEMANTIC_SCHOLAR_API_KEY`, if set
+    pub fn new() -> Self {
+        Self {
+            api_key: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok().filter(|key| !key.is_empty()),
+            min_interval: Duration::from_secs(1),
+            last_request: tokio::sync::Mutex::new(None),
+            papers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send `api_key` in the `x-api-key` header
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Leave at least `min_interval` between two requests
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// The Semantic Scholar IDs of an entry, most specific first: `ARXIV:`, `DOI:`, `CorpusId:`
+    pub fn paper_ids(entry: &BibEntry) -> Vec<String> {
+        let bib = Bibliography::new();
+        let mut ids = Vec::new();
+        if let Some(arxiv_id) = bib.extract_arxiv_id(entry) {
+            ids.push(format!("ARXIV:{}", arxiv_id));
+        }
+        if let Some(doi) = bib.extract_doi(entry) {
+            ids.push(format!("DOI:{}", doi));
+        }
+        let corpus_id = entry
+            .get("corpusid")
+            .filter(|id| id.chars().all(|c| c.is_ascii_digit()))
+            .cloned()
+            .or_else(|| {
+                ["url", "note", "raw"]
+                    .iter()
+                    .find_map(|field| CORPUS_ID_REGEX.captures(entry.get(field)?).map(|c| c[1].to_string()))
+            });
+        if let Some(corpus_id) = corpus_id {
+            ids.push(format!("CorpusId:{}", corpus_id));
+        }
+        ids
+    }
+
+    /// Wait until the rate limit allows another request
+    async fn wait_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            tokio::time::sleep_until(last + self.min_interval).await;
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    /// Send a request, retrying transient failures. Unknown papers and failed requests give `None`.
+    async fn send(&self, request: impl Fn() -> RequestBuilder, what: &str) -> Option<Value> {
+        let operation = || async {
+            self.wait_turn().await;
+            info!("Querying Semantic Scholar for: {}", what);
+            let mut request = request();
+            if let Some(api_key) = &self.api_key {
+                request = request.header("x-api-key", api_key);
+            }
+            let response = request.send().await
+                .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
+
+            read_json("Semantic Scholar", response).await
+        };
+
+        match retry(api_backoff(), operation).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Semantic Scholar API query failed for {}: {}", what, e);
+                None
+            }
+        }
+    }
+
+    /// Fetch the papers for `ids` with the batch endpoint and remember them, found or not.
+    /// The IDs of a failed request are remembered as unknown, so that their entries are
+    /// searched for by title rather than sent again.
+    pub async fn fetch_batch(&self, ids: &[String]) {
+        let url = format!("{}/graph/v1/paper/batch", base_url());
+        for chunk in ids.chunks(BATCH_SIZE) {
+            let body = json!({ "ids": chunk });
+            let request = || HTTP_CLIENT.post(&url).query(&[("fields", PAPER_FIELDS)]).json(&body);
+            // The response lists the papers in the order of the IDs, with null for unknown ones
+            let response = self.send(request, &format!("{} IDs", chunk.len())).await;
+            let papers = response.as_ref().and_then(|papers| papers.as_array());
+            let mut cache = self.papers.lock().unwrap();
+            for (index, id) in chunk.iter().enumerate() {
+                let paper = papers.and_then(|papers| papers.get(index)).filter(|paper| !paper.is_null());
+                cache.insert(id.clone(), paper.cloned());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for SemanticScholarProvider {
+    fn name(&self) -> &str {
+        "Semantic Scholar"
+    }
+
+    async fn lookup_id(&self, entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        let ids = Self::paper_ids(entry);
+        let missing: Vec<String> = {
+            let cache = self.papers.lock().unwrap();
+            ids.iter().filter(|id| !cache.contains_key(*id)).cloned().collect()
+        };
+        if !missing.is_empty() {
+            self.fetch_batch(&missing).await;
+        }
+
+        let cache = self.papers.lock().unwrap();
+        Ok(ids
+            .iter()
+            .find_map(|id| cache.get(id)?.as_ref())
+            .and_then(|paper| semantic_scholar_paper_to_entry(paper, &entry.key))
+            .map(|verified| Candidate::new(verified, 1.0, self.name())))
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let Some(title) = entry.get("title") else {
+            return Ok(Vec::new());
+        };
+        let query = title.replace(['{', '}'], "");
+        let url = format!("{}/graph/v1/paper/search/match", base_url());
+        let request = || HTTP_CLIENT.get(&url).query(&[("query", query.as_str()), ("fields", PAPER_FIELDS)]);
+        let Some(response) = self.send(request, &query).await else {
+            return Ok(Vec::new());
+        };
+
+        let mut candidates: Vec<Candidate> = response
+            .get("data")
+            .and_then(|data| data.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|paper| semantic_scholar_paper_to_entry(paper, &entry.key))
+            .map(|verified| {
+                let score = match_score(entry, &verified);
+                Candidate::new(verified, score, self.name())
+            })
+            .filter(|candidate| candidate.score >= MatchThresholds::default().min_score)
+            .collect();
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(candidates)
+    }
+
+    async fn prefetch(&self, entries: &[BibEntry]) -> Result<(), BibExtractError> {
+        let mut ids: Vec<String> = entries.iter().flat_map(Self::paper_ids).collect();
+        ids.sort();
+        ids.dedup();
+        if !ids.is_empty() {
+            self.fetch_batch(&ids).await;
+        }
+        Ok(())
+    }
+}
+
+fn base_url() -> String {
+    std::env::var("SEMANTIC_SCHOLAR_BASE_URL").unwrap_or_else(|_| "https://api.semanticscholar.org".to_string())
+}
+
+/// The identifiers Semantic Scholar knows a paper by, e.g. `DOI`, `ArXiv`, `ACL`, `DBLP`, `CorpusId`
+pub fn external_ids(paper: &Value) -> BTreeMap<String, String> {
+    paper
+        .get("externalIds")
+        .and_then(|ids| ids.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, id)| {
+            let id = match id {
+                Value::String(id) => id.clone(),
+                Value::Number(id) => id.to_string(),
+                _ => return None,
+            };
+            Some((name.clone(), id))
+        })
+        .collect()
+}
+
+/// Convert a Semantic Scholar paper into a BibEntry with the given key.
+///
+/// Its DOI and arXiv ID become the `doi`, `eprint` and `archivePrefix` fields, and its ACL
+/// Anthology, DBLP and Semantic Scholar corpus IDs the `acl`, `dblp` and `corpusid` fields.
+/// The `url` is the ACL Anthology page for papers with an ACL ID, the Semantic Scholar page
+/// otherwise.
+pub fn semantic_scholar_paper_to_entry(paper: &Value, key: &str) -> Option<BibEntry> {
+    let title = non_empty(paper.get("title"))?;
+
+    let publication_types: Vec<&str> = paper
+        .get("publicationTypes")
+        .and_then(|types| types.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.as_str())
+        .collect();
+    let entry_type = publication_types.iter().find_map(|t| semantic_scholar_entry_type(t)).unwrap_or("misc");
+    let mut builder = BibEntryBuilder::new(key, entry_type).field("title", title);
+
+    if let Some(authors) = paper.get("authors").and_then(|a| a.as_array()) {
+        let persons: Vec<Person> = authors.iter().filter_map(|a| non_empty(a.get("name"))).map(Person::parse).collect();
+        if !persons.is_empty() {
+            builder = builder.field("author", PersonList { persons, is_truncated: false }.to_bibtex());
+        }
+    }
+    if let Some(year) = paper.get("year").and_then(|y| y.as_i64()) {
+        builder = builder.field("year", year.to_string());
+    }
+
+    // Preprints are listed with "ArXiv" as their journal, which is not a venue
+    let journal = paper.get("journal");
+    let journal_name = non_empty(journal.and_then(|j| j.get("name"))).filter(|name| !name.eq_ignore_ascii_case("arxiv"));
+    let venue = non_empty(paper.get("venue")).filter(|venue| !venue.eq_ignore_ascii_case("arxiv.org"));
+    match entry_type {
+        "article" => {
+            if let Some(journal_name) = journal_name.or(venue) {
+                builder = builder.field("journal", journal_name);
+            }
+        }
+        "inproceedings" | "incollection" => {
+            if let Some(venue) = venue.or(journal_name) {
+                builder = builder.field("booktitle", venue);
+            }
+        }
+        _ => {}
+    }
+    if let Some(volume) = non_empty(journal.and_then(|j| j.get("volume"))) {
+        builder = builder.field("volume", volume);
+    }
+    if let Some(pages) = non_empty(journal.and_then(|j| j.get("pages"))) {
+        builder = builder.field("pages", pages.replace("--", "-").replace('-', "--"));
+    }
+
+    let ids = external_ids(paper);
+    if let Some(doi) = ids.get("DOI") {
+        builder = builder.field("doi", doi);
+    }
+    if let Some(arxiv_id) = ids.get("ArXiv") {
+        builder = builder.field("eprint", arxiv_id).field("archivePrefix", "arXiv");
+    }
+    for (name, field) in [("ACL", "acl"), ("DBLP", "dblp"), ("CorpusId", "corpusid")] {
+        if let Some(id) = ids.get(name) {
+            builder = builder.field(field, id);
+        }
+    }
+    let url = ids
+        .get("ACL")
+        .map(|acl_id| format!("https://aclanthology.org/{}", acl_id))
+        .or_else(|| non_empty(paper.get("url")).map(str::to_string));
+    if let Some(url) = url {
+        builder = builder.field("url", url);
+    }
+
+    Some(builder.build())
+}
+
+/// A string value, trimmed, unless it is empty
+fn non_empty(value: Option<&Value>) -> Option<&str> {
+    value.and_then(|v| v.as_str()).map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Map a Semantic Scholar publication type onto a BibTeX entry type
+fn semantic_scholar_entry_type(publication_type: &str) -> Option<&'static str> {
+    match publication_type {
+        "JournalArticle" | "Review" | "LettersAndComments" | "Editorial" => Some("article"),
+        "Conference" => Some("inproceedings"),
+        "Book" => Some("book"),
+        "BookSection" => Some("incollection"),
+        _ => None,
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeSet;
+
+use crate::latex::person::{fold_accents, latex_to_unicode};
+use crate::latex::{BibEntry, Person};
+
+/// Words too common in titles to tell papers apart, left out of shortened queries and of
+/// title word overlaps
+pub(crate) const STOPWORDS: [&str; 20] = [
+    "a", "an", "and", "are", "as", "at", "by", "for", "from", "in", "into", "is", "of", "on", "or", "the", "to",
+    "towards", "via", "with",
+];
+
+/// When a search result counts as a match for an entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchThresholds {
+    /// Results whose titles are less similar than this (0 to 1) to the entry's never match
+    pub min_title_similarity: f32,
+    /// Results scoring less than this (0 to 1) are not matches
+    pub min_score: f32,
+    /// Results scoring at least this (0 to 1) end the search for better ones
+    pub confident_score: f32,
+}
+
+impl Default for MatchThresholds {
+    /// Titles at least 80% similar; the same title alone, or a similar one with the year or
+    /// authors, suffices, and a matching title with the year or authors is confident
+    fn default() -> Self {
+        Self { min_title_similarity: 0.8, min_score: 3.0 / 6.0, confident_score: 4.0 / 6.0 }
+    }
+}
+
+impl MatchThresholds {
+    /// Reject results whose titles are less similar than `min_title_similarity`
+    pub fn with_min_title_similarity(mut self, min_title_similarity: f32) -> Self {
+        self.min_title_similarity = min_title_similarity;
+        self
+    }
+
+    /// Reject results scoring less than `min_score`
+    pub fn with_min_score(mut self, min_score: f32) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Stop searching for better results once one scores `confident_score`
+    pub fn with_confident_score(mut self, confident_score: f32) -> Self {
+        self.confident_score = confident_score;
+        self
+    }
+}
+
+/// Text reduced to what two spellings of a title have in common: LaTeX accents resolved and
+/// folded, math dropped, lowercase words without punctuation.
+pub fn fold_text(text: &str) -> String {
+    let text = latex_to_unicode(text);
+    let without_math: Vec<&str> = text.split('$').step_by(2).collect();
+    fold_accents(&without_math.join(" "))
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_lowercase().next().unwrap_or(c) } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Edit distance between two strings, in characters
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Similarity of two strings from their edit distance, from 0 to 1
+pub fn levenshtein_ratio(a: &str, b: &str) -> f32 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f32 / longest as f32
+}
+
+/// Similarity of two texts as sets of words, from 0 to 1: the edit distance ratio of their
+/// sorted, deduplicated words. Word order and repeated words do not matter, but missing words
+/// do, so a title is not taken for a longer one containing it.
+pub fn token_set_ratio(a: &str, b: &str) -> f32 {
+    let words = |text: &str| text.split_whitespace().collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>().join(" ");
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    levenshtein_ratio(&a, &b)
+}
+
+/// Similarity of two titles, from 0 to 1, regardless of case, markup, accents and math.
+/// A title found word for word in the other, as in a shortened citation, scores at least 0.8,
+/// and more the more of the other it covers.
+pub fn title_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (fold_text(a), fold_text(b));
+    let similarity = token_set_ratio(&a, &b);
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.is_empty() || !format!(" {} ", longer).contains(&format!(" {} ", shorter)) {
+        return similarity;
+    }
+    let coverage = shorter.split_whitespace().count() as f32 / longer.split_whitespace().count() as f32;
+    similarity.max(0.8 + 0.2 * coverage)
+}
+
+/// Share of the authors of the shorter list whose last names are in the other list, from 0 to 1
+pub fn author_similarity(authors: &[Person], other: &[Person]) -> f32 {
+    let last_names = |persons: &[Person]| -> BTreeSet<String> {
+        persons
+            .iter()
+            .map(|person| fold_text(&person.last))
+            .filter(|last| !last.is_empty() && last != "others")
+            .collect()
+    };
+    let (last_names, other_last_names) = (last_names(authors), last_names(other));
+    let shorter = last_names.len().min(other_last_names.len());
+    if shorter == 0 {
+        return 0.0;
+    }
+    last_names.intersection(&other_last_names).count() as f32 / shorter as f32
+}
+
+/// How well a search result matches an entry, from 0 to 1.
+///
+/// Out of 6 points: 3 times the title similarity, 1 for the same year and 2 times the author
+/// similarity. Results whose titles are less similar than the threshold, or that cannot be
+/// compared by title, score 0 whatever their year and authors: the same authors in the same
+/// year are not the same paper. Other fields the entry lacks score nothing.
+pub fn match_record(
+    entry: &BibEntry,
+    title: &str,
+    year: Option<&str>,
+    authors: &[Person],
+    thresholds: &MatchThresholds,
+) -> f32 {
+    let similarity = entry.get("title").map_or(0.0, |entry_title| title_similarity(entry_title, title));
+    if similarity == 0.0 || similarity < thresholds.min_title_similarity {
+        return 0.0;
+    }
+    let mut points = 3.0 * similarity;
+    if let (Some(entry_year), Some(year)) = (entry.get("year"), year) {
+        if entry_year.trim() == year.trim() {
+            points += 1.0;
+        }
+    }
+    points += 2.0 * author_similarity(&entry.authors().persons, authors);
+    points / 6.0
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+
+use crate::latex::ArxivPaper;
+
+/// How often a single bibliography entry is cited across the input papers
+#[derive(Debug, Clone)]
+pub struct EntryStats {
+    /// Normalized citation key, shared by the same work across papers
+    pub key: String,
+    pub title: String,
+    pub year: Option<String>,
+    /// Input papers citing this entry in their extracted sections
+    pub papers: Vec<String>,
+    /// Number of extracted sections citing this entry
+    pub section_count: usize,
+}
+
+impl EntryStats {
+    pub fn paper_count(&self) -> usize {
+        self.papers.len()
+    }
+}
+
+/// Two entries cited together by the same input papers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoCitation {
+    pub first: String,
+    pub second: String,
+    /// Number of input papers citing both entries
+    pub count: usize,
+}
+
+/// Bibliography overlap between two input papers
+#[derive(Debug, Clone)]
+pub struct PaperOverlap {
+    pub first: String,
+    pub second: String,
+    pub shared: usize,
+    pub jaccard: f64,
+}
+
+/// Citation statistics across a set of input papers
+#[derive(Debug, Clone, Default)]
+pub struct CitationStats {
+    pub paper_count: usize,
+    /// Entries sorted by paper count, then section count
+    pub entries: Vec<EntryStats>,
+    /// Co-cited pairs sorted by count
+    pub co_citations: Vec<CoCitation>,
+    /// Pairwise bibliography overlap between input papers
+    pub overlaps: Vec<PaperOverlap>,
+    /// Number of distinct works per publication year in the merged bibliography
+    pub year_histogram: BTreeMap<String, usize>,
+}
+
+impl CitationStats {
+    /// Compute statistics from the extracted sections and bibliographies of processed papers
+    pub fn from_papers(papers: &[ArxivPaper]) -> Self {
+        let mut entries: HashMap<String, EntryStats> = HashMap::new();
+        let mut cited_per_paper: Vec<BTreeSet<String>> = Vec::new();
+        let mut bibliography_per_paper: Vec<BTreeSet<String>> = Vec::new();
+        let mut years: HashMap<String, Option<String>> = HashMap::new();
+
+        for paper in papers {
+            // Normalized keys make the same work comparable across papers
+            let mut bibliography_keys = BTreeSet::new();
+            for entry in paper.bibliography.iter() {
+                let key = paper.bibliography.normalize_citation_key(entry);
+                years.entry(key.clone()).or_insert_with(|| entry.get("year").cloned());
+                bibliography_keys.insert(key);
+            }
+            bibliography_per_paper.push(bibliography_keys);
+
+            let mut cited = BTreeSet::new();
+            for section in &paper.sections {
+                let mut section_keys = BTreeSet::new();
+                for citation in &section.citations {
+                    if let Some(entry) = paper.bibliography.get(citation) {
+                        let key = paper.bibliography.normalize_citation_key(entry);
+                        let stats = entries.entry(key.clone()).or_insert_with(|| EntryStats {
+                            key: key.clone(),
+                            title: entry.get("title").cloned().unwrap_or_default(),
+                            year: entry.get("year").cloned(),
+                            papers: Vec::new(),
+                            section_count: 0,
+                        });
+                        if section_keys.insert(key.clone()) {
+                            stats.section_count += 1;
+                        }
+                        if !stats.papers.contains(&paper.id) {
+                            stats.papers.push(paper.id.clone());
+                        }
+                        cited.insert(key);
+                    }
+                }
+            }
+            cited_per_paper.push(cited);
+        }
+
+        let mut entries: Vec<EntryStats> = entries.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.paper_count()
+                .cmp(&a.paper_count())
+                .then(b.section_count.cmp(&a.section_count))
+                .then(a.key.cmp(&b.key))
+        });
+
+        // Count each pair of entries once per paper citing both
+        let mut pair_counts: HashMap<(String, String), usize> = HashMap::new();
+        for cited in &cited_per_paper {
+            let keys: Vec<&String> = cited.iter().collect();
+            for (i, first) in keys.iter().enumerate() {
+                for second in &keys[i + 1..] {
+                    *pair_counts.entry(((*first).clone(), (*second).clone())).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut co_citations: Vec<CoCitation> = pair_counts
+            .into_iter()
+            .map(|((first, second), count)| CoCitation { first, second, count })
+            .collect();
+        co_citations.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(a.first.cmp(&b.first))
+                .then(a.second.cmp(&b.second))
+        });
+
+        let mut overlaps = Vec::new();
+        for i in 0..papers.len() {
+            for j in (i + 1)..papers.len() {
+                let shared = bibliography_per_paper[i].intersection(&bibliography_per_paper[j]).count();
+                let union = bibliography_per_paper[i].union(&bibliography_per_paper[j]).count();
+                let jaccard = if union == 0 { 0.0 } else { shared as f64 / union as f64 };
+                overlaps.push(PaperOverlap {
+                    first: papers[i].id.clone(),
+                    second: papers[j].id.clone(),
+                    shared,
+                    jaccard,
+                });
+            }
+        }
+
+        let mut year_histogram = BTreeMap::new();
+        for year in years.into_values() {
+            let year = year.unwrap_or_else(|| "unknown".to_string());
+            *year_histogram.entry(year).or_insert(0) += 1;
+        }
+
+        Self {
+            paper_count: papers.len(),
+            entries,
+            co_citations,
+            overlaps,
+            year_histogram,
+        }
+    }
+
+    /// Convert the statistics to a JSON value
+    pub fn to_json(&self) -> Value {
+        json!({
+            "paper_count": self.paper_count,
+            "entries": self.entries.iter().map(|e| json!({
+                "key": e.key,
+                "title": e.title,
+                "year": e.year,
+                "paper_count": e.paper_count(),
+                "section_count": e.section_count,
+                "papers": e.papers,
+            })).collect::<Vec<_>>(),
+            "co_citations": self.co_citations.iter().map(|c| json!({
+                "first": c.first,
+                "second": c.second,
+                "count": c.count,
+            })).collect::<Vec<_>>(),
+            "overlaps": self.overlaps.iter().map(|o| json!({
+                "first": o.first,
+                "second": o.second,
+                "shared": o.shared,
+                "jaccard": o.jaccard,
+            })).collect::<Vec<_>>(),
+            "year_histogram": self.year_histogram,
+        })
+    }
+
+    /// Render the statistics as human-readable tables, listing at most `limit` rows per table
+    pub fn to_table(&self, limit: usize) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "Most cited entries across {} papers", self.paper_count);
+        let _ = writeln!(out, "{:>6}  {:>8}  {:<40}  title", "papers", "sections", "key");
+        for entry in self.entries.iter().take(limit) {
+            let _ = writeln!(
+                out,
+                "{:>6}  {:>8}  {:<40}  {}",
+                entry.paper_count(),
+                entry.section_count,
+                truncate(&entry.key, 40),
+                truncate(&entry.title, 60)
+            );
+        }
+
+        out.push_str("\nMost co-cited pairs\n");
+        let _ = writeln!(out, "{:>6}  {:<40}  {:<40}", "papers", "first", "second");
+        for pair in self.co_citations.iter().take(limit) {
+            let _ = writeln!(out, "{:>6}  {:<40}  {:<40}", pair.count, truncate(&pair.first, 40), truncate(&pair.second, 40));
+        }
+
+        out.push_str("\nBibliography overlap (Jaccard)\n");
+        let _ = writeln!(out, "{:<12}  {:<12}  {:>6}  {:>7}", "first", "second", "shared", "jaccard");
+        for overlap in &self.overlaps {
+            let _ = writeln!(
+                out,
+                "{:<12}  {:<12}  {:>6}  {:>7.3}",
+                overlap.first, overlap.second, overlap.shared, overlap.jaccard
+            );
+        }
+
+        out.push_str("\nEntries per year\n");
+        for (year, count) in &self.year_histogram {
+            let _ = writeln!(out, "{:<8}  {:>5}  {}", year, count, "#".repeat((*count).min(60)));
+        }
+
+        out
+    }
+}
+
+/// Shorten a string to at most `max` characters for table output
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        let shortened: String = text.chars().take(max - 3).collect();
+        format!("{}...", shortened)
+    } else {
+        text.to_string()
+    }
+}
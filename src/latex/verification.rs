@@ -1,17 +1,20 @@
 use crate::error::BibExtractError;
 use anyhow::Result;
 use log::info;
-use reqwest::Client;
 use serde_json::Value;
-use once_cell::sync::Lazy;
-use backoff::{future::retry, ExponentialBackoff};
-use std::time::Duration;
+use backoff::future::retry;
 use bibparser::{Parser as BibParser};
 
-use crate::latex::{Bibliography, BibEntry, BibEntryBuilder};
+use async_trait::async_trait;
 
-// Use a single, lazily-initialized reqwest::Client for all API calls to enable connection pooling.
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(Client::new);
+use crate::latex::http::{api_backoff, status_error, HTTP_CLIENT};
+use crate::latex::providers::{Candidate, MetadataProvider, Verifier};
+use crate::latex::person::latex_to_unicode;
+use crate::latex::similarity::{fold_text, match_record, MatchThresholds, STOPWORDS};
+use crate::latex::{Bibliography, BibEntry, BibEntryBuilder, Person, PersonList};
+
+/// Title words searched for along with the first author's last name
+const TITLE_PREFIX_WORDS: usize = 4;
 
 impl Bibliography {
     /// Parse a BibTeX entry string into a BibEntry using the bibparser crate.
@@ -43,36 +46,25 @@ impl Bibliography {
     
     
 
-    /// Query DBLP API for paper information based on paper title and author
+    /// Query DBLP API for paper information based on the entry's full title
     pub async fn query_dblp_api_async(&self, entry: &BibEntry) -> Result<Option<Value>, BibExtractError> {
-        let title = match entry.get("title") {
-            Some(t) => t,
-            None => return Ok(None), // No title, can't search
+        let Some(query) = dblp_queries(entry).into_iter().next() else {
+            return Ok(None); // No title, can't search
         };
-        
-        let clean_title = title.replace("{", "").replace("}", "").replace("*", "");
-        let encoded_title = clean_title.replace(" ", "+");
-        
+        // Failed queries find nothing
+        Ok(self.query_dblp_search_async(&query).await.unwrap_or(None))
+    }
+
+    /// Run a query against DBLP's publication search, retrying transient failures. Gives
+    /// `None` when nothing is found, and an error when DBLP cannot be reached.
+    pub async fn query_dblp_search_async(&self, query: &str) -> Result<Option<Value>, BibExtractError> {
         // Support configurable base URL for testing
         let base_url = std::env::var("DBLP_BASE_URL").unwrap_or_else(|_| "https://dblp.org".to_string());
-        let url = format!("{}/search/publ/api?q={}&format=json", base_url, encoded_title);
-        
-        // Create exponential backoff strategy with configurable timeout for testing
-        let max_timeout = std::env::var("API_TIMEOUT_SECS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(30);
-        
-        let backoff = ExponentialBackoff {
-            initial_interval: Duration::from_millis(100),
-            max_interval: Duration::from_secs(5),
-            max_elapsed_time: Some(Duration::from_secs(max_timeout)),
-            ..Default::default()
-        };
+        let url = format!("{}/search/publ/api", base_url);
         
         let operation = || async {
-            info!("Querying DBLP API for paper: {}", clean_title);
-            let response = HTTP_CLIENT.get(&url).send().await
+            info!("Querying DBLP API for: {}", query);
+            let response = HTTP_CLIENT.get(&url).query(&[("q", query), ("format", "json")]).send().await
                 .map_err(|e| backoff::Error::transient(BibExtractError::NetworkError(e)))?;
 
             if response.status().is_success() {
@@ -101,130 +93,67 @@ impl Bibliography {
                 }
                 Ok(None)
             } else {
-                if response.status().as_u16() == 500 {
-                    log::warn!("DBLP API returned 500 Internal Server Error for query: {}", url);
-                } else {
-                    log::warn!("DBLP API returned status {}", response.status());
-                }
-                Err(backoff::Error::transient(BibExtractError::ApiError(format!("DBLP API returned status {}", response.status()))))
+                Err(status_error("DBLP", response.status()))
             }
         };
 
-        match retry(backoff, operation).await {
-            Ok(result) => Ok(result),
-            Err(_) => {
-                log::warn!("DBLP API query failed after retries for: {}", clean_title);
-                Ok(None)
-            }
-        }
+        retry(api_backoff(), operation).await.inspect_err(|_| {
+            log::warn!("DBLP API query failed after retries for: {}", query);
+        })
     }
 
     
     /// Find the best matching entry in DBLP results for a given entry
     pub fn find_best_match_in_dblp(&self, dblp_results: &Value, entry: &BibEntry) -> Option<Value> {
-        /*
-        This function finds the best matching entry in DBLP results based on title, year, and author.
-        It compares the original entry's title, year, and author with the titles, years,
-        and authors in the DBLP results, scoring matches based on exact matches, year matches,
-        and author matches. The best match is returned if the score is above a certain threshold.
-        If no suitable match is found, it returns None.
-        
-        The scoring system is as follows:
-        - Exact title match: 3 points
-        - Title contains hit title or vice versa: 2 points
-        - Year match: 1 point
-        - Author match (5 or more matching words): 2 points
-        
-        The best match is the one with the highest score.
-        If no match has a score of at least 2, None is returned.
-        */
+        self.best_matches_in_dblp(dblp_results, entry).into_iter().next().map(|(_, hit)| hit)
+    }
 
+    /// Score the DBLP results against an entry with the default thresholds: the matching hits,
+    /// best first, with their scores from 0 to 1
+    pub fn best_matches_in_dblp(&self, dblp_results: &Value, entry: &BibEntry) -> Vec<(f32, Value)> {
+        self.best_matches_in_dblp_with(dblp_results, entry, &MatchThresholds::default())
+    }
+
+    /// Score the DBLP results against an entry: the hits reaching `thresholds.min_score`, best
+    /// first, with their scores from 0 to 1 (see `match_record`). Titles are compared after
+    /// folding case, accents and math; authors by their last names.
+    /// Equal scores keep DBLP's order.
+    pub fn best_matches_in_dblp_with(
+        &self,
+        dblp_results: &Value,
+        entry: &BibEntry,
+        thresholds: &MatchThresholds,
+    ) -> Vec<(f32, Value)> {
         let hits = dblp_results
             .get("result")
             .and_then(|r| r.get("hits"))
             .and_then(|h| h.get("hit"))
-            .and_then(|h| h.as_array())?;
-        
-        let original_title = entry.get("title")?;
-        let original_year = entry.get("year")?;
-        let original_author = entry.get("author")?;
+            .and_then(|h| h.as_array());
+        // Results are found by title, so there is nothing to match without one
+        let (Some(hits), Some(_)) = (hits, entry.get("title")) else {
+            return Vec::new();
+        };
 
-        let mut best_match = None;
-        let mut best_score = 0;
-        
+        let mut matches = Vec::new();
         for hit in hits {
-            let info = hit.get("info")?;
-            let hit_title = info.get("title").and_then(|t| t.as_str())?;
-            let hit_year = info.get("year").and_then(|y| y.as_str())?;
-
-            let mut score = 0;
-            if hit_year == original_year {
-                score += 1;
-            }
-            let clean_original = original_title.to_lowercase().replace("{", "").replace("}", "");
-            let clean_hit = hit_title.to_lowercase();
-            
-            if clean_original == clean_hit {
-                score += 3;
-            } else if clean_original.contains(&clean_hit) || clean_hit.contains(&clean_original) {
-                score += 2;
-            }
+            // Hits without a title cannot be matched; those without a year miss its point
+            let Some((info, hit_title)) = hit
+                .get("info")
+                .and_then(|info| Some((info, info.get("title")?.as_str()?)))
             else {
-                let original_words: Vec<&str> = clean_original.split_whitespace().collect();
-                let hit_words: Vec<&str> = clean_hit.split_whitespace().collect();
-                
-                let matching_words = original_words.iter()
-                    .filter(|&word| hit_words.contains(word))
-                    .count();
-                
-                if matching_words > 2 {
-                    score += 1;
-                }
-            }
-            // Author matching
-            if let Some(original_author) = Some(original_author) {
-                if let Some(hit_authors) = info.get("authors").and_then(|a| a.get("author")).and_then(|a| a.as_array()) {
-                    let hit_author_names: Vec<String> = hit_authors.iter()
-                        .filter_map(|a| a.get("text").and_then(|t| t.as_str()).map(|s| s.to_string()))
-                        .collect();
-                    
-                    if !hit_author_names.is_empty() {
-                        let hit_authors_text = hit_author_names.join(" and ");
-                        
-                        // Split on both spaces and tildes for word extraction
-                        let original_author_words: Vec<&str> = original_author
-                            .split(|c| c == ' ' || c == '~')
-                            .filter(|w| !w.is_empty())
-                            .collect();
-                        let hit_author_words: Vec<&str> = hit_authors_text
-                            .split(|c| c == ' ' || c == '~')
-                            .filter(|w| !w.is_empty())
-                            .collect();
-                        
-                        let matching_author_words = original_author_words.iter()
-                            .filter(|&word| hit_author_words.contains(word))
-                            .count();
-                        
-                        if matching_author_words >= 5 {
-                            score += 2;
-                        }
-                    }
-                }
-            }
+                continue;
+            };
+            let hit_year = info.get("year").and_then(|y| y.as_str());
 
-            if score > best_score {
-                best_score = score;
-                best_match = Some(info.clone());
+            let score = match_record(entry, hit_title, hit_year, &dblp_authors(info), thresholds);
+            if score >= thresholds.min_score {
+                matches.push((score, info.clone()));
             }
         }
 
-         
-
-        if best_score >= 2 {
-            best_match
-        } else {
-            None
-        }
+        // Stable, so equal scores keep DBLP's ranking
+        matches.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        matches
     }
 
     /// Get BibTeX entry from arXiv for a given arXiv ID (async version)
@@ -233,19 +162,6 @@ impl Bibliography {
         let base_url = std::env::var("ARXIV_BASE_URL").unwrap_or_else(|_| "https://arxiv.org".to_string());
         let url = format!("{}/bibtex/{}", base_url, arxiv_id);
         
-        // Create exponential backoff strategy with configurable timeout for testing
-        let max_timeout = std::env::var("API_TIMEOUT_SECS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(30);
-        
-        let backoff = ExponentialBackoff {
-            initial_interval: Duration::from_millis(100),
-            max_interval: Duration::from_secs(5),
-            max_elapsed_time: Some(Duration::from_secs(max_timeout)),
-            ..Default::default()
-        };
-        
         let operation = || async {
             info!("Querying arXiv for BibTeX entry, ID: {}", arxiv_id);
             let response = HTTP_CLIENT.get(&url).send().await
@@ -262,12 +178,11 @@ impl Bibliography {
                     Ok(None)
                 }
             } else {
-                log::warn!("arXiv API returned status {}", response.status());
-                Err(backoff::Error::transient(BibExtractError::ApiError(format!("arXiv API returned status {}", response.status()))))
+                Err(status_error("arXiv", response.status()))
             }
         };
 
-        match retry(backoff, operation).await {
+        match retry(api_backoff(), operation).await {
             Ok(result) => Ok(result),
             Err(_) => {
                 log::warn!("arXiv API query failed after retries for ID: {}", arxiv_id);
@@ -276,105 +191,202 @@ impl Bibliography {
         }
     }
 
-    /// Verifies a BibEntry using the arXiv API.
-    async fn verify_from_arxiv(&self, entry: &BibEntry) -> Result<Option<BibEntry>, BibExtractError> {
-        if let Some(arxiv_id) = self.extract_arxiv_id(entry) {
-            let bibtex = self.get_arxiv_bibtex_async(&arxiv_id).await?;
-            if let Some(bibtex) = bibtex {
-                if let Some(mut verified_entry) = self.parse_bibtex_entry(&bibtex) {
-                    verified_entry.set("verified_source", "arXiv".to_string());
-                    return Ok(Some(verified_entry));
-                }
-            }
-        }
-        Ok(None)
+    /// Verify a single entry against the default providers (see `Verifier::default`)
+    pub async fn verify_entry(&self, entry: &mut BibEntry) -> Result<bool, BibExtractError> {
+        Verifier::default().verify_entry(entry).await
     }
+}
 
-    /// Verifies a BibEntry using the DBLP API.
-    async fn verify_from_dblp(&self, entry: &BibEntry) -> Result<Option<BibEntry>, BibExtractError> {
-        if let Some(dblp_results) = self.query_dblp_api_async(entry).await? {
-            if let Some(best_match) = self.find_best_match_in_dblp(&dblp_results, entry) {
-                let mut builder = BibEntryBuilder::new(entry.key.clone(), entry.entry_type.clone());
+/// Verifies entries with an arXiv ID through arXiv's BibTeX export
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArxivProvider;
 
-                for (field, value) in &entry.fields {
-                    if field != "verified_source" {
-                        builder = builder.field(field, value);
-                    }
-                }
+#[async_trait]
+impl MetadataProvider for ArxivProvider {
+    fn name(&self) -> &str {
+        "arXiv"
+    }
 
-                if let Some(title) = best_match.get("title").and_then(|t| t.as_str()) {
-                    builder = builder.field("title", title);
-                }
+    async fn lookup_id(&self, entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        let bib = Bibliography::new();
+        let Some(arxiv_id) = bib.extract_arxiv_id(entry) else {
+            return Ok(None);
+        };
+        let Some(bibtex) = bib.get_arxiv_bibtex_async(&arxiv_id).await? else {
+            return Ok(None);
+        };
+        Ok(bib.parse_bibtex_entry(&bibtex).map(|mut verified_entry| {
+            let authors = verified_entry.authors();
+            verified_entry.set_persons("author", &authors);
+            Candidate::new(verified_entry, 1.0, self.name())
+        }))
+    }
+}
 
-                if let Some(year) = best_match.get("year").and_then(|y| y.as_str()) {
-                    builder = builder.field("year", year);
-                }
+/// Verifies entries through DBLP's publication search
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DblpProvider {
+    thresholds: MatchThresholds,
+}
 
-                if let Some(venue) = best_match.get("venue").and_then(|v| v.as_str()) {
-                    builder = builder.field("booktitle", venue);
-                }
+impl DblpProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-                if let Some(url) = best_match.get("url").and_then(|u| u.as_str()) {
-                    builder = builder.field("url", url);
-                }
+    /// Match search results with `thresholds` instead of the defaults
+    pub fn with_thresholds(mut self, thresholds: MatchThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+}
 
-                if let Some(volume) = best_match.get("volume").and_then(|v| v.as_str()) {
-                    builder = builder.field("volume", volume);
-                }
+#[async_trait]
+impl MetadataProvider for DblpProvider {
+    fn name(&self) -> &str {
+        "DBLP"
+    }
 
-                if let Some(doi) = best_match.get("doi").and_then(|d| d.as_str()) {
-                    builder = builder.field("doi", doi);
-                }
+    /// Tries the queries of `dblp_queries` until one finds a confident match, and otherwise
+    /// gives the matches of the query that found the best one
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        let bib = Bibliography::new();
+        let mut best: Vec<Candidate> = Vec::new();
+        for query in dblp_queries(entry) {
+            let dblp_results = match bib.query_dblp_search_async(&query).await {
+                Ok(Some(dblp_results)) => dblp_results,
+                Ok(None) => continue,
+                // The other queries would fail as well
+                Err(_) => break,
+            };
+            let candidates: Vec<Candidate> = bib
+                .best_matches_in_dblp_with(&dblp_results, entry, &self.thresholds)
+                .into_iter()
+                .map(|(score, hit)| Candidate::new(dblp_hit_to_entry(&hit, entry), score, self.name()))
+                .collect();
+            let top_score = candidates.first().map_or(0.0, |candidate| candidate.score);
+            if top_score > best.first().map_or(0.0, |candidate| candidate.score) {
+                best = candidates;
+            }
+            if top_score >= self.thresholds.confident_score {
+                break;
+            }
+        }
+        Ok(best)
+    }
+}
+
+/// The DBLP searches for an entry, from the most to the least specific: its full title, its
+/// title without stopwords, punctuation and math, then the first words of that with the first
+/// author's last name, for titles DBLP spells differently. Entries without a title have none.
+pub fn dblp_queries(entry: &BibEntry) -> Vec<String> {
+    let Some(title) = entry.get("title") else {
+        return Vec::new();
+    };
+    // Markup and DBLP's query operators (`$` for exact words, `*`, `|`) are left out
+    let full_title = latex_to_unicode(title)
+        .replace(['{', '}', '$', '*', '|', '\\'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let folded = fold_text(title);
+    let keywords: Vec<&str> = folded.split_whitespace().filter(|word| !STOPWORDS.contains(word)).collect();
+    let mut queries = vec![full_title, keywords.join(" ")];
+    let last_name = entry
+        .authors()
+        .persons
+        .first()
+        .and_then(|person| fold_text(&person.last).split_whitespace().last().map(str::to_string));
+    if let Some(last_name) = last_name.filter(|_| !keywords.is_empty()) {
+        let prefix = &keywords[..keywords.len().min(TITLE_PREFIX_WORDS)];
+        queries.push(format!("{} {}", prefix.join(" "), last_name));
+    }
+    let mut unique = Vec::new();
+    for query in queries {
+        if !query.is_empty() && !unique.contains(&query) {
+            unique.push(query);
+        }
+    }
+    unique
+}
+
+/// The entry a DBLP hit describes, on top of the fields of the entry searched for
+pub(crate) fn dblp_hit_to_entry(best_match: &Value, entry: &BibEntry) -> BibEntry {
+    let entry_type = best_match
+        .get("type")
+        .and_then(|t| t.as_str())
+        .and_then(dblp_entry_type)
+        .unwrap_or(&entry.entry_type);
+    let mut builder = BibEntryBuilder::new(entry.key.clone(), entry_type);
+
+    for (field, value) in &entry.fields {
+        if field != "verified_source" {
+            builder = builder.field(field, value);
+        }
+    }
 
-                if let Some(authors) = best_match.get("authors").and_then(|a| a.get("author")).and_then(|a| a.as_array()) {
-                    let author_names: Vec<String> = authors.iter()
-                        .filter_map(|a| a.get("text").and_then(|t| t.as_str()).map(|s| s.to_string()))
-                        .collect();
-
-                    if !author_names.is_empty() {
-                        let cleaned_authors: Vec<String> = author_names.iter()
-                            .map(|name| {
-                                let parts: Vec<&str> = name.split_whitespace().collect();
-                                if parts.len() > 1 && parts.last().unwrap().chars().all(char::is_numeric) {
-                                    parts[..parts.len() - 1].join(" ")
-                                } else {
-                                    name.clone()
-                                }
-                            })
-                            .collect();
-                        builder = builder.field("author", cleaned_authors.join(" and "));
-                    }
-                }
+    if let Some(title) = best_match.get("title").and_then(|t| t.as_str()) {
+        builder = builder.field("title", title);
+    }
 
-                builder = builder.field("verified_source", "DBLP");
+    if let Some(year) = best_match.get("year").and_then(|y| y.as_str()) {
+        builder = builder.field("year", year);
+    }
 
-                return Ok(Some(builder.build()));
-            }
-        }
-        Ok(None)
+    if let Some(venue) = best_match.get("venue").and_then(|v| v.as_str()) {
+        let venue_field = if entry_type == "article" { "journal" } else { "booktitle" };
+        builder = builder.field(venue_field, venue);
     }
 
-    /// Updates a BibEntry with verified data, prioritizing arXiv.
-    fn update_entry_with_verified_data(&self, entry: &mut BibEntry, arxiv_result: Option<BibEntry>, dblp_result: Option<BibEntry>) -> bool {
-        let (source, verified_entry) = match (arxiv_result, dblp_result) {
-            (Some(arxiv_entry), _) => ("arXiv", arxiv_entry),
-            (_, Some(dblp_entry)) => ("DBLP", dblp_entry),
-            _ => return false,
-        };
+    if let Some(url) = best_match.get("url").and_then(|u| u.as_str()) {
+        builder = builder.field("url", url);
+    }
 
-        for (field, value) in verified_entry.fields.iter() {
-            if field != "raw" {
-                entry.set(field, value.clone());
-            }
-        }
-        entry.set("verified_source", source.to_string());
-        true
+    if let Some(volume) = best_match.get("volume").and_then(|v| v.as_str()) {
+        builder = builder.field("volume", volume);
     }
 
-    /// Verify a single entry using both DBLP and arXiv APIs
-    pub async fn verify_entry(&self, entry: &mut BibEntry) -> Result<bool, BibExtractError> {
-        let arxiv_result = self.verify_from_arxiv(entry).await?;
-        let dblp_result = self.verify_from_dblp(entry).await?;
-        Ok(self.update_entry_with_verified_data(entry, arxiv_result, dblp_result))
+    if let Some(doi) = best_match.get("doi").and_then(|d| d.as_str()) {
+        builder = builder.field("doi", doi);
+    }
+
+    let persons = dblp_authors(best_match);
+    if !persons.is_empty() {
+        builder = builder.field("author", PersonList { persons, is_truncated: false }.to_bibtex());
+    }
+
+    builder.build()
+}
+
+/// The authors of a DBLP hit
+fn dblp_authors(info: &Value) -> Vec<Person> {
+    let Some(authors) = info.get("authors").and_then(|a| a.get("author")).and_then(|a| a.as_array()) else {
+        return Vec::new();
+    };
+    authors
+        .iter()
+        .filter_map(|a| a.get("text").and_then(|t| t.as_str()))
+        .map(|name| {
+            // DBLP disambiguates homonyms with a numeric suffix, e.g. "Wei Wang 0001"
+            let parts: Vec<&str> = name.split_whitespace().collect();
+            if parts.len() > 1 && parts.last().unwrap().chars().all(char::is_numeric) {
+                Person::parse(&parts[..parts.len() - 1].join(" "))
+            } else {
+                Person::parse(name)
+            }
+        })
+        .collect()
+}
+
+/// Map a DBLP publication type onto the BibTeX entry type DBLP exports it as
+fn dblp_entry_type(dblp_type: &str) -> Option<&'static str> {
+    match dblp_type {
+        "Journal Articles" | "Informal Publications" | "Informal and Other Publications" => Some("article"),
+        "Conference and Workshop Papers" => Some("inproceedings"),
+        "Parts in Books or Collections" => Some("incollection"),
+        "Books and Theses" => Some("book"),
+        "Editorship" => Some("proceedings"),
+        "Reference Works" | "Data and Artifacts" => Some("misc"),
+        _ => None,
     }
-}
\ No newline at end of file
+}
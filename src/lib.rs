@@ -2,6 +2,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 pub mod latex;
 pub mod error;
+pub mod crawl;
 pub use internal::extract_survey_internal;
 
 
@@ -9,6 +10,7 @@ pub use internal::extract_survey_internal;
 #[pymodule]
 fn bibextract(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(extract_survey, m)?)?;
+    m.add_function(wrap_pyfunction!(crawl_survey, m)?)?;
     Ok(())
 }
 
@@ -40,6 +42,47 @@ fn extract_survey(paper_ids: Vec<String>) -> PyResult<PyObject> {
     })
 }
 
+/// Crawls arXiv papers starting from seed IDs, following arXiv IDs cited in their related work
+/// sections, and merges everything into a single survey and BibTeX bibliography.
+#[pyfunction]
+#[pyo3(name = "crawl_survey", signature = (paper_ids, max_depth=1, max_papers=20, categories=None, min_year=None, max_year=None))]
+fn crawl_survey(
+    paper_ids: Vec<String>,
+    max_depth: usize,
+    max_papers: usize,
+    categories: Option<Vec<String>>,
+    min_year: Option<u32>,
+    max_year: Option<u32>,
+) -> PyResult<PyObject> {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).try_init();
+    let options = crawl::CrawlOptions {
+        max_depth,
+        max_papers,
+        categories: categories.unwrap_or_default(),
+        min_year,
+        max_year,
+        ..crawl::CrawlOptions::new(paper_ids)
+    };
+    Python::with_gil(|py| {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_io().enable_time().build().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to create async runtime: {}", e))
+        })?;
+
+        match rt.block_on(internal::crawl_survey_internal(&options)) {
+            Ok((survey_text, bibtex)) => {
+                let dict = PyDict::new(py);
+                dict.set_item("survey_text", survey_text)?;
+                dict.set_item("bibtex", bibtex)?;
+                Ok(dict.into())
+            }
+            Err(e) => Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Error crawling arXiv papers: {}",
+                e
+            ))),
+        }
+    })
+}
+
 // Encapsulate the core logic in a private module to be shared between the lib and bin targets.
 pub mod internal {
     use crate::latex;
@@ -49,8 +92,23 @@ pub mod internal {
     /// Internal function to process papers and return survey text and BibTeX.
     /// This is the core logic shared by the Python API and the CLI.
     pub async fn extract_survey_internal(paper_ids: Vec<String>) -> Result<(String, String), BibExtractError> {
-        use latex::Bibliography;
+        let all_papers = fetch_papers(paper_ids).await?;
+        build_survey(&all_papers)
+    }
+
+    /// Crawl from seed papers and merge every visited paper into one survey text and BibTeX.
+    pub async fn crawl_survey_internal(options: &crate::crawl::CrawlOptions) -> Result<(String, String), BibExtractError> {
+        let all_papers = crate::crawl::crawl(options).await?;
+        build_survey(&all_papers)
+    }
+
+    /// Download, parse and verify a set of arXiv papers concurrently.
+    pub async fn fetch_papers(paper_ids: Vec<String>) -> Result<Vec<latex::ArxivPaper>, BibExtractError> {
+        fetch_papers_with(paper_ids, &latex::Verifier::default()).await
+    }
 
+    /// Download and parse a set of arXiv papers concurrently, verifying them with `verifier`.
+    pub async fn fetch_papers_with(paper_ids: Vec<String>, verifier: &latex::Verifier) -> Result<Vec<latex::ArxivPaper>, BibExtractError> {
         if paper_ids.is_empty() {
             return Err(BibExtractError::NoPaperIdsProvided);
         }
@@ -58,24 +116,7 @@ pub mod internal {
         // Process papers concurrently using tokio tasks
         let paper_tasks: Vec<_> = paper_ids
             .into_iter()
-            .map(|paper_id| {
-                tokio::spawn(async move {
-                    info!("Processing arXiv paper with ID: {}", paper_id);
-                    let mut paper = latex::download_arxiv_source_async(&paper_id).await?;
-                    
-                    info!("Verifying bibliography entries for paper {}", paper_id);
-                    let verified_count = paper.verify_bibliography().await?;
-                    info!(
-                        "Verified {}/{} entries for paper {} using async verification",
-                        verified_count,
-                        paper.bibliography.iter().count(),
-                        paper_id
-                    );
-
-                    info!("Found {} sections with bibliography entries", paper.sections.len());
-                    Ok::<_, BibExtractError>(paper)
-                })
-            })
+            .map(|paper_id| tokio::spawn(fetch_paper_with(paper_id, verifier.clone())))
             .collect();
 
         // Wait for all papers to be processed
@@ -85,17 +126,51 @@ pub mod internal {
             all_papers.push(paper);
         }
 
-        let mut consolidated_bibliography = Bibliography::new();
-        // Merge bibliographies from all papers into a single consolidated one.
-        for paper in &all_papers {
+        Ok(all_papers)
+    }
+
+    /// Download, parse and verify a single arXiv paper.
+    pub async fn fetch_paper(paper_id: String) -> Result<latex::ArxivPaper, BibExtractError> {
+        fetch_paper_with(paper_id, latex::Verifier::default()).await
+    }
+
+    /// Download and parse a single arXiv paper, verifying it with `verifier`.
+    pub async fn fetch_paper_with(paper_id: String, verifier: latex::Verifier) -> Result<latex::ArxivPaper, BibExtractError> {
+        info!("Processing arXiv paper with ID: {}", paper_id);
+        let mut paper = latex::download_arxiv_source_with(&paper_id, &verifier).await?;
+
+        info!("Verifying bibliography entries for paper {}", paper_id);
+        let verified_count = paper.verify_bibliography_with(&verifier).await?;
+        info!(
+            "Verified {}/{} entries for paper {} using async verification",
+            verified_count,
+            paper.bibliography.iter().count(),
+            paper_id
+        );
+
+        info!("Bibliography of paper {}: {}", paper_id, paper.bibliography.parse_summary());
+        info!("Found {} sections with bibliography entries", paper.sections.len());
+        Ok(paper)
+    }
+
+    /// Merge bibliographies from all papers into a single consolidated one.
+    pub fn consolidate_bibliography(papers: &[latex::ArxivPaper]) -> latex::Bibliography {
+        let mut consolidated_bibliography = latex::Bibliography::new();
+        for paper in papers {
             for entry in paper.bibliography.iter() {
                 consolidated_bibliography.insert(entry.clone());
             }
         }
+        consolidated_bibliography
+    }
+
+    /// Build the survey text and BibTeX string from already processed papers.
+    pub fn build_survey(all_papers: &[latex::ArxivPaper]) -> Result<(String, String), BibExtractError> {
+        let consolidated_bibliography = consolidate_bibliography(all_papers);
 
         // Process and format all sections, normalizing citations against the consolidated bibliography.
         let mut survey_text = String::new();
-        for paper in &all_papers {
+        for paper in all_papers {
             // add paper ID, title and authors to the as a comment
             survey_text.push_str(&format!(
                 "% Paper ID: {}\n% Title: {}\n% Authors: {}\n\n",
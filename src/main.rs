@@ -3,9 +3,12 @@ use clap::Parser;
 use log::info;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-// Use the shared internal function from the library crate.
-use bibextract::extract_survey_internal;
+// Use the shared internal functions from the library crate.
+use bibextract::crawl::{crawl, CrawlOptions};
+use bibextract::internal::{build_survey, fetch_papers_with};
+use bibextract::latex::{AclAnthologyProvider, ArxivSnapshotProvider, CitationGraph, CitationStats, DblpDumpProvider, GraphFormat, LowConfidence, Verifier};
 
 /// A CLI for extracting survey content and bibliography from arXiv papers.
 #[derive(Parser)]
@@ -20,6 +23,79 @@ struct Args {
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Write the citation graph to this file.
+    #[arg(long)]
+    graph: Option<PathBuf>,
+
+    /// Format of the citation graph: 'json', 'graphml' or 'dot'.
+    /// Defaults to the extension of the graph file, or 'json'.
+    #[arg(long)]
+    graph_format: Option<GraphFormat>,
+
+    /// Print citation statistics (most cited entries, co-citations, overlap, years).
+    #[arg(long)]
+    stats: bool,
+
+    /// Write the citation statistics as JSON to this file.
+    #[arg(long)]
+    stats_json: Option<PathBuf>,
+
+    /// Recursively follow the arXiv IDs cited in the extracted sections.
+    #[arg(long)]
+    crawl: bool,
+
+    /// Maximum number of citation hops to follow when crawling.
+    #[arg(long, default_value_t = 1)]
+    max_depth: usize,
+
+    /// Maximum number of papers to download when crawling, seeds included.
+    #[arg(long, default_value_t = 20)]
+    max_papers: usize,
+
+    /// Only crawl papers in these arXiv categories (e.g. 'cs.CL' or 'cs').
+    #[arg(long)]
+    category: Vec<String>,
+
+    /// Only crawl papers submitted in or after this year.
+    #[arg(long)]
+    min_year: Option<u32>,
+
+    /// Only crawl papers submitted in or before this year.
+    #[arg(long)]
+    max_year: Option<u32>,
+
+    /// Flag bibliography entries whose parse confidence (0 to 1) is below this value.
+    #[arg(long)]
+    min_confidence: Option<f32>,
+
+    /// Drop low-confidence entries instead of flagging them with a 'parse_warnings' field.
+    #[arg(long, requires = "min_confidence")]
+    drop_low_confidence: bool,
+
+    /// Verify entries against this local dblp.xml(.gz) dump instead of the DBLP API.
+    /// The dump is indexed once, into '<dump>.index.jsonl' next to it.
+    #[arg(long)]
+    dblp_dump: Option<PathBuf>,
+
+    /// Verify entries against this local arXiv metadata snapshot (JSON Lines, optionally
+    /// gzip-compressed) instead of the arXiv API. It also supplies the title and authors of
+    /// the papers when arXiv's BibTeX export is unavailable.
+    #[arg(long)]
+    arxiv_snapshot: Option<PathBuf>,
+
+    /// Also verify entries against this local ACL Anthology BibTeX export ('anthology.bib' or
+    /// 'anthology+abstracts.bib', optionally gzip-compressed), right after arXiv.
+    #[arg(long)]
+    acl_anthology: Option<PathBuf>,
+
+    /// Do not verify entries against this provider (e.g. 'OpenAlex' or 'Crossref'); may be repeated.
+    #[arg(long)]
+    disable_provider: Vec<String>,
+
+    /// Verify entries only against local data (--dblp-dump, --arxiv-snapshot, --acl-anthology), without network lookups.
+    #[arg(long)]
+    offline: bool,
+
     /// Enable verbose logging to see detailed processing steps.
     #[arg(short, long)]
     verbose: bool,
@@ -33,9 +109,57 @@ async fn main() -> Result<()> {
     let log_level = if args.verbose { "debug" } else { "info" };
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level)).init();
 
-    // Use the shared internal function to process papers.
-    match extract_survey_internal(args.paper_ids).await {
-        Ok((survey_text, bibtex)) => {
+    let mut verifier = if args.offline { Verifier::new() } else { Verifier::default() };
+    if let Some(dump) = &args.dblp_dump {
+        let provider = DblpDumpProvider::open(dump).with_context(|| format!("Failed to open DBLP dump {:?}", dump))?;
+        verifier.replace("DBLP", Arc::new(provider));
+    }
+
+    if let Some(snapshot) = &args.arxiv_snapshot {
+        let provider = ArxivSnapshotProvider::open(snapshot).with_context(|| format!("Failed to open arXiv snapshot {:?}", snapshot))?;
+        verifier.replace("arXiv", Arc::new(provider));
+    }
+    if let Some(anthology) = &args.acl_anthology {
+        let provider = AclAnthologyProvider::open(anthology).with_context(|| format!("Failed to open ACL Anthology {:?}", anthology))?;
+        verifier.register(Arc::new(provider));
+        verifier.set_priority(&["arXiv", "ACL Anthology"]);
+    }
+    for name in &args.disable_provider {
+        verifier.remove(name);
+    }
+
+    // Use the shared internal functions to process papers.
+    let papers = if args.crawl {
+        let options = CrawlOptions {
+            max_depth: args.max_depth,
+            max_papers: args.max_papers,
+            categories: args.category,
+            min_year: args.min_year,
+            max_year: args.max_year,
+            verifier,
+            ..CrawlOptions::new(args.paper_ids)
+        };
+        crawl(&options).await
+    } else {
+        fetch_papers_with(args.paper_ids, &verifier).await
+    };
+    let papers = papers.map(|mut papers| {
+        if let Some(threshold) = args.min_confidence {
+            let policy = if args.drop_low_confidence { LowConfidence::Drop } else { LowConfidence::Flag };
+            for paper in &mut papers {
+                let count = paper.bibliography.apply_confidence_threshold(threshold, policy);
+                if count > 0 {
+                    let action = if policy == LowConfidence::Drop { "Dropped" } else { "Flagged" };
+                    info!("{} {} low-confidence entries of paper {}", action, count, paper.id);
+                }
+            }
+        }
+        papers
+    });
+    let result = papers.and_then(|papers| build_survey(&papers).map(|output| (papers, output)));
+
+    match result {
+        Ok((papers, (survey_text, bibtex))) => {
             // Handle file output.
             if let Some(output_path) = args.output {
                 // Create the .tex file.
@@ -54,6 +178,31 @@ async fn main() -> Result<()> {
                 println!("--- bibliography.bib ---");
                 println!("{}", bibtex);
             }
+
+            // Write the citation graph if requested.
+            if let Some(graph_path) = args.graph {
+                let format = args
+                    .graph_format
+                    .or_else(|| graph_path.extension().and_then(|ext| ext.to_str()?.parse().ok()))
+                    .unwrap_or(GraphFormat::Json);
+                let graph = CitationGraph::from_papers(&papers);
+                fs::write(&graph_path, graph.export(format))
+                    .with_context(|| format!("Failed to write to {:?}", graph_path))?;
+                info!("Citation graph written to {:?}", graph_path);
+            }
+
+            // Print or write the citation statistics if requested.
+            if args.stats || args.stats_json.is_some() {
+                let stats = CitationStats::from_papers(&papers);
+                if args.stats {
+                    println!("{}", stats.to_table(20));
+                }
+                if let Some(stats_path) = args.stats_json {
+                    let json = serde_json::to_string_pretty(&stats.to_json())?;
+                    fs::write(&stats_path, json).with_context(|| format!("Failed to write to {:?}", stats_path))?;
+                    info!("Citation statistics written to {:?}", stats_path);
+                }
+            }
         }
         Err(e) => {
             log::error!("{}", e);
@@ -63,4 +212,3 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
-
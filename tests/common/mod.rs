@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests. Each test crate uses some of them.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use bibextract::latex::{BibEntry, FieldSource};
+
+/// Path of a file in `tests/fixtures`
+pub fn fixture_path(file_name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(file_name)
+}
+
+/// A `misc` entry with these fields, as parsed from a .bbl file
+pub fn entry_with(fields: &[(&str, &str)]) -> BibEntry {
+    keyed_entry_with("key", fields)
+}
+
+/// A `misc` entry with this key and these fields, as parsed from a .bbl file
+pub fn keyed_entry_with(key: &str, fields: &[(&str, &str)]) -> BibEntry {
+    let mut entry = BibEntry {
+        key: key.to_string(),
+        entry_type: "misc".to_string(),
+        fields: fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        ..Default::default()
+    };
+    entry.mark_source(FieldSource::Bbl);
+    entry
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use bibextract::latex::acl_anthology::anthology_id;
+use bibextract::latex::{AclAnthologyProvider, AnthologyIndex, MetadataProvider, Verifier};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use common::{entry_with, fixture_path};
+
+fn provider() -> AclAnthologyProvider {
+    AclAnthologyProvider::new(Arc::new(AnthologyIndex::from_bib(&fixture_path("anthology_sample.bib")).unwrap()))
+}
+
+#[test]
+fn test_anthology_id() {
+    let entry = entry_with(&[("url", "https://www.aclweb.org/anthology/N19-1423.pdf")]);
+    assert_eq!(anthology_id(&entry).unwrap(), "N19-1423");
+    let entry = entry_with(&[("doi", "10.18653/v1/2020.acl-main.703")]);
+    assert_eq!(anthology_id(&entry).unwrap(), "2020.acl-main.703");
+    let entry = entry_with(&[("raw", "In Proc. of ACL, 2021. https://aclanthology.org/2021.findings-acl.12/")]);
+    assert_eq!(anthology_id(&entry).unwrap(), "2021.findings-acl.12");
+    assert!(anthology_id(&entry_with(&[("url", "https://arxiv.org/abs/1810.04805")])).is_none());
+}
+
+#[test]
+fn test_anthology_index() {
+    let index = AnthologyIndex::from_bib(&fixture_path("anthology_sample.bib")).unwrap();
+    assert_eq!(index.len(), 5);
+    let bert = index.get("N19-1423").unwrap();
+    assert_eq!(bert.key, "devlin-etal-2019-bert");
+    assert_eq!(bert.get("month").unwrap(), "June");
+    assert!(bert.get("abstract").is_none());
+    assert!(index.get("N19-9999").is_none());
+}
+
+#[test]
+fn test_acl_anthology_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let provider = provider();
+
+        // Matched by title and authors, with the Anthology's own fields
+        let mut bert = entry_with(&[
+            ("author", "Devlin, J. and Chang, M.-W. and Lee, K. and Toutanova, K."),
+            ("title", "BERT: Pre-training of deep bidirectional transformers for language understanding"),
+            ("journal", "arXiv preprint"),
+            ("year", "2019"),
+        ]);
+        let verifier = Verifier::new().with_provider(provider.clone());
+        assert!(verifier.verify_entry(&mut bert).await.unwrap());
+        assert_eq!(bert.key, "key");
+        assert_eq!(bert.entry_type, "inproceedings");
+        assert_eq!(bert.get("url").unwrap(), "https://aclanthology.org/N19-1423");
+        assert_eq!(bert.get("doi").unwrap(), "10.18653/v1/N19-1423");
+        assert_eq!(bert.get("pages").unwrap(), "4171--4186");
+        assert_eq!(bert.get("publisher").unwrap(), "Association for Computational Linguistics");
+        assert_eq!(bert.get("verified_source").unwrap(), "ACL Anthology");
+
+        // The paper with the same title by someone else is passed over
+        let bart = entry_with(&[
+            ("author", "Lewis, Mike and others"),
+            ("title", "BART: Denoising sequence-to-sequence pre-training for natural language generation, translation, and comprehension"),
+            ("year", "2020"),
+        ]);
+        let candidates = provider.search(&bart).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry.get("url").unwrap(), "https://aclanthology.org/2020.acl-main.703");
+
+        // Names the Anthology writes with TeX accents are the same people
+        let muller = entry_with(&[("author", "Müller, T."), ("title", "Parsing compounds with character models"), ("year", "2020")]);
+        let candidates = provider.search(&muller).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry.get("url").unwrap(), "https://aclanthology.org/2020.acl-main.999");
+
+        // Links to the Anthology resolve directly
+        let by_id = entry_with(&[("title", "BERT"), ("url", "https://aclanthology.org/N19-1423.pdf")]);
+        let candidate = provider.lookup_id(&by_id).await.unwrap().unwrap();
+        assert_eq!(candidate.score, 1.0);
+        assert_eq!(candidate.entry.get("doi").unwrap(), "10.18653/v1/N19-1423");
+
+        let unknown = entry_with(&[("author", "Doe, J."), ("title", "Something else entirely"), ("year", "2019")]);
+        assert!(provider.search(&unknown).await.unwrap().is_empty());
+    });
+}
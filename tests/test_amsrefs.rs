@@ -0,0 +1,128 @@
+use bibextract::latex::amsrefs::{is_amsrefs, parse_amsrefs};
+use bibextract::latex::{Bibliography, FieldSource};
+
+const AMSREFS: &str = r"\begin{bibdiv}
+\begin{biblist}
+
+\bib{erdos1947}{article}{
+  author={Erd\H{o}s, P.},
+  title={Some remarks on the theory of graphs},
+  journal={Bull. Amer. Math. Soc.},
+  volume={53},
+  date={1947},
+  pages={292--294},
+  review={\MR{0019911}},
+}
+
+\bib{hardy1938}{book}{
+  author={Hardy, G. H.},
+  author={Wright, E. M.},
+  title={An introduction to the theory of numbers},
+  publisher={Clarendon Press},
+  place={Oxford},
+  date=1938,
+}
+
+\bib{tao2008}{article}{
+  author={Tao, Terence},
+  title={Structure and randomness in combinatorics},
+  subtitle={A survey},
+  conference={
+    title={48th Annual IEEE Symposium on Foundations of Computer Science},
+    date={2007},
+  },
+  book={
+    publisher={IEEE Computer Society},
+    place={Los Alamitos, CA},
+  },
+  date={2007-10},
+  pages={3--15},
+  eprint={arXiv:0707.4269},
+}
+
+\bib{chapter2001}{article}{
+  author={Smith, A.},
+  title={A chapter},
+  book={
+    title={Collected essays},
+    editor={Jones, B.},
+    publisher={Springer},
+  },
+  date={2001},
+}
+
+\bib{thesis1999}{thesis}{
+  author={Doe, Jane},
+  title={Graphs and their colourings},
+  type={Master's Thesis},
+  organization={University of Oslo},
+  date={1999},
+}
+
+\bib*{erdos1947}{article}{
+  title={Duplicate record, ignored},
+}
+
+\end{biblist}
+\end{bibdiv}";
+
+#[test]
+fn test_is_amsrefs() {
+    assert!(is_amsrefs(AMSREFS));
+    assert!(!is_amsrefs(r"\begin{thebibliography}{9}\bibitem{a} A.\end{thebibliography}"));
+    assert!(!is_amsrefs(r"\bibfield{author}{\bibinfo{person}{A}}"));
+}
+
+#[test]
+fn test_parse_amsrefs_records() {
+    let entries = parse_amsrefs(AMSREFS);
+    assert_eq!(entries.len(), 5);
+    let entry = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+
+    let erdos = entry("erdos1947");
+    assert_eq!(erdos.entry_type, "article");
+    assert_eq!(erdos.get("author").unwrap(), "Erdős, P.");
+    assert_eq!(erdos.get("journal").unwrap(), "Bull. Amer. Math. Soc.");
+    assert_eq!(erdos.get("year").unwrap(), "1947");
+    assert_eq!(erdos.get("pages").unwrap(), "292--294");
+    assert_eq!(erdos.get("mrnumber").unwrap(), "0019911");
+
+    let hardy = entry("hardy1938");
+    assert_eq!(hardy.entry_type, "book");
+    assert_eq!(hardy.get("author").unwrap(), "Hardy, G. H. and Wright, E. M.");
+    assert_eq!(hardy.get("address").unwrap(), "Oxford");
+    assert_eq!(hardy.get("year").unwrap(), "1938");
+
+    let tao = entry("tao2008");
+    assert_eq!(tao.entry_type, "inproceedings");
+    assert_eq!(tao.get("title").unwrap(), "Structure and randomness in combinatorics: A survey");
+    assert_eq!(tao.get("booktitle").unwrap(), "48th Annual IEEE Symposium on Foundations of Computer Science");
+    assert_eq!(tao.get("publisher").unwrap(), "IEEE Computer Society");
+    assert_eq!(tao.get("year").unwrap(), "2007");
+    assert_eq!(tao.get("eprint").unwrap(), "0707.4269");
+    assert_eq!(tao.get("archivePrefix").unwrap(), "arXiv");
+
+    let chapter = entry("chapter2001");
+    assert_eq!(chapter.entry_type, "incollection");
+    assert_eq!(chapter.get("booktitle").unwrap(), "Collected essays");
+    assert_eq!(chapter.get("editor").unwrap(), "Jones, B.");
+
+    let thesis = entry("thesis1999");
+    assert_eq!(thesis.entry_type, "mastersthesis");
+    assert_eq!(thesis.get("school").unwrap(), "University of Oslo");
+}
+
+#[test]
+fn test_amsrefs_through_bibliography() {
+    let bibliography = Bibliography::parse_bbl(AMSREFS).unwrap();
+    assert_eq!(bibliography.entries.len(), 5);
+    let erdos = bibliography.get("erdos1947").unwrap();
+    assert_eq!(erdos.source("title"), Some(FieldSource::Bbl));
+    assert!(erdos.warnings.is_empty());
+
+    // amsrefs lists embedded in the LaTeX source
+    let latex = format!("\\section{{Background}} See \\cite{{hardy1938}}.\n{}\n\\end{{document}}", AMSREFS);
+    let embedded = Bibliography::parse_embedded_bibliography(&latex).unwrap();
+    assert_eq!(embedded.entries.len(), 5);
+    assert_eq!(embedded.get("hardy1938").unwrap().get("publisher").unwrap(), "Clarendon Press");
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use bibextract::latex::arxiv_search::{arxiv_search_query, cites_preprint, parse_arxiv_feed};
+use bibextract::latex::{ArxivSearchProvider, DblpProvider, MetadataProvider, Verifier};
+use mockito::{Matcher, Server};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+use common::entry_with;
+
+const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="html">ArXiv Query: search_query=ti:"attention is all you need" AND au:Vaswani</title>
+  <entry>
+    <id>http://arxiv.org/abs/1706.03762v7</id>
+    <updated>2023-08-02T00:41:18Z</updated>
+    <published>2017-06-12T17:57:34Z</published>
+    <title>Attention Is All
+  You Need</title>
+    <author>
+      <name>Ashish Vaswani</name>
+    </author>
+    <author>
+      <name>Noam Shazeer</name>
+    </author>
+    <arxiv:primary_category xmlns:arxiv="http://arxiv.org/schemas/atom" term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/abs/2001.00001v1</id>
+    <published>2020-01-01T00:00:00Z</published>
+    <title>Attention Is Not All You Need for Q&amp;A</title>
+    <author><name>Jane Roe</name></author>
+    <arxiv:doi xmlns:arxiv="http://arxiv.org/schemas/atom">10.1000/xyz</arxiv:doi>
+  </entry>
+</feed>"#;
+
+#[test]
+fn test_parse_arxiv_feed() {
+    let results = parse_arxiv_feed(FEED);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].id, "1706.03762");
+    assert_eq!(results[0].title, "Attention Is All You Need");
+    assert_eq!(results[0].authors, vec!["Ashish Vaswani", "Noam Shazeer"]);
+    assert_eq!(results[0].year, "2017");
+    assert_eq!(results[0].primary_category.as_deref(), Some("cs.CL"));
+    assert!(results[0].doi.is_none());
+    assert_eq!(results[1].title, "Attention Is Not All You Need for Q&A");
+    assert_eq!(results[1].doi.as_deref(), Some("10.1000/xyz"));
+
+    // Error feeds have no papers
+    let error = r#"<feed><entry><id>http://arxiv.org/api/errors#incorrect_id_format</id><title>Error</title></entry></feed>"#;
+    assert!(parse_arxiv_feed(error).is_empty());
+}
+
+#[test]
+fn test_arxiv_search_query() {
+    let entry = entry_with(&[("author", "Vaswani, A. and others"), ("title", "Attention is {All} you need")]);
+    assert_eq!(arxiv_search_query(&entry).unwrap(), "ti:\"attention is all you need\" AND au:Vaswani");
+
+    // Math and query syntax are left out
+    let entry = entry_with(&[("title", "Learning $\\alpha$-divergences: \"robust\" Q&A #1?")]);
+    assert_eq!(arxiv_search_query(&entry).unwrap(), "ti:\"learning divergences robust q a 1\"");
+    assert!(arxiv_search_query(&entry_with(&[("title", "$x$")])).is_none());
+
+    assert!(cites_preprint(&entry_with(&[("title", "A")])));
+    assert!(cites_preprint(&entry_with(&[("journal", "arXiv preprint arXiv:1706.03762")])));
+    assert!(cites_preprint(&entry_with(&[("journal", "CoRR")])));
+    assert!(!cites_preprint(&entry_with(&[("booktitle", "NeurIPS")])));
+}
+
+#[test]
+fn test_arxiv_search_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let search_mock = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("search_query".into(), "ti:\"attention is all you need\" AND au:Vaswani".into()),
+                Matcher::UrlEncoded("max_results".into(), "5".into()),
+            ]))
+            .with_status(200)
+            .with_body(FEED)
+            .expect(2)
+            .create_async()
+            .await;
+        std::env::set_var("ARXIV_API_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let provider = ArxivSearchProvider::new().with_rate_limit(Duration::from_millis(200));
+        let mut entry = entry_with(&[
+            ("author", "Vaswani, Ashish and Shazeer, Noam and Parmar, Niki and Uszkoreit, Jakob and Jones, Llion"),
+            ("title", "Attention is all you need"),
+            ("year", "2017"),
+        ]);
+        let start = Instant::now();
+        let candidates = provider.search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].score, 1.0);
+        assert_eq!(candidates[0].provider, "arXiv search");
+
+        let verifier = Verifier::new().with_provider(provider);
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(entry.get("eprint").unwrap(), "1706.03762");
+        assert_eq!(entry.get("archivePrefix").unwrap(), "arXiv");
+        assert_eq!(entry.get("primaryClass").unwrap(), "cs.CL");
+        assert_eq!(entry.get("url").unwrap(), "https://arxiv.org/abs/1706.03762");
+        assert_eq!(entry.get("title").unwrap(), "Attention Is All You Need");
+        assert_eq!(entry.get("year").unwrap(), "2017");
+        assert_eq!(entry.entry_type, "misc");
+
+        // Published papers are not searched for
+        let published = entry_with(&[
+            ("author", "Vaswani, Ashish"),
+            ("title", "Attention is all you need"),
+            ("booktitle", "NeurIPS"),
+            ("year", "2017"),
+        ]);
+        assert!(ArxivSearchProvider::new().search(&published).await.unwrap().is_empty());
+
+        // Registered after DBLP, the search only runs for entries DBLP does not verify
+        let _dblp_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"result":{"hits":{"@total":"1","hit":[{"info":{
+                "authors":{"author":[{"text":"Ashish Vaswani"},{"text":"Noam Shazeer"}]},
+                "title":"Attention is All you Need.","venue":"NIPS","year":"2017","type":"Conference and Workshop Papers"
+            }}]}}}"#)
+            .create_async()
+            .await;
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        let verifier = Verifier::new().with_provider(DblpProvider::new()).with_provider(ArxivSearchProvider::new());
+        let mut preprint = entry_with(&[
+            ("author", "Vaswani, Ashish and Shazeer, Noam"),
+            ("title", "Attention is all you need"),
+            ("year", "2017"),
+        ]);
+        assert!(verifier.verify_entry(&mut preprint).await.unwrap());
+        assert_eq!(preprint.get("verified_source").unwrap(), "DBLP");
+        assert!(preprint.get("eprint").is_none());
+        search_mock.assert_async().await;
+
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("ARXIV_API_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use bibextract::latex::{download_arxiv_source_with, ArxivIndex, ArxivSnapshotProvider, MetadataProvider, Verifier};
+use mockito::Server;
+use std::io::Write;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use common::{entry_with, fixture_path};
+
+fn provider() -> ArxivSnapshotProvider {
+    ArxivSnapshotProvider::new(Arc::new(ArxivIndex::from_snapshot(&fixture_path("arxiv_snapshot.jsonl")).unwrap()))
+}
+
+#[test]
+fn test_arxiv_index_from_snapshot() {
+    // The malformed line is skipped
+    let index = ArxivIndex::from_snapshot(&fixture_path("arxiv_snapshot.jsonl")).unwrap();
+    assert_eq!(index.len(), 3);
+
+    let attention = index.get("1706.03762v5").unwrap();
+    assert_eq!(attention.year.as_deref(), Some("2017"));
+    assert_eq!(attention.categories, vec!["cs.CL", "cs.LG"]);
+    let entry = attention.to_entry("vaswani2017");
+    assert_eq!(entry.entry_type, "misc");
+    assert_eq!(entry.get("author").unwrap(), "Vaswani, Ashish and Shazeer, Noam and Parmar, Niki");
+    assert_eq!(entry.get("eprint").unwrap(), "1706.03762");
+    assert_eq!(entry.get("archivePrefix").unwrap(), "arXiv");
+    assert_eq!(entry.get("primaryClass").unwrap(), "cs.CL");
+    assert_eq!(entry.get("url").unwrap(), "https://arxiv.org/abs/1706.03762");
+
+    // Old-style IDs, the year from the ID, the author string when there are no parsed authors
+    let branes = index.get("hep-th/9901001").unwrap();
+    assert_eq!(branes.title, "Branes and Black Holes");
+    assert_eq!(branes.year.as_deref(), Some("1999"));
+    let entry = branes.to_entry("muller1999");
+    assert_eq!(entry.get("author").unwrap(), "Müller, J. and Smith, A.");
+    assert_eq!(entry.get("doi").unwrap(), "10.1016/S0370-2693(99)00001-1");
+    assert_eq!(entry.get("note").unwrap(), "Phys.Lett. B450 (1999) 1-10");
+
+    assert!(index.get("1234.56789").is_none());
+
+    // Compressed snapshots
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("arxiv-metadata-oai-snapshot.json.gz");
+    let mut encoder = flate2::write::GzEncoder::new(std::fs::File::create(&path).unwrap(), flate2::Compression::default());
+    encoder.write_all(&std::fs::read(fixture_path("arxiv_snapshot.jsonl")).unwrap()).unwrap();
+    encoder.finish().unwrap();
+    assert_eq!(ArxivIndex::from_snapshot(&path).unwrap().len(), 3);
+}
+
+#[test]
+fn test_arxiv_snapshot_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let provider = provider();
+
+        let by_id = entry_with(&[("title", "Attention"), ("raw", "Vaswani et al. Attention. arXiv:1706.03762, 2017.")]);
+        let candidate = provider.lookup_id(&by_id).await.unwrap().unwrap();
+        assert_eq!(candidate.score, 1.0);
+        assert_eq!(candidate.provider, "arXiv");
+        assert_eq!(candidate.entry.key, "key");
+
+        // Cited by title only
+        let mut by_title = entry_with(&[
+            ("author", "Vaswani, A. and Shazeer, N."),
+            ("title", "Attention is all you need"),
+            ("year", "2017"),
+        ]);
+        assert!(provider.lookup_id(&by_title).await.unwrap().is_none());
+        let verifier = Verifier::new().with_provider(provider.clone());
+        assert!(verifier.verify_entry(&mut by_title).await.unwrap());
+        assert_eq!(by_title.get("eprint").unwrap(), "1706.03762");
+        assert_eq!(by_title.get("verified_source").unwrap(), "arXiv");
+
+        let unknown = entry_with(&[("author", "Doe, J."), ("title", "Something else entirely"), ("year", "2017")]);
+        assert!(provider.search(&unknown).await.unwrap().is_empty());
+
+        let paper = verifier.lookup_arxiv_paper("2104.08653").await.unwrap();
+        assert_eq!(paper.get("title").unwrap(), "A Survey of Surveys");
+        assert!(Verifier::new().lookup_arxiv_paper("2104.08653").await.is_none());
+    });
+}
+
+#[test]
+fn test_paper_metadata_without_bibtex_export() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let tex = "\\documentclass{article}\n\\begin{document}\n\\section{Introduction}\nText \\cite{a}.\n\\begin{thebibliography}{9}\n\\bibitem{a} A. Author. A title. 2020.\n\\end{thebibliography}\n\\end{document}\n";
+        let mut tar_data = Vec::new();
+        {
+            let mut tar = tar::Builder::new(&mut tar_data);
+            let mut header = tar::Header::new_gnu();
+            header.set_path("main.tex").unwrap();
+            header.set_size(tex.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append(&header, tex.as_bytes()).unwrap();
+            tar.finish().unwrap();
+        }
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&tar_data).unwrap();
+        let _source_mock = server
+            .mock("GET", "/e-print/2104.08653")
+            .with_status(200)
+            .with_body(encoder.finish().unwrap())
+            .create_async()
+            .await;
+        let _bibtex_mock = server.mock("GET", "/bibtex/2104.08653").with_status(503).create_async().await;
+        std::env::set_var("ARXIV_BASE_URL", server.url());
+
+        let paper = download_arxiv_source_with("2104.08653", &Verifier::new().with_provider(provider())).await.unwrap();
+        assert_eq!(paper.title, "A Survey of Surveys");
+        assert_eq!(paper.authors, "Roe, Jane");
+
+        // Without a provider that knows the paper, the download fails as before
+        assert!(download_arxiv_source_with("2104.08653", &Verifier::new()).await.is_err());
+
+        std::env::remove_var("ARXIV_BASE_URL");
+    });
+}
@@ -0,0 +1,378 @@
+use bibextract::latex::bbl_style::{style_parsers, GenericStyle};
+use bibextract::latex::{bbl_style_by_name, detect_bbl_style, register_bbl_style, BibEntry, Bibliography, BblStyleParser};
+use std::fs;
+use std::path::Path;
+
+fn load_bbl_fixture(file_name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(file_name);
+    fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read {}", file_name))
+}
+
+const APALIKE_BBL: &str = r"\begin{thebibliography}{}
+
+\bibitem[Acemoglu and Restrepo, 2018]{acemoglu2018}
+Acemoglu, D. and Restrepo, P. (2018).
+\newblock Artificial intelligence, automation, and work.
+\newblock In {\em The Economics of Artificial Intelligence}, pages 197--236.
+
+\bibitem[Baker et~al., 2022]{vpt}
+Baker, B., Akkaya, I., and Clune, J. (2022).
+\newblock Video pretraining ({VPT}): Learning to act by watching unlabeled online videos.
+\newblock {\em arXiv preprint arXiv:2206.11795}.
+
+\end{thebibliography}
+";
+
+const AMSPLAIN_BBL: &str = r"\providecommand{\bysame}{\leavevmode\hbox to3em{\hrulefill}\thinspace}
+\providecommand{\MR}{\relax\ifhmode\unskip\space\fi MR }
+\providecommand{\MRhref}[2]{%
+  \href{http://www.ams.org/mathscinet-getitem?mr=#1}{#2}
+}
+\providecommand{\href}[2]{#2}
+\begin{thebibliography}{10}
+
+\bibitem{erdos1947}
+P.~Erd{\H o}s, \emph{Some remarks on the theory of graphs}, Bull. Amer. Math. Soc. \textbf{53} (1947), 292--294.
+
+\bibitem{erdos1959}
+\bysame, \emph{Graph theory and probability}, Canadian J. Math. \textbf{11} (1959), 34--38.
+
+\bibitem{knuth1997}
+D.~E. Knuth, \emph{The art of computer programming}, 3rd ed., vol.~1, Addison-Wesley, Reading, MA, 1997.
+
+\end{thebibliography}
+";
+
+#[test]
+fn test_detect_fixture_styles() {
+    assert_eq!(detect_bbl_style(&load_bbl_fixture("1.bbl")).name(), "generic");
+    assert_eq!(detect_bbl_style(&load_bbl_fixture("2.bbl")).name(), "plainnat");
+    assert_eq!(detect_bbl_style(&load_bbl_fixture("3.bbl")).name(), "IEEEtran");
+    assert_eq!(detect_bbl_style(&load_bbl_fixture("4.bbl")).name(), "ACM-Reference-Format");
+    assert_eq!(detect_bbl_style(APALIKE_BBL).name(), "apalike");
+    assert_eq!(detect_bbl_style(AMSPLAIN_BBL).name(), "amsplain");
+
+    // The header comment takes precedence over fingerprints
+    let abbrvnat = format!("% Generated by abbrvnat.bst\n{}", APALIKE_BBL);
+    assert_eq!(detect_bbl_style(&abbrvnat).name(), "plainnat");
+}
+
+#[test]
+fn test_style_lookup_by_name() {
+    assert_eq!(bbl_style_by_name("IEEEtranN.bst").unwrap().name(), "IEEEtran");
+    assert_eq!(bbl_style_by_name("abbrvnat").unwrap().name(), "plainnat");
+    assert_eq!(bbl_style_by_name("amsalpha").unwrap().name(), "amsplain");
+    assert!(bbl_style_by_name("nonexistent").is_none());
+    assert_eq!(style_parsers().last().unwrap().name(), "generic");
+}
+
+#[test]
+fn test_ieeetran_separates_authors_and_title() {
+    let bibliography = Bibliography::parse_bbl(&load_bbl_fixture("3.bbl")).unwrap();
+
+    let wei = bibliography.get("wei2022chain").unwrap();
+    assert_eq!(wei.get("author").unwrap(), "Wei, J. and Wang, X. and Schuurmans, D. and Bosma, M. and b. ichter and Xia, F. and others");
+    assert_eq!(
+        wei.get("title").unwrap(),
+        "Chain-of-thought prompting elicits reasoning in large language models"
+    );
+    assert_eq!(wei.get("year").unwrap(), "2022");
+
+    let huang = bibliography.get("huang22a").unwrap();
+    assert_eq!(huang.get("author").unwrap(), "Huang, W. and Abbeel, P. and Pathak, D. and Mordatch, I.");
+    assert!(huang.get("raw").unwrap().starts_with("\\bibitem"));
+}
+
+#[test]
+fn test_natbib_titles() {
+    let bibliography = Bibliography::parse_bbl(&load_bbl_fixture("2.bbl")).unwrap();
+
+    let gqa = bibliography.get("gqa2023").unwrap();
+    assert_eq!(
+        gqa.get("title").unwrap(),
+        "Gqa: Training generalized multi-query transformer models from multi-head checkpoints"
+    );
+    assert_eq!(gqa.get("year").unwrap(), "2023");
+    assert!(gqa.get("author").unwrap().starts_with("Ainslie, Joshua and Lee-Thorp, James and de Jong, Michiel and "));
+
+    let falcon = bibliography.get("falcon40b").unwrap();
+    assert_eq!(
+        falcon.get("title").unwrap(),
+        "Falcon-40B: an open large language model with state-of-the-art performance"
+    );
+}
+
+#[test]
+fn test_apalike_and_amsplain() {
+    let bibliography = Bibliography::parse_bbl(APALIKE_BBL).unwrap();
+    let vpt = bibliography.get("vpt").unwrap();
+    assert_eq!(vpt.get("author").unwrap(), "Baker, B. and Akkaya, I. and Clune, J.");
+    assert_eq!(vpt.get("year").unwrap(), "2022");
+    assert_eq!(
+        vpt.get("title").unwrap(),
+        "Video pretraining (VPT): Learning to act by watching unlabeled online videos"
+    );
+
+    let bibliography = Bibliography::parse_bbl(AMSPLAIN_BBL).unwrap();
+    assert_eq!(bibliography.entries.len(), 3);
+    let erdos = bibliography.get("erdos1959").unwrap();
+    // \bysame repeats the authors of the previous item
+    assert_eq!(erdos.get("author").unwrap(), "Erdős, P.");
+    assert_eq!(erdos.get("title").unwrap(), "Graph theory and probability");
+    assert_eq!(erdos.get("year").unwrap(), "1959");
+    let knuth = bibliography.get("knuth1997").unwrap();
+    assert_eq!(knuth.get("title").unwrap(), "The art of computer programming");
+    assert_eq!(knuth.get("year").unwrap(), "1997");
+}
+
+struct KeyOnlyStyle;
+
+impl BblStyleParser for KeyOnlyStyle {
+    fn name(&self) -> &'static str {
+        "key-only"
+    }
+
+    fn detect(&self, _content: &str) -> bool {
+        false
+    }
+
+    fn parse_item(&self, key: &str, _item: &str) -> BibEntry {
+        BibEntry::builder(key, "misc").field("note", "custom").build()
+    }
+}
+
+#[test]
+fn test_parse_with_explicit_style() {
+    let bibliography = Bibliography::parse_bbl_with_style(APALIKE_BBL, &KeyOnlyStyle).unwrap();
+    let entry = bibliography.get("acemoglu2018").unwrap();
+    assert_eq!(entry.entry_type, "misc");
+    assert_eq!(entry.get("note").unwrap(), "custom");
+    assert!(entry.get("raw").is_some());
+
+    // The generic parser still handles every fixture
+    for fixture in ["1.bbl", "2.bbl", "3.bbl", "4.bbl"] {
+        let bibliography = Bibliography::parse_bbl_with_style(&load_bbl_fixture(fixture), &GenericStyle).unwrap();
+        assert!(bibliography.entries.len() >= 4, "{} should parse with the generic style", fixture);
+    }
+}
+
+#[test]
+fn test_venue_and_page_details() {
+    let ieee = Bibliography::parse_bbl(&load_bbl_fixture("3.bbl")).unwrap();
+    let wei = ieee.get("wei2022chain").unwrap();
+    assert_eq!(wei.get("booktitle").unwrap(), "Advances in Neural Information Processing Systems");
+    assert_eq!(wei.get("volume").unwrap(), "35");
+    assert_eq!(wei.get("pages").unwrap(), "24824--24837");
+    let besta = ieee.get("besta2024graph").unwrap();
+    assert_eq!(besta.get("number").unwrap(), "16");
+    let react = ieee.get("2023react").unwrap();
+    assert_eq!(react.get("journal").unwrap(), "arXiv preprint arXiv:2210.03629");
+    assert_eq!(react.get("eprint").unwrap(), "2210.03629");
+    assert_eq!(react.get("archivePrefix").unwrap(), "arXiv");
+
+    let generic = Bibliography::parse_bbl(&load_bbl_fixture("1.bbl")).unwrap();
+    let vpt = generic.get("vpt").unwrap();
+    assert_eq!(vpt.get("booktitle").unwrap(), "Advances in Neural Information Processing Systems");
+    assert_eq!(vpt.get("volume").unwrap(), "35");
+    assert_eq!(vpt.get("pages").unwrap(), "24639--24654");
+    assert_eq!(vpt.get("publisher").unwrap(), "Curran Associates, Inc.");
+    let minedojo = generic.get("minedojo").unwrap();
+    assert_eq!(minedojo.get("volume").unwrap(), "35");
+    assert_eq!(minedojo.get("pages").unwrap(), "18343--18362");
+
+    let natbib = Bibliography::parse_bbl(&load_bbl_fixture("2.bbl")).unwrap();
+    let acemoglu = natbib.get("acemoglu2018artificial").unwrap();
+    assert_eq!(acemoglu.get("booktitle").unwrap(), "The economics of artificial intelligence: An agenda");
+    assert_eq!(acemoglu.get("publisher").unwrap(), "University of Chicago Press");
+    assert_eq!(acemoglu.get("pages").unwrap(), "197--236");
+
+    let acm = Bibliography::parse_bbl(&load_bbl_fixture("4.bbl")).unwrap();
+    let educon = acm.get("8363455").unwrap();
+    assert_eq!(educon.get("booktitle").unwrap(), "2018 IEEE Global Engineering Education Conference (EDUCON)");
+    assert_eq!(educon.get("doi").unwrap(), "10.1109/EDUCON.2018.8363455");
+    assert_eq!(educon.get("url").unwrap(), "https://doi.org/10.1109/EDUCON.2018.8363455");
+    let bucea = acm.get("bucea2021blockchain").unwrap();
+    assert_eq!(bucea.get("journal").unwrap(), "Sustainability");
+    assert_eq!(bucea.get("volume").unwrap(), "13");
+    assert_eq!(bucea.get("number").unwrap(), "22");
+
+    let ams = Bibliography::parse_bbl(AMSPLAIN_BBL).unwrap();
+    let erdos = ams.get("erdos1947").unwrap();
+    assert_eq!(erdos.get("journal").unwrap(), "Bull. Amer. Math. Soc");
+    assert_eq!(erdos.get("volume").unwrap(), "53");
+    assert_eq!(erdos.get("pages").unwrap(), "292--294");
+}
+
+#[test]
+fn test_identifier_commands() {
+    let bbl = r"\begin{thebibliography}{3}
+\providecommand{\natexlab}[1]{#1}
+
+\bibitem[Doe(2021)]{doe2021}
+Jane Doe.
+\newblock A study of things.
+\newblock \emph{Journal of Things}, 12(3):45--67, 2021.
+\newblock \doi{10.1000/jot.2021.12_3}.
+
+\bibitem[Roe(2020)]{roe2020}
+Richard Roe.
+\newblock A website.
+\newblock \url{https://example.com/page}, 2020.
+
+\bibitem[Poe(2019)]{poe2019}
+Edgar Poe.
+\newblock A preprint.
+\newblock \href{https://arxiv.org/abs/1901.00001}{link}, 2019.
+\newblock \eprint{arXiv:1901.00001}.
+
+\end{thebibliography}";
+    let bibliography = Bibliography::parse_bbl(bbl).unwrap();
+
+    let doe = bibliography.get("doe2021").unwrap();
+    assert_eq!(doe.get("journal").unwrap(), "Journal of Things");
+    assert_eq!(doe.get("volume").unwrap(), "12");
+    assert_eq!(doe.get("number").unwrap(), "3");
+    assert_eq!(doe.get("pages").unwrap(), "45--67");
+    assert_eq!(doe.get("doi").unwrap(), "10.1000/jot.2021.12_3");
+
+    let roe = bibliography.get("roe2020").unwrap();
+    assert_eq!(roe.get("url").unwrap(), "https://example.com/page");
+    assert!(roe.get("pages").is_none());
+
+    let poe = bibliography.get("poe2019").unwrap();
+    assert_eq!(poe.get("url").unwrap(), "https://arxiv.org/abs/1901.00001");
+    assert_eq!(poe.get("eprint").unwrap(), "1901.00001");
+    assert_eq!(poe.get("archivePrefix").unwrap(), "arXiv");
+}
+
+#[test]
+fn test_infer_entry_type_from_fixtures() {
+    let generic = Bibliography::parse_bbl(&load_bbl_fixture("1.bbl")).unwrap();
+    assert_eq!(generic.get("vpt").unwrap().entry_type, "inproceedings");
+    assert_eq!(generic.get("babyai_iclr19").unwrap().entry_type, "inproceedings");
+    assert_eq!(generic.get("llama3").unwrap().entry_type, "article");
+    assert_eq!(generic.get("deng2023mind2web").unwrap().entry_type, "misc");
+    assert_eq!(generic.get("innermonologue").unwrap().entry_type, "misc");
+
+    let natbib = Bibliography::parse_bbl(&load_bbl_fixture("2.bbl")).unwrap();
+    assert_eq!(natbib.get("acemoglu2018artificial").unwrap().entry_type, "incollection");
+    assert_eq!(natbib.get("zhuo2023exploring").unwrap().entry_type, "article");
+
+    let acm = Bibliography::parse_bbl(&load_bbl_fixture("4.bbl")).unwrap();
+    assert_eq!(acm.get("8363455").unwrap().entry_type, "inproceedings");
+    assert_eq!(acm.get("bucea2021blockchain").unwrap().entry_type, "article");
+}
+
+#[test]
+fn test_infer_entry_type_cues() {
+    let bbl = r"\begin{thebibliography}{7}
+\providecommand{\natexlab}[1]{#1}
+
+\bibitem[Alpha(2019)]{phd}
+Ann Alpha.
+\newblock \emph{Learning to Plan}.
+\newblock PhD thesis, Stanford University, 2019.
+
+\bibitem[Beta(2018)]{masters}
+Bob Beta.
+\newblock Small models.
+\newblock Master's thesis, University of Oslo, 2018.
+
+\bibitem[Gamma(2017)]{report}
+Carl Gamma.
+\newblock Fast sorting.
+\newblock Technical Report TR-17-04, Example Labs, 2017.
+
+\bibitem[Delta(2020)]{proceedings}
+Dora Delta.
+\newblock Graph search.
+\newblock In Proceedings of the Annual Meeting on Search, pages 1--9, 2020.
+
+\bibitem[Epsilon(2009)]{book}
+Eve Epsilon.
+\newblock The art of parsing.
+\newblock Addison-Wesley, 2009.
+
+\bibitem[Zeta(2023)]{webpage}
+Zed Zeta.
+\newblock Project homepage.
+\newblock \url{https://example.com/project}, 2023.
+
+\bibitem[Eta(2024)]{preprint}
+Ella Eta.
+\newblock A preprint.
+\newblock \eprint{2401.00001}, 2024.
+
+\end{thebibliography}";
+    let bibliography = Bibliography::parse_bbl(bbl).unwrap();
+    let entry_type = |key: &str| bibliography.get(key).unwrap().entry_type.clone();
+    assert_eq!(entry_type("phd"), "phdthesis");
+    assert_eq!(entry_type("masters"), "mastersthesis");
+    assert_eq!(entry_type("report"), "techreport");
+    assert_eq!(entry_type("proceedings"), "inproceedings");
+    assert_eq!(entry_type("book"), "book");
+    assert_eq!(entry_type("webpage"), "misc");
+    assert_eq!(entry_type("preprint"), "misc");
+}
+
+#[test]
+fn test_details_with_non_ascii_sentences() {
+    // Sentences starting with multi-byte characters, such as a curly apostrophe in the
+    // second character or an accented first word
+    let bbl = "\\begin{thebibliography}{1}
+
+\\bibitem{oneill2020}
+O\u{2019}Neill, A. and Smith, B.
+\\newblock A study of regional printing.
+\\newblock \u{141}\u{f3}d\u{17a} University Press, 2020.
+
+\\bibitem{nbsp2021}
+\u{e9}2023 Doe, J.
+\\newblock Another title.
+\\newblock In\u{a0}Proceedings of the Workshop, 2021.
+
+\\end{thebibliography}
+";
+    let bibliography = Bibliography::parse_bbl(bbl).unwrap();
+    let oneill = bibliography.get("oneill2020").unwrap();
+    assert_eq!(oneill.get("publisher").unwrap(), "\u{141}\u{f3}d\u{17a} University Press");
+    assert!(bibliography.get("nbsp2021").is_some());
+}
+
+/// A lab's own style, which ends every item with `[title: ...]`
+struct LabStyle;
+
+impl BblStyleParser for LabStyle {
+    fn name(&self) -> &'static str {
+        "labstyle"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content.contains("% Generated by labstyle.bst")
+    }
+
+    fn parse_item(&self, key: &str, item: &str) -> BibEntry {
+        let title = item.split("[title: ").nth(1).and_then(|rest| rest.split(']').next()).unwrap_or("");
+        BibEntry::builder(key, "misc").field("title", title).build()
+    }
+}
+
+static LAB_STYLE: LabStyle = LabStyle;
+
+#[test]
+fn test_register_bbl_style() {
+    register_bbl_style(&LAB_STYLE);
+    // Registering again replaces the parser rather than adding another
+    register_bbl_style(&LAB_STYLE);
+    assert_eq!(style_parsers().iter().filter(|parser| parser.name() == "labstyle").count(), 1);
+    assert_eq!(style_parsers().last().unwrap().name(), "generic");
+
+    let bbl = "% Generated by labstyle.bst\n\\begin{thebibliography}{1}\n\n\\bibitem{lab2024}\nSomeone. [title: Registered styles]\n\n\\end{thebibliography}\n";
+    assert_eq!(detect_bbl_style(bbl).name(), "labstyle");
+    assert_eq!(bbl_style_by_name("labstyle.bst").unwrap().name(), "labstyle");
+    let bibliography = Bibliography::parse_bbl(bbl).unwrap();
+    assert_eq!(bibliography.get("lab2024").unwrap().get("title").unwrap(), "Registered styles");
+
+    // Other files are still detected as before
+    assert_eq!(detect_bbl_style(APALIKE_BBL).name(), "apalike");
+}
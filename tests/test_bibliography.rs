@@ -1,4 +1,4 @@
-use bibextract::latex::bibliography::{BibEntry, Bibliography};
+use bibextract::latex::bibliography::{BblScope, BblUnit, BibEntry, Bibliography, FieldSource};
 use bibextract::latex::clean_text;
 use std::collections::HashMap;
 use std::fs;
@@ -79,6 +79,7 @@ fn test_normalize_citations_no_key_in_bib() {
 }
 
 #[test]
+#[allow(clippy::useless_conversion)]
 fn test_bib_entry_builder() {
     let entry = BibEntry::builder("key1", "article")
         .field("title", "Test Title")
@@ -205,6 +206,7 @@ fn test_extract_arxiv_id() {
 
 
 #[test]
+#[allow(clippy::unnecessary_get_then_check)]
 fn test_normalize_citations() {
     let mut bib = Bibliography::new();
     let entry1 = BibEntry::builder("oldkey1", "article")
@@ -229,4 +231,215 @@ fn test_normalize_citations() {
     assert_eq!(key_map.get("oldkey1").unwrap(), "one_title_title_2020");
     assert_eq!(key_map.get("oldkey2").unwrap(), "two_title_title_2021");
     assert!(key_map.get("unknownkey").is_none());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_parse_bibliography_sources_prefers_bib() {
+    let dir = tempdir().unwrap();
+    let bbl_file = dir.path().join("main.bbl");
+    fs::write(&bbl_file, r#"\begin{thebibliography}{99}
+\bibitem{lecun2015}
+Yann LeCun.
+\newblock Wrongly parsed title.
+\newblock {\em Nature}, 2015. arXiv:1234.56789
+
+\bibitem{onlybbl}
+Some Author.
+\newblock Only in the bbl.
+\newblock 2019.
+\end{thebibliography}"#).unwrap();
+
+    let bib_database = r#"@article{lecun2015,
+  author = {LeCun, Yann and Bengio, Yoshua and Hinton, Geoffrey},
+  title = {Deep learning},
+  journal = {Nature},
+  year = 2015
+}
+@article{uncited, title = {Never cited}}
+@article{cited_in_text, title = {Cited with natbib}}"#.to_string();
+    let latex = r"\citep[see][p.~3]{cited_in_text} and \cite{lecun2015}";
+
+    let bib = Bibliography::parse_bibliography_sources(&[bib_database], &[bbl_file], latex).unwrap();
+    assert_eq!(bib.entries.len(), 3);
+    assert!(bib.get("uncited").is_none());
+    assert!(bib.get("cited_in_text").is_some());
+
+    let lecun = bib.get("lecun2015").unwrap();
+    assert_eq!(lecun.get("title").unwrap(), "Deep learning");
+    assert_eq!(lecun.source("title"), Some(FieldSource::Bib));
+    // The raw bibitem only exists in the .bbl and fills the gap
+    assert!(lecun.get("raw").unwrap().contains("arXiv:1234.56789"));
+    assert_eq!(lecun.source("raw"), Some(FieldSource::Bbl));
+
+    let only_bbl = bib.get("onlybbl").unwrap();
+    assert_eq!(only_bbl.source("title"), Some(FieldSource::Bbl));
+}
+
+#[test]
+fn test_parse_bibliography_sources_nocite_all() {
+    let bib_database = "@misc{a, title={A}}\n@misc{b, title={B}}".to_string();
+    let bib = Bibliography::parse_bibliography_sources(&[bib_database], &[], r"\nocite{*}").unwrap();
+    assert_eq!(bib.entries.len(), 2);
+
+    // Without .bib databases the .bbl bibliography is returned unchanged
+    let bib = Bibliography::parse_bibliography_sources(&[], &[], r"\cite{a}").unwrap();
+    assert!(bib.entries.is_empty());
+}
+
+#[test]
+fn test_parse_embedded_bibliography() {
+    let latex = r#"\documentclass{article}
+\begin{document}
+\section{Related Work}
+Transformers \cite{vaswani2017} build on attention \cite{bahdanau2015}.
+
+% An old bibliography kept around
+% \begin{thebibliography}{9}
+% \bibitem{stale} Stale Author. Stale title. 2001.
+% \end{thebibliography}
+
+\begin{thebibliography}{10}
+\bibitem{vaswani2017}
+Ashish Vaswani, Noam Shazeer, and Niki Parmar.
+\newblock Attention is all you need.
+\newblock In {\em Advances in Neural Information Processing Systems}, pages 5998--6008, 2017.
+
+\bibitem[Bahdanau et~al.(2015)]%
+        {bahdanau2015}
+Dzmitry Bahdanau, Kyunghyun Cho, and Yoshua Bengio.
+\newblock Neural machine translation by jointly learning to align and translate.
+\newblock In {\em International Conference on Learning Representations}, 2015.
+\end{thebibliography}
+
+\appendix
+\begin{thebibliography}{1}
+\bibitem{vaswani2017} Duplicate key, ignored.
+\bibitem{appendix2020} Ann Author.
+\newblock An appendix reference.
+\newblock 2020.
+\end{thebibliography}
+\end{document}"#;
+
+    let bib = Bibliography::parse_embedded_bibliography(latex).unwrap();
+    assert_eq!(bib.entries.len(), 3);
+    assert!(bib.get("stale").is_none());
+
+    let vaswani = bib.get("vaswani2017").unwrap();
+    assert_eq!(vaswani.get("title").unwrap(), "Attention is all you need");
+    assert_eq!(vaswani.get("year").unwrap(), "2017");
+    assert_eq!(vaswani.entry_type, "inproceedings");
+    assert_eq!(vaswani.source("title"), Some(FieldSource::Bbl));
+
+    let bahdanau = bib.get("bahdanau2015").unwrap();
+    assert!(bahdanau.get("author").unwrap().starts_with("Bahdanau, Dzmitry"));
+    assert!(bib.get("appendix2020").is_some());
+
+    assert!(Bibliography::parse_embedded_bibliography(r"\cite{a}").unwrap().entries.is_empty());
+}
+
+#[test]
+fn test_parse_bibliography_sources_without_bbl() {
+    let dir = tempdir().unwrap();
+    let bbl_file = dir.path().join("main.bbl");
+    fs::write(&bbl_file, r#"\begin{thebibliography}{9}
+\bibitem{shared}
+Bbl Author.
+\newblock Title from the bbl.
+\newblock 2019.
+\end{thebibliography}"#).unwrap();
+
+    let latex = r#"\cite{shared,handwritten}
+\begin{thebibliography}{9}
+\bibitem{shared}
+Tex Author.
+\newblock Title from the source.
+\newblock 2018.
+
+\bibitem{handwritten}
+Hand Writer.
+\newblock Written in main.tex.
+\newblock 2021.
+\end{thebibliography}"#;
+
+    // No .bbl file: the environment in the source is the bibliography
+    let bib = Bibliography::parse_bibliography_sources(&[], &[], latex).unwrap();
+    assert_eq!(bib.entries.len(), 2);
+    assert_eq!(bib.get("handwritten").unwrap().get("title").unwrap(), "Written in main.tex");
+
+    // A compiled .bbl wins for keys it declares
+    let bib = Bibliography::parse_bibliography_sources(&[], &[bbl_file], latex).unwrap();
+    assert_eq!(bib.get("shared").unwrap().get("title").unwrap(), "Title from the bbl");
+    assert!(bib.get("handwritten").is_some());
+}
+
+#[test]
+fn test_parse_bibliography_files_reports_conflicts() {
+    let dir = tempdir().unwrap();
+    let main_bbl = dir.path().join("main.bbl");
+    let supp_bbl = dir.path().join("supp.bbl");
+    fs::write(&main_bbl, r#"\begin{thebibliography}{9}
+\bibitem{smith2020}
+John Smith.
+\newblock Deep learning for parsing.
+\newblock 2020.
+
+\bibitem{shared2019}
+Ann Lee.
+\newblock A shared reference.
+\newblock 2019.
+\end{thebibliography}"#).unwrap();
+    fs::write(&supp_bbl, r#"\begin{thebibliography}{9}
+\bibitem{smith2020}
+Jane Smith.
+\newblock Something else entirely.
+\newblock 2020.
+
+\bibitem{shared2019}
+Ann Lee.
+\newblock A shared reference.
+\newblock 2019.
+
+\bibitem{supponly}
+Bob Brown.
+\newblock Only in the supplement.
+\newblock 2021.
+\end{thebibliography}"#).unwrap();
+
+    let files = vec![main_bbl.clone(), supp_bbl.clone()];
+    let (bib, conflicts) = Bibliography::parse_bibliography_files_with_conflicts(&files).unwrap();
+    assert_eq!(bib.entries.len(), 3);
+    assert_eq!(bib.get("smith2020").unwrap().get("title").unwrap(), "Deep learning for parsing");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].key, "smith2020");
+    assert_eq!(conflicts[0].kept, main_bbl);
+    assert_eq!(conflicts[0].other, supp_bbl);
+    assert_eq!(conflicts[0].renamed, None);
+    assert!(conflicts[0].to_string().contains("smith2020"));
+
+    // The logging variant keeps the same entries
+    let bib = Bibliography::parse_bibliography_files(&files).unwrap();
+    assert_eq!(bib.get("smith2020").unwrap().get("title").unwrap(), "Deep learning for parsing");
+}
+
+#[test]
+fn test_consolidate_bbl_units_keeps_scoped_keys() {
+    let unit = |path: &str, scope: BblScope, title: &str| {
+        let content = format!("\\begin{{thebibliography}}{{9}}\n\\bibitem{{smith2020}}\nJohn Smith.\n\\newblock {}.\n\\newblock 2020.\n\\end{{thebibliography}}", title);
+        BblUnit { path: path.into(), scope, bibliography: Bibliography::parse_bbl(&content).unwrap() }
+    };
+    let units = vec![
+        unit("main.bbl", BblScope::Document, "Deep learning for parsing"),
+        unit("ltr.bbl", BblScope::List("ltr".to_string()), "Further reading on parsing"),
+        unit("refs.bbl", BblScope::Document, "A stale build"),
+    ];
+
+    // The multibib list keeps its entry under its own key; the unscoped file's is ignored
+    let (bib, conflicts) = Bibliography::consolidate_bbl_units(units);
+    assert_eq!(bib.entries.len(), 2);
+    assert_eq!(bib.get("smith2020").unwrap().get("title").unwrap(), "Deep learning for parsing");
+    assert_eq!(bib.get("ltr:smith2020").unwrap().get("title").unwrap(), "Further reading on parsing");
+    assert_eq!(conflicts.len(), 2);
+    let renamed: Vec<Option<&str>> = conflicts.iter().map(|conflict| conflict.renamed.as_deref()).collect();
+    assert!(renamed.contains(&Some("ltr:smith2020")));
+    assert!(renamed.contains(&None));
+}
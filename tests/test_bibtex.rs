@@ -0,0 +1,64 @@
+use bibextract::latex::bibtex::parse_bibtex;
+
+#[test]
+fn test_parse_bibtex_database() {
+    let content = r#"% Generated by hand
+@comment{this is ignored}
+@string{neurips = "Advances in Neural Information Processing Systems"}
+
+@InProceedings{vaswani2017,
+  Author    = {Ashish Vaswani and Noam Shazeer},
+  title     = {Attention is All
+               you Need},
+  booktitle = neurips # " 30",
+  year      = 2017,
+  month     = dec,
+}
+
+Some free text between entries, with an email@example.com in it.
+
+@article(knuth1984,
+  author = "Donald E. Knuth",
+  title = "Literate {"}Programming{"}",
+  journal = {The Computer Journal},
+  pages = {97--111}
+)
+"#;
+    let entries = parse_bibtex(content);
+    assert_eq!(entries.len(), 2);
+
+    let vaswani = &entries[0];
+    assert_eq!(vaswani.key, "vaswani2017");
+    assert_eq!(vaswani.entry_type, "inproceedings");
+    assert_eq!(vaswani.get("author").unwrap(), "Ashish Vaswani and Noam Shazeer");
+    assert_eq!(vaswani.get("title").unwrap(), "Attention is All you Need");
+    assert_eq!(vaswani.get("booktitle").unwrap(), "Advances in Neural Information Processing Systems 30");
+    assert_eq!(vaswani.get("year").unwrap(), "2017");
+    assert_eq!(vaswani.get("month").unwrap(), "December");
+
+    let knuth = &entries[1];
+    assert_eq!(knuth.key, "knuth1984");
+    assert_eq!(knuth.get("title").unwrap(), "Literate {\"}Programming{\"}");
+    assert_eq!(knuth.get("pages").unwrap(), "97--111");
+}
+
+#[test]
+fn test_parse_bibtex_recovers_from_malformed_entries() {
+    let content = r#"@article{broken, title = {Unbalanced
+@misc{ok1, title = {First}}
+@book{ok2, title = {Second}, note = {Nested {braces} and \{escaped\} ones}}"#;
+    let entries = parse_bibtex(content);
+    let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, vec!["ok1", "ok2"]);
+    assert_eq!(entries[1].get("note").unwrap(), r"Nested {braces} and \{escaped\} ones");
+}
+
+#[test]
+fn test_parse_bibtex_empty_and_non_ascii() {
+    assert!(parse_bibtex("").is_empty());
+    assert!(parse_bibtex("no entries here").is_empty());
+
+    let entries = parse_bibtex("@misc{erdos, author = {Paul Erd\\H{o}s and J\\'{a}nos Pach \u{00e9}}, title={Ünïcödé}}");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].get("title").unwrap(), "Ünïcödé");
+}
@@ -0,0 +1,245 @@
+use bibextract::crawl::{arxiv_id_year, crawl, cited_arxiv_ids, CrawlOptions};
+use bibextract::internal::build_survey;
+use mockito::{Matcher, Server, ServerGuard};
+use std::io::Write;
+use tokio::runtime::Runtime;
+
+fn latex_source(cite_keys: &str) -> String {
+    format!(
+        r#"\documentclass{{article}}
+\begin{{document}}
+\section{{Introduction}}
+Not followed \cite{{ignored}}.
+
+\section{{Related Work}}
+Prior work \cite{{{}}}.
+
+\bibliography{{refs}}
+\end{{document}}"#,
+        cite_keys
+    )
+}
+
+fn bbl_source(items: &[(&str, &str, &str)]) -> String {
+    let mut bbl = String::from("\\begin{thebibliography}{99}\n\n");
+    for (key, title, note) in items {
+        bbl.push_str(&format!(
+            "\\bibitem{{{}}}\nSome Author.\n\\newblock {}.\n\\newblock {}, 2020.\n\n",
+            key, title, note
+        ));
+    }
+    bbl.push_str("\\end{thebibliography}");
+    bbl
+}
+
+fn create_tar_gz(paper_id: &str, tex: &str, bbl: &str) -> Vec<u8> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use tar::Builder;
+
+    let mut tar_data = Vec::new();
+    {
+        let mut tar = Builder::new(&mut tar_data);
+        for (name, content) in [("main.tex", tex), ("main.bbl", bbl)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_path(format!("{}/{}", paper_id, name)).unwrap();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append(&header, content.as_bytes()).unwrap();
+        }
+        tar.finish().unwrap();
+    }
+
+    let mut gz_data = Vec::new();
+    {
+        let mut encoder = GzEncoder::new(&mut gz_data, Compression::default());
+        encoder.write_all(&tar_data).unwrap();
+        encoder.finish().unwrap();
+    }
+    gz_data
+}
+
+async fn mock_paper(server: &mut ServerGuard, paper_id: &str, tex: &str, bbl: &str) -> mockito::Mock {
+    server
+        .mock("GET", format!("/bibtex/{}", paper_id).as_str())
+        .with_status(200)
+        .with_body(format!(
+            "@article{{paper{},\n  title={{Paper {}}},\n  author={{Some Author}},\n  year={{2021}}\n}}",
+            paper_id.replace('.', ""),
+            paper_id
+        ))
+        .expect_at_least(0)
+        .create_async()
+        .await;
+    server
+        .mock("GET", format!("/e-print/{}", paper_id).as_str())
+        .with_status(200)
+        .with_header("content-type", "application/gzip")
+        .with_body(create_tar_gz(paper_id, tex, bbl))
+        .expect_at_least(1)
+        .create_async()
+        .await
+}
+
+#[test]
+fn test_arxiv_id_year() {
+    assert_eq!(arxiv_id_year("2104.08653"), Some(2021));
+    assert_eq!(arxiv_id_year("1706.03762"), Some(2017));
+    assert_eq!(arxiv_id_year("hep-th/9901001"), None);
+    assert_eq!(arxiv_id_year("1.5"), None);
+}
+
+#[test]
+fn test_crawl_options_defaults() {
+    let options = CrawlOptions::new(vec!["2104.08653".to_string()]);
+    assert_eq!(options.max_depth, 1);
+    assert_eq!(options.max_papers, 20);
+    assert!(options.categories.is_empty());
+}
+
+#[tokio::test]
+async fn test_crawl_no_seeds() {
+    let result = crawl(&CrawlOptions::new(Vec::new())).await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_crawl_with_mock_arxiv() {
+    let rt = Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+
+        // Seed cites two arXiv papers in its related work and one only in the introduction
+        let seed_tex = latex_source("child2021, old2017");
+        let seed_bbl = bbl_source(&[
+            ("child2021", "A child paper", "arXiv preprint arXiv:2108.07258"),
+            ("old2017", "An old paper", "arXiv preprint arXiv:1706.03762"),
+            ("ignored", "Cited outside related work", "arXiv preprint arXiv:2201.00001"),
+        ]);
+        let seed_mock = mock_paper(&mut server, "2104.08653", &seed_tex, &seed_bbl).await;
+
+        // Child cites the seed back (already visited) and a grandchild
+        let child_tex = latex_source("seed, grandchild");
+        let child_bbl = bbl_source(&[
+            ("seed", "The seed paper", "arXiv preprint arXiv:2104.08653"),
+            ("grandchild", "A grandchild paper", "arXiv preprint arXiv:2203.00002"),
+        ]);
+        let child_mock = mock_paper(&mut server, "2108.07258", &child_tex, &child_bbl).await;
+
+        let grandchild_tex = latex_source("none");
+        let grandchild_bbl = bbl_source(&[]);
+        let grandchild_mock = mock_paper(&mut server, "2203.00002", &grandchild_tex, &grandchild_bbl).await;
+
+        // Cited papers that must never be downloaded still resolve during verification
+        for paper_id in ["1706.03762", "2201.00001"] {
+            server
+                .mock("GET", format!("/bibtex/{}", paper_id).as_str())
+                .with_status(200)
+                .with_body(format!("@article{{p,\n  title={{Paper {}}},\n  author={{Someone}},\n  year={{2017}}\n}}", paper_id))
+                .expect_at_least(0)
+                .create_async()
+                .await;
+        }
+
+        let old_mock = server.mock("GET", "/e-print/1706.03762").with_status(200).expect(0).create_async().await;
+        let ignored_mock = server.mock("GET", "/e-print/2201.00001").with_status(200).expect(0).create_async().await;
+
+        let _categories = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::UrlEncoded("id_list".into(), "2108.07258".into()))
+            .with_status(200)
+            .with_body(r#"<feed><entry><arxiv:primary_category term="cs.CL"/><category term="cs.CL"/><category term="cs.LG"/></entry></feed>"#)
+            .create_async()
+            .await;
+        let _catchall_categories = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"<feed><entry><category term="math.PR"/></entry></feed>"#)
+            .expect_at_least(0)
+            .create_async()
+            .await;
+
+        let _crossref_catchall_mock = server
+            .mock("GET", Matcher::Regex(r"^/works".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message": {"items": []}}"#)
+            .expect_at_least(0)
+            .create_async()
+            .await;
+        let _dblp_catchall_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"result": {"hits": {"@total": "0"}}}"#)
+            .expect_at_least(0)
+            .create_async()
+            .await;
+
+        std::env::set_var("ARXIV_BASE_URL", server.url());
+        std::env::set_var("ARXIV_API_BASE_URL", server.url());
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        std::env::set_var("CROSSREF_BASE_URL", server.url());
+        std::env::set_var("OPENALEX_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "2");
+
+        // Depth 1 with a year filter: the 2017 paper and the grandchild are not downloaded
+        let options = CrawlOptions {
+            min_year: Some(2018),
+            ..CrawlOptions::new(vec!["2104.08653".to_string()])
+        };
+        let papers = crawl(&options).await.expect("crawl should succeed");
+        let ids: Vec<&str> = papers.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["2104.08653", "2108.07258"]);
+        assert_eq!(cited_arxiv_ids(&papers[0]), vec!["2108.07258", "1706.03762"]);
+
+        let (survey_text, _bibtex) = build_survey(&papers).unwrap();
+        assert!(survey_text.contains("% Paper ID: 2104.08653"));
+        assert!(survey_text.contains("% Paper ID: 2108.07258"));
+
+        // Depth 2 follows the grandchild; the seed cited back is not downloaded twice
+        let options = CrawlOptions {
+            max_depth: 2,
+            min_year: Some(2018),
+            ..CrawlOptions::new(vec!["2104.08653".to_string(), "2104.08653".to_string()])
+        };
+        let papers = crawl(&options).await.expect("crawl should succeed");
+        assert_eq!(papers.len(), 3);
+        assert_eq!(papers[2].id, "2203.00002");
+
+        // The paper budget stops the crawl after the seed
+        let options = CrawlOptions {
+            max_depth: 2,
+            max_papers: 1,
+            ..CrawlOptions::new(vec!["2104.08653".to_string()])
+        };
+        let papers = crawl(&options).await.expect("crawl should succeed");
+        assert_eq!(papers.len(), 1);
+
+        // The category filter only keeps the cs.CL child
+        let options = CrawlOptions {
+            categories: vec!["cs.CL".to_string()],
+            ..CrawlOptions::new(vec!["2104.08653".to_string()])
+        };
+        let papers = crawl(&options).await.expect("crawl should succeed");
+        let ids: Vec<&str> = papers.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["2104.08653", "2108.07258"]);
+
+        seed_mock.assert_async().await;
+        child_mock.assert_async().await;
+        grandchild_mock.assert_async().await;
+        old_mock.assert_async().await;
+        ignored_mock.assert_async().await;
+
+        std::env::remove_var("ARXIV_BASE_URL");
+        std::env::remove_var("ARXIV_API_BASE_URL");
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("CROSSREF_BASE_URL");
+        std::env::remove_var("OPENALEX_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
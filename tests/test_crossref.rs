@@ -0,0 +1,166 @@
+mod common;
+
+use bibextract::latex::crossref::crossref_work_to_entry;
+use bibextract::latex::{Bibliography, CrossrefProvider, MetadataProvider, Verifier};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use tokio::runtime::Runtime;
+
+use common::entry_with;
+
+#[test]
+fn test_extract_doi() {
+    let bib = Bibliography::new();
+    assert_eq!(bib.extract_doi(&entry_with(&[("doi", "10.1000/xyz123")])).as_deref(), Some("10.1000/xyz123"));
+    assert_eq!(
+        bib.extract_doi(&entry_with(&[("url", "https://doi.org/10.1103/PhysRev.47.777")])).as_deref(),
+        Some("10.1103/PhysRev.47.777")
+    );
+    assert_eq!(
+        bib.extract_doi(&entry_with(&[("raw", "J. Smith. A title. KDD, 2019. doi:10.1145/3292500.3330701.")])).as_deref(),
+        Some("10.1145/3292500.3330701")
+    );
+    assert_eq!(bib.extract_doi(&entry_with(&[("raw", "J. Smith. A title, version 10.2, 2019.")])), None);
+
+    // TeX escapes are not part of the DOI
+    assert_eq!(
+        bib.extract_doi(&entry_with(&[("raw", "doi:10.1002/(SICI)1097-4636(199603)31:3\\_{\\_}x.")])).as_deref(),
+        Some("10.1002/(SICI)1097-4636(199603)31:3__x")
+    );
+}
+
+#[test]
+fn test_crossref_work_to_entry() {
+    let work = json!({
+        "DOI": "10.1103/PhysRev.47.777",
+        "URL": "http://dx.doi.org/10.1103/PhysRev.47.777",
+        "type": "journal-article",
+        "title": ["Can Quantum-Mechanical Description of Physical Reality Be Considered Complete?"],
+        "author": [
+            {"given": "A.", "family": "Einstein"},
+            {"given": "B.", "family": "Podolsky"},
+            {"name": "The Example Collaboration"}
+        ],
+        "container-title": ["Physical Review"],
+        "issued": {"date-parts": [[1935, 5, 15]]},
+        "volume": "47",
+        "issue": "10",
+        "page": "777-780",
+        "publisher": "American Physical Society (APS)"
+    });
+    let entry = crossref_work_to_entry(&work, "epr").unwrap();
+    assert_eq!(entry.key, "epr");
+    assert_eq!(entry.entry_type, "article");
+    assert_eq!(entry.get("author").unwrap(), "Einstein, A. and Podolsky, B. and {The Example Collaboration}");
+    assert_eq!(entry.get("journal").unwrap(), "Physical Review");
+    assert_eq!(entry.get("year").unwrap(), "1935");
+    assert_eq!(entry.get("number").unwrap(), "10");
+    assert_eq!(entry.get("pages").unwrap(), "777--780");
+    assert_eq!(entry.get("doi").unwrap(), "10.1103/PhysRev.47.777");
+
+    let chapter = json!({"type": "book-chapter", "title": ["A chapter"], "container-title": ["A Book"]});
+    let entry = crossref_work_to_entry(&chapter, "chapter").unwrap();
+    assert_eq!(entry.entry_type, "incollection");
+    assert_eq!(entry.get("booktitle").unwrap(), "A Book");
+    assert!(entry.get("year").is_none());
+
+    assert!(crossref_work_to_entry(&json!({"type": "journal-article", "title": []}), "untitled").is_none());
+}
+
+#[test]
+fn test_crossref_provider_lookup_and_search() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let _doi_mock = server
+            .mock("GET", "/works/10.1000%2Fxyz123")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "ok", "message": {
+                "DOI": "10.1000/xyz123", "type": "proceedings-article",
+                "title": ["Resolved by DOI"], "author": [{"given": "Jane", "family": "Doe"}],
+                "container-title": ["Proceedings of Things"], "issued": {"date-parts": [[2021]]}
+            }}"#)
+            .create_async()
+            .await;
+        let _unknown_doi_mock = server
+            .mock("GET", "/works/10.1000%2Funknown")
+            .with_status(404)
+            .create_async()
+            .await;
+        // Rejected requests are not retried
+        let rejected_doi_mock = server
+            .mock("GET", "/works/10.1000%2Frejected")
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+        let search_mock = server
+            .mock("GET", "/works")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("query.bibliographic".into(), "Curie Recherches sur les substances radioactives 1903".into()),
+                Matcher::UrlEncoded("rows".into(), "5".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "ok", "message": {"items": [
+                {"type": "journal-article", "title": ["Something else entirely"], "author": [{"given": "A.", "family": "Other"}],
+                 "issued": {"date-parts": [[1950]]}},
+                {"type": "dissertation", "title": ["Recherches sur les substances radioactives"], "DOI": "10.1000/curie",
+                 "author": [{"given": "Marie", "family": "Curie"}], "issued": {"date-parts": [[1903]]}}
+            ]}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        // Escaped in the entry, and a single path segment in the request
+        let escaped_doi_mock = server
+            .mock("GET", "/works/10.1002%2F(SICI)1097-4636_x")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status": "ok", "message": {"type": "journal-article", "title": ["Escaped"]}}"#)
+            .create_async()
+            .await;
+        std::env::set_var("CROSSREF_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let entry = entry_with(&[("raw", "J. Doe. Some talk. doi:10.1000/xyz123"), ("title", "Some talk")]);
+        let candidate = CrossrefProvider.lookup_id(&entry).await.unwrap().unwrap();
+        assert_eq!(candidate.score, 1.0);
+        assert_eq!(candidate.entry.entry_type, "inproceedings");
+        assert_eq!(candidate.entry.get("title").unwrap(), "Resolved by DOI");
+        assert_eq!(candidate.entry.get("booktitle").unwrap(), "Proceedings of Things");
+
+        let entry = entry_with(&[("doi", "10.1002/(SICI)1097-4636\\_x")]);
+        let candidate = CrossrefProvider.lookup_id(&entry).await.unwrap().unwrap();
+        assert_eq!(candidate.entry.get("title").unwrap(), "Escaped");
+        escaped_doi_mock.assert_async().await;
+
+        let entry = entry_with(&[("doi", "10.1000/unknown")]);
+        assert!(CrossrefProvider.lookup_id(&entry).await.unwrap().is_none());
+
+        let entry = entry_with(&[("doi", "10.1000/rejected")]);
+        assert!(CrossrefProvider.lookup_id(&entry).await.unwrap().is_none());
+        rejected_doi_mock.assert_async().await;
+
+        // Without a DOI, the entry is found by a bibliographic search
+        let mut entry = entry_with(&[
+            ("author", "Curie, Marie"),
+            ("title", "Recherches sur les substances radioactives"),
+            ("year", "1903"),
+        ]);
+        assert!(CrossrefProvider.lookup_id(&entry).await.unwrap().is_none());
+        let candidates = CrossrefProvider.search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].score, 1.0);
+
+        let verifier = Verifier::new().with_provider(CrossrefProvider);
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.entry_type, "phdthesis");
+        assert_eq!(entry.get("doi").unwrap(), "10.1000/curie");
+        assert_eq!(entry.get("verified_source").unwrap(), "Crossref");
+        search_mock.assert_async().await;
+
+        std::env::remove_var("CROSSREF_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use bibextract::latex::dblp_dump::parse_dblp_xml;
+use bibextract::latex::{DblpDumpProvider, DblpIndex, DblpProvider, DblpRecord, MetadataProvider, Verifier};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use mockito::{Matcher, Server};
+use std::io::{BufReader, Write};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use common::{entry_with, fixture_path};
+
+#[test]
+fn test_parse_dblp_xml() {
+    let file = std::fs::File::open(fixture_path("dblp_sample.xml")).unwrap();
+    let mut records = Vec::new();
+    let count = parse_dblp_xml(BufReader::new(file), |record| records.push(record)).unwrap();
+    // Person pages are not publications
+    assert_eq!(count, 4);
+
+    let attention = &records[0];
+    assert_eq!(attention.key, "conf/nips/VaswaniSPUJGKP17");
+    assert_eq!(attention.record_type, "Conference and Workshop Papers");
+    assert_eq!(attention.title, "Attention is All you Need.");
+    assert_eq!(attention.authors, vec!["Ashish Vaswani", "Noam Shazeer", "Niki Parmar"]);
+    assert_eq!(attention.venue.as_deref(), Some("NIPS"));
+    assert!(attention.doi.is_none());
+
+    assert_eq!(records[1].record_type, "Informal and Other Publications");
+    assert_eq!(records[1].volume.as_deref(), Some("abs/1706.03762"));
+
+    // Entities, markup and a title spanning two lines
+    let tacl = &records[2];
+    assert_eq!(tacl.title, "Learning k-Nearest Neighbours for Low-Resource Translation & Parsing.");
+    assert_eq!(tacl.authors, vec!["Jörg Müller 0002", "Ana Ramírez"]);
+    assert_eq!(tacl.doi.as_deref(), Some("10.1162/tacl_a_00001"));
+    assert_eq!(tacl.record_type, "Journal Articles");
+
+    // Proceedings volumes list their editors
+    assert_eq!(records[3].record_type, "Editorship");
+    assert_eq!(records[3].authors, vec!["Isabelle Guyon"]);
+}
+
+#[test]
+fn test_dblp_index_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let dump = dir.path().join("dblp.xml.gz");
+    let mut encoder = GzEncoder::new(std::fs::File::create(&dump).unwrap(), Compression::default());
+    encoder.write_all(&std::fs::read(fixture_path("dblp_sample.xml")).unwrap()).unwrap();
+    encoder.finish().unwrap();
+
+    // The first open builds the index file, later ones read it
+    let index = DblpIndex::open(&dump).unwrap();
+    assert_eq!(index.len(), 4);
+    let index_path = DblpIndex::index_path(&dump);
+    assert_eq!(index_path, dir.path().join("dblp.xml.gz.index.jsonl"));
+    assert!(index_path.exists());
+    let reopened = DblpIndex::open(&dump).unwrap();
+    assert_eq!(reopened.len(), 4);
+
+    let saved = dir.path().join("saved.jsonl");
+    index.save(&saved).unwrap();
+    let loaded = DblpIndex::load(&saved).unwrap();
+    let entry = entry_with(&[("author", "Müller, Jörg"), ("title", "Learning k-nearest neighbours for low-resource translation and parsing")]);
+    assert_eq!(loaded.candidates(&entry), index.candidates(&entry));
+    assert_eq!(loaded.candidates(&entry)[0].key, "journals/tacl/MullerR19");
+
+    std::fs::write(&saved, "not an index\n").unwrap();
+    assert!(DblpIndex::load(&saved).is_err());
+}
+
+#[test]
+fn test_dblp_dump_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let provider = DblpDumpProvider::new(Arc::new(DblpIndex::from_xml(&fixture_path("dblp_sample.xml")).unwrap()));
+        let mut entry = entry_with(&[
+            ("author", "M{\\\"u}ller, J. and Ram{\\'i}rez, A."),
+            ("title", "Learning {k}-Nearest Neighbours for Low-Resource Translation \\& Parsing"),
+            ("year", "2019"),
+        ]);
+        let verifier = Verifier::new().with_provider(provider.clone());
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.entry_type, "article");
+        assert_eq!(entry.get("journal").unwrap(), "Trans. Assoc. Comput. Linguistics");
+        assert_eq!(entry.get("doi").unwrap(), "10.1162/tacl_a_00001");
+        assert_eq!(entry.get("url").unwrap(), "https://dblp.org/rec/journals/tacl/MullerR19");
+        assert_eq!(entry.get("author").unwrap(), "Müller, Jörg and Ramírez, Ana");
+        assert_eq!(entry.get("verified_source").unwrap(), "DBLP");
+
+        let unknown = entry_with(&[("author", "Doe, J."), ("title", "Something else entirely"), ("year", "2019")]);
+        assert!(provider.search(&unknown).await.unwrap().is_empty());
+
+        // The dump takes the place of the online provider
+        let mut verifier = Verifier::default();
+        verifier.replace("DBLP", Arc::new(provider));
+        assert_eq!(verifier.provider_names(), vec!["arXiv", "DBLP", "Crossref", "OpenAlex", "arXiv search"]);
+    });
+}
+
+#[test]
+fn test_dblp_dump_matches_online_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let index = DblpIndex::from_xml(&fixture_path("dblp_sample.xml")).unwrap();
+        let entry = entry_with(&[("author", "Vaswani, Ashish and Shazeer, Noam"), ("title", "Attention is all you need"), ("year", "2017")]);
+
+        // The API answers with the same records the dump holds
+        let hits: Vec<_> = index
+            .candidates(&entry)
+            .into_iter()
+            .map(|record| serde_json::json!({"info": record.to_api_info()}))
+            .collect();
+        assert_eq!(hits.len(), 2);
+        let body = serde_json::json!({"result": {"hits": {"@total": "2", "hit": hits}}});
+
+        let mut server = Server::new_async().await;
+        let _dblp_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::UrlEncoded("format".into(), "json".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let online = DblpProvider::new().search(&entry).await.unwrap();
+        let offline = DblpDumpProvider::new(Arc::new(index)).search(&entry).await.unwrap();
+        assert!(!offline.is_empty());
+        assert_eq!(online.len(), offline.len());
+        for (online, offline) in online.iter().zip(&offline) {
+            assert_eq!(online.entry.entry_type, offline.entry.entry_type);
+            assert_eq!(online.entry.fields, offline.entry.fields);
+            assert_eq!(online.score, offline.score);
+        }
+        assert_eq!(offline[0].entry.get("booktitle").unwrap(), "NIPS");
+
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
+
+#[test]
+fn test_candidates_for_common_last_names() {
+    let record = |key: String, title: String| DblpRecord {
+        key,
+        record_type: "Journal Articles".to_string(),
+        title,
+        authors: vec!["Wei Wang 0001".to_string()],
+        year: "2020".to_string(),
+        venue: None,
+        volume: None,
+        doi: None,
+    };
+    let mut records = Vec::new();
+    for i in 0..150 {
+        // Only stopwords and one word in common: not a candidate
+        records.push(record(format!("noise/{}", i), format!("Deep models for the analysis of images {}.", i)));
+        // Three words in common, among many others
+        records.push(record(format!("similar/{}", i), format!("Sparse graph estimation in remote sensing imagery, part {}.", i)));
+    }
+    records.push(record("true".to_string(), "Sparse Graph Estimation for Analysing Networks.".to_string()));
+    let index = DblpIndex::from_records(records);
+
+    let entry = entry_with(&[
+        ("author", "Wang, W."),
+        ("title", "Sparse graph estimation for the analysis of networks"),
+    ]);
+    let candidates = index.candidates(&entry);
+    assert_eq!(candidates.len(), 100);
+    assert_eq!(candidates[0].key, "true");
+    assert!(candidates.iter().all(|candidate| !candidate.key.starts_with("noise/")));
+}
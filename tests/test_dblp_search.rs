@@ -0,0 +1,134 @@
+mod common;
+
+use bibextract::latex::verification::dblp_queries;
+use bibextract::latex::{MetadataProvider, DblpProvider};
+use mockito::{Matcher, Server, ServerGuard};
+use tokio::runtime::Runtime;
+
+use common::entry_with;
+
+fn search_results(title: &str, year: &str, author: &str) -> String {
+    serde_json::json!({"result": {"hits": {"@total": "1", "hit": [{"info": {
+        "title": title,
+        "authors": {"author": [{"text": author}]},
+        "venue": "NIPS",
+        "year": year,
+        "type": "Conference and Workshop Papers"
+    }}]}}})
+    .to_string()
+}
+
+async fn mock_query(server: &mut ServerGuard, query: &str, status: usize, body: &str, hits: usize) -> mockito::Mock {
+    server
+        .mock("GET", "/search/publ/api")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("q".into(), query.into()),
+            Matcher::UrlEncoded("format".into(), "json".into()),
+        ]))
+        .with_status(status)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+#[test]
+fn test_dblp_queries() {
+    let entry = entry_with(&[
+        ("author", "Vaswani, Ashish and Shazeer, Noam"),
+        ("title", "Attention is {All} you need"),
+    ]);
+    assert_eq!(
+        dblp_queries(&entry),
+        vec!["Attention is All you need", "attention all you need", "attention all you need vaswani"]
+    );
+
+    // Query operators and math are left out
+    let entry = entry_with(&[
+        ("author", "M{\\\"u}ller, Klaus"),
+        ("title", "Learning $\\alpha$-Divergences for Q\\&A: Who? #1*"),
+    ]);
+    assert_eq!(
+        dblp_queries(&entry),
+        vec![
+            "Learning alpha-Divergences for Q&A: Who? #1",
+            "learning divergences q who 1",
+            "learning divergences q who muller",
+        ]
+    );
+
+    // Nothing to add for short titles without stopwords or authors
+    assert_eq!(dblp_queries(&entry_with(&[("title", "Transformers")])), vec!["Transformers", "transformers"]);
+    assert!(dblp_queries(&entry_with(&[("author", "Doe, J.")])).is_empty());
+}
+
+#[test]
+fn test_dblp_search_strategies() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "1");
+        let empty = r#"{"result": {"hits": {"@total": "0"}}}"#;
+
+        // Titles with URL syntax are sent encoded
+        let entry = entry_with(&[("author", "Doe, Jane"), ("title", "Who needs Q\\&A? #1"), ("year", "2020")]);
+        let encoded = mock_query(&mut server, "Who needs Q&A? #1", 200, &search_results("Who needs Q&A? #1.", "2020", "Jane Doe"), 1).await;
+        let candidates = DblpProvider::new().search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].score, 1.0);
+        encoded.assert_async().await;
+
+        // A title DBLP spells differently is found by its words, and the search stops there
+        let entry = entry_with(&[
+            ("author", "Vaswani, Ashish"),
+            ("title", "Attention is All you Need!!"),
+            ("year", "2017"),
+        ]);
+        let full = mock_query(&mut server, "Attention is All you Need!!", 200, empty, 1).await;
+        let words = mock_query(&mut server, "attention all you need", 200, &search_results("Attention is All you Need.", "2017", "Ashish Vaswani"), 1).await;
+        let prefix = mock_query(&mut server, "attention all you need vaswani", 200, empty, 0).await;
+        let candidates = DblpProvider::new().search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry.get("booktitle").unwrap(), "NIPS");
+        full.assert_async().await;
+        words.assert_async().await;
+        prefix.assert_async().await;
+
+        // Weak matches are kept while the other queries look for better ones
+        let entry = entry_with(&[
+            ("author", "Bahdanau, Dzmitry"),
+            ("title", "Neural machine translation by jointly learning to align and translate"),
+            ("year", "2014"),
+        ]);
+        let full = mock_query(&mut server, "Neural machine translation by jointly learning to align and translate", 200,
+            &search_results("Neural machine translation by jointly learning to align and to translate", "2016", "Someone Else"), 1).await;
+        let words = mock_query(&mut server, "neural machine translation jointly learning align translate", 200, empty, 1).await;
+        let prefix = mock_query(&mut server, "neural machine translation jointly bahdanau", 200,
+            &search_results("Neural Machine Translation by Jointly Learning to Align and Translate", "2015", "Dzmitry Bahdanau"), 1).await;
+        let candidates = DblpProvider::new().search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry.get("year").unwrap(), "2015");
+        full.assert_async().await;
+        words.assert_async().await;
+        prefix.assert_async().await;
+
+        // When DBLP fails, the other queries are not tried
+        let entry = entry_with(&[("author", "Roe, Jane"), ("title", "An unavailable service"), ("year", "2021")]);
+        let full = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::UrlEncoded("q".into(), "An unavailable service".into()))
+            .with_status(500)
+            .expect_at_least(1)
+            .create_async()
+            .await;
+        let words = mock_query(&mut server, "unavailable service", 200, empty, 0).await;
+        assert!(DblpProvider::new().search(&entry).await.unwrap().is_empty());
+        full.assert_async().await;
+        words.assert_async().await;
+
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
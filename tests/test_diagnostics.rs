@@ -0,0 +1,106 @@
+use bibextract::latex::{BibEntry, Bibliography, LowConfidence, ParseWarning};
+use std::fs;
+use std::path::Path;
+
+fn load_bbl_fixture(file_name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(file_name);
+    fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read {}", file_name))
+}
+
+const NATBIB_BBL: &str = r"\begin{thebibliography}{3}
+\providecommand{\natexlab}[1]{#1}
+
+\bibitem[Clean(2020)]{clean}
+Ann Clean and Bob Tidy.
+\newblock A well formed entry.
+\newblock \emph{Journal of Order}, 3(1):1--10, 2020.
+
+\bibitem[Venue]{venue}
+Carl Venue.
+\newblock In Proceedings of the Conference on Things.
+
+\bibitem[Bare]{bare}
+\newblock
+
+\end{thebibliography}";
+
+const GUESSED_AUTHOR_BBL: &str = r"\begin{thebibliography}{2}
+
+\bibitem{consortium}
+The Example Consortium of many places.
+\newblock A report on things.
+\newblock 2021.
+
+\bibitem{doe}
+Jane Doe and John Roe.
+\newblock A paper on other things.
+\newblock {\em Journal of Things}, 2(1):3--4, 2020.
+
+\end{thebibliography}";
+
+#[test]
+fn test_entry_warnings_and_confidence() {
+    let bibliography = Bibliography::parse_bbl(NATBIB_BBL).unwrap();
+
+    let clean = bibliography.get("clean").unwrap();
+    assert!(clean.warnings.is_empty());
+    assert_eq!(clean.confidence(), 1.0);
+
+    let venue = bibliography.get("venue").unwrap();
+    assert_eq!(venue.warnings, vec![ParseWarning::TitleLooksLikeVenue, ParseWarning::MissingYear]);
+    assert!((venue.confidence() - 0.45).abs() < 1e-6);
+
+    let bare = bibliography.get("bare").unwrap();
+    assert!(bare.warnings.contains(&ParseWarning::MissingTitle));
+    assert!(bare.warnings.contains(&ParseWarning::MissingAuthor));
+    assert!(bare.warnings.contains(&ParseWarning::MissingYear));
+    assert!(bare.confidence() < 0.1);
+
+    // Entries built by hand carry no warnings
+    assert_eq!(BibEntry::builder("key", "article").build().confidence(), 1.0);
+}
+
+#[test]
+fn test_parse_summary_for_fixtures() {
+    let natbib = Bibliography::parse_bbl(&load_bbl_fixture("3.bbl")).unwrap();
+    let summary = natbib.parse_summary();
+    assert_eq!(summary.total, 31);
+    assert_eq!(summary.clean, 31);
+    assert!(summary.warnings.is_empty());
+
+    // The generic parser finds the author lists by their names
+    let generic = Bibliography::parse_bbl(&load_bbl_fixture("1.bbl")).unwrap();
+    let summary = generic.parse_summary();
+    assert_eq!(summary.clean, 8);
+    assert_eq!(summary.to_string(), "8/8 entries parsed cleanly (mean confidence 1.00)");
+
+    // A first line that does not read as names is taken as the authors by position
+    let guessed = Bibliography::parse_bbl(GUESSED_AUTHOR_BBL).unwrap();
+    let summary = guessed.parse_summary();
+    assert_eq!(summary.warnings.get(&ParseWarning::AuthorGuessed), Some(&1));
+    assert_eq!(
+        summary.to_string(),
+        "1/2 entries parsed cleanly (mean confidence 0.90): 1 author line guessed"
+    );
+
+    let biblatex = Bibliography::parse_bbl(&load_bbl_fixture("5.bbl")).unwrap();
+    let summary = biblatex.parse_summary();
+    assert_eq!(summary.warnings.get(&ParseWarning::MissingAuthor), Some(&1));
+}
+
+#[test]
+fn test_confidence_threshold() {
+    let mut flagged = Bibliography::parse_bbl(NATBIB_BBL).unwrap();
+    assert_eq!(flagged.apply_confidence_threshold(0.5, LowConfidence::Flag), 2);
+    assert_eq!(flagged.entries.len(), 3);
+    assert!(flagged.get("clean").unwrap().get("parse_warnings").is_none());
+    assert_eq!(
+        flagged.get("venue").unwrap().get("parse_warnings").unwrap(),
+        "confidence 0.45: title looks like a venue; year not found"
+    );
+
+    let mut dropped = Bibliography::parse_bbl(NATBIB_BBL).unwrap();
+    assert_eq!(dropped.apply_confidence_threshold(0.5, LowConfidence::Drop), 2);
+    let keys: Vec<&str> = dropped.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(keys, vec!["clean"]);
+}
@@ -0,0 +1,127 @@
+use bibextract::latex::graph::NodeKind;
+use bibextract::latex::{ArxivPaper, BibEntry, Bibliography, CitationGraph, ExtractedSection, GraphFormat};
+
+fn make_paper(id: &str, title: &str, sections: Vec<ExtractedSection>, entries: Vec<BibEntry>) -> ArxivPaper {
+    let mut bibliography = Bibliography::new();
+    for entry in entries {
+        bibliography.insert(entry);
+    }
+    ArxivPaper {
+        id: id.to_string(),
+        title: title.to_string(),
+        authors: "Test Author".to_string(),
+        sections,
+        bibliography,
+        _temp_dir: tempfile::TempDir::new().unwrap(),
+    }
+}
+
+fn section(title: &str, citations: &[&str]) -> ExtractedSection {
+    ExtractedSection {
+        title: title.to_string(),
+        content: String::new(),
+        citations: citations.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+fn sample_papers() -> Vec<ArxivPaper> {
+    let vaswani = BibEntry::builder("vaswani2017", "inproceedings")
+        .field("author", "Ashish Vaswani")
+        .field("title", "Attention is all you need")
+        .field("year", "2017")
+        .field("doi", "10.5555/3295222")
+        .build();
+    let other_paper = BibEntry::builder("smith2020", "article")
+        .field("author", "Jane Smith")
+        .field("title", "Second input paper")
+        .field("year", "2020")
+        .field("journal", "arXiv preprint arXiv:2001.00001")
+        .build();
+    let paper_a = make_paper(
+        "2104.08653",
+        "First Paper",
+        vec![
+            section("Related Work", &["vaswani2017", "smith2020"]),
+            section("Background", &["vaswani2017", "missing"]),
+        ],
+        vec![vaswani, other_paper],
+    );
+
+    let vaswani_again = BibEntry::builder("attention", "article")
+        .field("author", "Ashish Vaswani")
+        .field("title", "Attention is all you need")
+        .field("year", "2017")
+        .build();
+    let paper_b = make_paper(
+        "2001.00001",
+        "Second <Paper> & \"Friends\"",
+        vec![section("Related Work", &["attention", "missing"])],
+        vec![vaswani_again],
+    );
+
+    vec![paper_a, paper_b]
+}
+
+#[test]
+fn test_graph_nodes_and_edges() {
+    let graph = CitationGraph::from_papers(&sample_papers());
+
+    // Two papers, one shared cited work, and an unresolved key per paper citing it
+    assert_eq!(graph.nodes.iter().filter(|n| n.kind == NodeKind::Paper).count(), 2);
+    assert_eq!(graph.nodes.iter().filter(|n| n.kind == NodeKind::Work).count(), 3);
+
+    let work = graph.node("vaswani_attention_need_2017").expect("shared work node");
+    assert_eq!(work.attributes.get("doi").unwrap(), "10.5555/3295222");
+
+    // Same work cited from two sections of the first paper and once from the second
+    let edges_to_work: Vec<_> = graph.edges.iter().filter(|e| e.target == "vaswani_attention_need_2017").collect();
+    assert_eq!(edges_to_work.len(), 3);
+    assert!(edges_to_work.iter().any(|e| e.source == "2104.08653" && e.section == "Background"));
+    assert!(edges_to_work.iter().any(|e| e.source == "2001.00001" && e.section == "Related Work"));
+
+    // A cited arXiv paper that is also an input links to the paper node
+    assert!(graph.edges.iter().any(|e| e.source == "2104.08653" && e.target == "2001.00001"));
+
+    let missing = graph.node("2104.08653:missing").expect("unresolved node");
+    assert_eq!(missing.label, "missing");
+    assert_eq!(missing.attributes.get("unresolved").unwrap(), "true");
+    // The same key in another paper may be another work
+    assert!(graph.node("2001.00001:missing").is_some());
+    assert!(graph.node("missing").is_none());
+}
+
+#[test]
+fn test_graph_json_export() {
+    let graph = CitationGraph::from_papers(&sample_papers());
+    let json = graph.to_json();
+    assert_eq!(json["nodes"].as_array().unwrap().len(), graph.nodes.len());
+    assert_eq!(json["edges"].as_array().unwrap().len(), graph.edges.len());
+    assert_eq!(json["nodes"][0]["kind"], "paper");
+
+    let parsed: serde_json::Value = serde_json::from_str(&graph.export(GraphFormat::Json)).unwrap();
+    assert_eq!(parsed, json);
+}
+
+#[test]
+fn test_graph_graphml_and_dot_export() {
+    let graph = CitationGraph::from_papers(&sample_papers());
+
+    let graphml = graph.export(GraphFormat::GraphMl);
+    assert!(graphml.starts_with("<?xml"));
+    assert!(graphml.contains("<key id=\"doi\" for=\"node\""));
+    assert!(graphml.contains("Second &lt;Paper&gt; &amp; &quot;Friends&quot;"));
+    assert!(graphml.contains("<data key=\"section\">Background</data>"));
+
+    let dot = graph.export(GraphFormat::Dot);
+    assert!(dot.starts_with("digraph citations {"));
+    assert!(dot.contains("\"2104.08653\" -> \"vaswani_attention_need_2017\" [label=\"Related Work\"];"));
+    assert!(dot.contains("Second <Paper> & \\\"Friends\\\""));
+}
+
+#[test]
+fn test_graph_format_from_str() {
+    assert_eq!("json".parse::<GraphFormat>().unwrap(), GraphFormat::Json);
+    assert_eq!("GraphML".parse::<GraphFormat>().unwrap(), GraphFormat::GraphMl);
+    assert_eq!("gv".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+    assert!("csv".parse::<GraphFormat>().unwrap_err().contains("unknown graph format 'csv'"));
+}
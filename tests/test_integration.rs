@@ -84,6 +84,7 @@ fn create_mock_tar_gz() -> Vec<u8> {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_full_pipeline_with_mocked_apis() {
     // Create a single runtime for the entire test
     let rt = Runtime::new().unwrap();
@@ -262,9 +263,32 @@ fn test_full_pipeline_with_mocked_apis() {
             .create_async()
             .await;
         
+        // Crossref and OpenAlex find nothing, so the DBLP matches are kept
+        let _crossref_catchall_mock = server
+            .mock("GET", Matcher::Regex(r"^/works".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message": {"items": []}}"#)
+            .expect_at_least(0)
+            .create_async()
+            .await;
+        
+        // Nor does the arXiv search for entries without an arXiv ID
+        let _arxiv_search_catchall_mock = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"<feed xmlns="http://www.w3.org/2005/Atom"></feed>"#)
+            .expect_at_least(0)
+            .create_async()
+            .await;
+        
         // Override the base URLs for testing
         std::env::set_var("ARXIV_BASE_URL", &server.url());
+        std::env::set_var("ARXIV_API_BASE_URL", server.url());
         std::env::set_var("DBLP_BASE_URL", &server.url());
+        std::env::set_var("CROSSREF_BASE_URL", server.url());
+        std::env::set_var("OPENALEX_BASE_URL", server.url());
         std::env::set_var("API_TIMEOUT_SECS", "10"); // Shorter timeout for faster tests
         
         // Execute the full pipeline in an async context
@@ -295,12 +319,16 @@ fn test_full_pipeline_with_mocked_apis() {
         
         // Clean up environment variables
         std::env::remove_var("ARXIV_BASE_URL");
+        std::env::remove_var("ARXIV_API_BASE_URL");
         std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("CROSSREF_BASE_URL");
+        std::env::remove_var("OPENALEX_BASE_URL");
         std::env::remove_var("API_TIMEOUT_SECS");
     });
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_pipeline_with_network_failures() {
     let rt = Runtime::new().unwrap();
     
@@ -340,14 +368,28 @@ fn test_pipeline_with_network_failures() {
             .await;
         
         // Mock failing DBLP API calls
+        let _crossref_fail_mock = server
+            .mock("GET", Matcher::Regex(r"^/works".to_string()))
+            .with_status(500)
+            .create_async()
+            .await;
         let _dblp_fail_mock = server
             .mock("GET", "/search/publ/api")
             .with_status(500)
             .create_async()
             .await;
+        let _arxiv_search_fail_mock = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::Any)
+            .with_status(500)
+            .create_async()
+            .await;
         
         std::env::set_var("ARXIV_BASE_URL", &server.url());
+        std::env::set_var("ARXIV_API_BASE_URL", server.url());
         std::env::set_var("DBLP_BASE_URL", &server.url());
+        std::env::set_var("CROSSREF_BASE_URL", server.url());
+        std::env::set_var("OPENALEX_BASE_URL", server.url());
         std::env::set_var("API_TIMEOUT_SECS", "2"); // Short timeout for faster test execution
         
         let paper_ids = vec!["2104.08653".to_string()];
@@ -364,7 +406,10 @@ fn test_pipeline_with_network_failures() {
         
         // Clean up
         std::env::remove_var("ARXIV_BASE_URL");
+        std::env::remove_var("ARXIV_API_BASE_URL");
         std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("CROSSREF_BASE_URL");
+        std::env::remove_var("OPENALEX_BASE_URL");
         std::env::remove_var("API_TIMEOUT_SECS");
     });
 }
@@ -24,6 +24,7 @@ fn test_format_bibliography_as_bibtex() {
         key: "testkey".to_string(),
         entry_type: "article".to_string(),
         fields,
+        ..Default::default()
     };
     bibliography.insert(entry);
 
@@ -0,0 +1,145 @@
+mod common;
+
+use bibextract::latex::openalex::openalex_work_to_entry;
+use bibextract::latex::{MetadataProvider, OpenAlexProvider, Verifier};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use tokio::runtime::Runtime;
+
+use common::entry_with;
+
+#[test]
+fn test_openalex_work_to_entry() {
+    let work = json!({
+        "id": "https://openalex.org/W2194775991",
+        "doi": "https://doi.org/10.1109/cvpr.2016.90",
+        "title": "Deep Residual Learning for Image Recognition",
+        "publication_year": 2016,
+        "type": "article",
+        "authorships": [
+            {"author": {"display_name": "Kaiming He"}},
+            {"author": {"display_name": "Xiangyu Zhang"}}
+        ],
+        "primary_location": {
+            "landing_page_url": "https://doi.org/10.1109/cvpr.2016.90",
+            "source": {"display_name": "2016 IEEE Conference on Computer Vision and Pattern Recognition (CVPR)", "type": "conference"}
+        },
+        "biblio": {"volume": null, "issue": null, "first_page": "770", "last_page": "778"},
+        "open_access": {"is_oa": true, "oa_url": "https://arxiv.org/pdf/1512.03385"}
+    });
+    let entry = openalex_work_to_entry(&work, "he2016").unwrap();
+    assert_eq!(entry.entry_type, "inproceedings");
+    assert_eq!(entry.get("author").unwrap(), "He, Kaiming and Zhang, Xiangyu");
+    assert_eq!(entry.get("year").unwrap(), "2016");
+    assert_eq!(entry.get("booktitle").unwrap(), "2016 IEEE Conference on Computer Vision and Pattern Recognition (CVPR)");
+    assert_eq!(entry.get("pages").unwrap(), "770--778");
+    assert_eq!(entry.get("doi").unwrap(), "10.1109/cvpr.2016.90");
+    assert_eq!(entry.get("url").unwrap(), "https://arxiv.org/pdf/1512.03385");
+    assert!(entry.get("volume").is_none());
+
+    // The Crossref type wins; repositories are not venues
+    let work = json!({
+        "display_name": "A chapter",
+        "type": "article",
+        "type_crossref": "book-chapter",
+        "primary_location": {"landing_page_url": "https://example.org/chapter", "source": {"display_name": "Some Book", "type": "book series"}}
+    });
+    let entry = openalex_work_to_entry(&work, "chapter").unwrap();
+    assert_eq!(entry.entry_type, "incollection");
+    assert_eq!(entry.get("booktitle").unwrap(), "Some Book");
+    assert_eq!(entry.get("url").unwrap(), "https://example.org/chapter");
+
+    let work = json!({
+        "title": "A preprint",
+        "type": "preprint",
+        "primary_location": {"source": {"display_name": "arXiv (Cornell University)", "type": "repository"}}
+    });
+    let entry = openalex_work_to_entry(&work, "preprint").unwrap();
+    assert_eq!(entry.entry_type, "misc");
+    assert!(entry.get("journal").is_none() && entry.get("booktitle").is_none());
+
+    let work = json!({"title": "A thesis", "type": "dissertation", "biblio": {"first_page": "1", "last_page": "1"}});
+    let entry = openalex_work_to_entry(&work, "thesis").unwrap();
+    assert_eq!(entry.entry_type, "phdthesis");
+    assert_eq!(entry.get("pages").unwrap(), "1");
+
+    assert!(openalex_work_to_entry(&json!({"type": "article"}), "untitled").is_none());
+}
+
+#[test]
+fn test_openalex_provider_search() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let search_mock = server
+            .mock("GET", "/works")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("search".into(), "Statistical methods for rates and proportions".into()),
+                Matcher::UrlEncoded("filter".into(), "publication_year:2002-2004".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"meta": {"count": 2}, "results": [
+                {"title": "Statistical Methods for Rates and Proportions", "publication_year": 2003, "type": "book",
+                 "authorships": [{"author": {"display_name": "Someone Else"}}]},
+                {"title": "Statistical Methods for Rates and Proportions", "publication_year": 2003, "type": "book",
+                 "doi": "https://doi.org/10.1002/0471445428",
+                 "authorships": [{"author": {"display_name": "Joseph L. Fleiss"}}, {"author": {"display_name": "Bruce Levin"}}]}
+            ]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        // Rejected requests are not retried
+        let rejected_mock = server
+            .mock("GET", "/works")
+            .match_query(Matcher::UrlEncoded("search".into(), "A rejected query".into()))
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+        std::env::set_var("OPENALEX_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        // The work with the same title by someone else is passed over
+        let mut entry = entry_with(&[
+            ("author", "Fleiss, J. L. and Levin, B."),
+            ("title", "Statistical methods for rates and proportions"),
+            ("year", "2003"),
+        ]);
+        let candidates = OpenAlexProvider.search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].score, 1.0);
+
+        let verifier = Verifier::new().with_provider(OpenAlexProvider);
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.entry_type, "book");
+        assert_eq!(entry.get("doi").unwrap(), "10.1002/0471445428");
+        assert_eq!(entry.get("verified_source").unwrap(), "OpenAlex");
+        search_mock.assert_async().await;
+
+        // Names written with TeX accents are the same people
+        let accents_mock = server
+            .mock("GET", "/works")
+            .match_query(Matcher::UrlEncoded("search".into(), "On the evolution of random graphs".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"meta": {"count": 1}, "results": [
+                {"title": "On the evolution of random graphs", "publication_year": 1960, "type": "article",
+                 "authorships": [{"author": {"display_name": "Paul Erdős"}}]}
+            ]}"#)
+            .create_async()
+            .await;
+        let entry = entry_with(&[("author", "Erd{\\H{o}}s, P."), ("title", "On the evolution of random graphs"), ("year", "1960")]);
+        let candidates = OpenAlexProvider.search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].score, 1.0);
+        accents_mock.assert_async().await;
+
+        let entry = entry_with(&[("title", "A rejected query")]);
+        assert!(OpenAlexProvider.search(&entry).await.unwrap().is_empty());
+        rejected_mock.assert_async().await;
+
+        std::env::remove_var("OPENALEX_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
@@ -1,5 +1,8 @@
-use bibextract::latex::parser::{find_bbl_files, find_main_tex_file, extract_all_latex_from_files, resolve_input_path};
+use bibextract::latex::parser::{find_bbl_files, find_bib_databases, find_document_bbl_files, find_main_tex_file, extract_all_latex_from_files, extract_all_latex_with_spans, parse_document_bbl_files, resolve_input_path};
 use bibextract::error::BibExtractError;
+use bibextract::internal::build_survey;
+use bibextract::latex::citation::extract_sections_from_latex;
+use bibextract::latex::ArxivPaper;
 use std::fs;
 use tempfile::tempdir;
 
@@ -17,6 +20,72 @@ fn test_find_bbl_files() {
     assert!(bbl_files.contains(&dir.path().join("subdir/test3.bbl")));
 }
 
+#[test]
+fn test_find_bib_databases() {
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("paper")).unwrap();
+    fs::create_dir(dir.path().join("bib")).unwrap();
+    let main_tex = dir.path().join("paper/main.tex");
+    fs::write(dir.path().join("paper/refs.bib"), "@misc{a, title={A}}").unwrap();
+    // Latin-1, with an "é"
+    fs::write(dir.path().join("bib/extra.bib"), b"@misc{b, title={B}, author={Ren\xe9 Dupont}}").unwrap();
+    fs::write(dir.path().join("stale.bib"), "@misc{c, title={C}}").unwrap();
+
+    let content = r#"\begin{filecontents*}[overwrite]{inline.bib}
+@misc{d, title={D}}
+\end{filecontents*}
+\documentclass{article}
+\begin{document}
+% \bibliography{stale}
+\bibliography{refs, bib/extra,inline}
+\addbibresource[label=x]{missing.bib}
+\end{document}"#;
+
+    let databases = find_bib_databases(dir.path(), &main_tex, content).unwrap();
+    assert_eq!(databases.len(), 3);
+    assert!(databases[0].contains("@misc{a"));
+    assert!(databases[1].contains("@misc{b"));
+    assert!(databases[1].contains("Dupont"));
+    assert!(databases[2].contains("@misc{d"));
+}
+
+#[test]
+fn test_find_document_bbl_files() {
+    let dir = tempdir().unwrap();
+    for subdir in ["old", "supplement", "chapters"] {
+        fs::create_dir(dir.path().join(subdir)).unwrap();
+    }
+    let main_tex = dir.path().join("main.tex");
+    for bbl in ["main.bbl", "old/main.bbl", "supplement/supp.bbl", "ltr.bbl", "chapters/intro.bbl", "refs.bbl", "draft.bbl"] {
+        fs::File::create(dir.path().join(bbl)).unwrap();
+    }
+    let included = vec![dir.path().join("chapters/intro.tex")];
+    let content = r"\newcites{ltr}{Further reading}
+% \newcites{draft}{Unused}
+\bibliography{bib/refs}";
+
+    let bbl_files = find_document_bbl_files(dir.path(), &main_tex, &included, content).unwrap();
+    assert_eq!(
+        bbl_files,
+        vec![
+            dir.path().join("main.bbl"),
+            dir.path().join("ltr.bbl"),
+            dir.path().join("refs.bbl"),
+            dir.path().join("chapters/intro.bbl"),
+        ]
+    );
+
+    // A .bbl that does not match the renamed main file is still used when it is the only one
+    let renamed = tempdir().unwrap();
+    fs::File::create(renamed.path().join("arxiv.bbl")).unwrap();
+    let bbl_files = find_document_bbl_files(renamed.path(), &renamed.path().join("ms.tex"), &[], "").unwrap();
+    assert_eq!(bbl_files, vec![renamed.path().join("arxiv.bbl")]);
+
+    // Several unmatched files are all used rather than none
+    fs::File::create(renamed.path().join("other.bbl")).unwrap();
+    let bbl_files = find_document_bbl_files(renamed.path(), &renamed.path().join("ms.tex"), &[], "").unwrap();
+    assert_eq!(bbl_files.len(), 2);
+}
 
 #[test]
 fn test_find_main_tex_file() {
@@ -72,4 +141,81 @@ fn test_extract_all_latex_from_files() {
     assert!(content.contains("Main content."));
     assert_eq!(included_files.len(), 1);
     assert_eq!(included_files[0], included_tex_path);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_extract_all_latex_with_spans() {
+    let dir = tempdir().unwrap();
+    let main_tex_path = dir.path().join("main.tex");
+    fs::write(&main_tex_path, "Main. \\include{chapter} End.").unwrap();
+    fs::write(dir.path().join("chapter.tex"), "Chapter \\input{section} done.").unwrap();
+    fs::write(dir.path().join("section.tex"), "Section.").unwrap();
+
+    let (content, included_files) = extract_all_latex_with_spans(dir.path(), &main_tex_path).unwrap();
+    assert_eq!(content, "Main. Chapter Section. done. End.");
+    assert_eq!(included_files.len(), 2);
+    assert_eq!(included_files[0].0, dir.path().join("chapter.tex"));
+    assert_eq!(&content[included_files[0].1.clone()], "Chapter Section. done.");
+    assert_eq!(&content[included_files[1].1.clone()], "Section.");
+}
+
+#[test]
+fn test_parse_document_bbl_files_with_colliding_keys() {
+    let dir = tempdir().unwrap();
+    let bbl = |title: &str| {
+        format!("\\begin{{thebibliography}}{{9}}\n\\bibitem{{smith2020}}\nJohn Smith.\n\\newblock {}.\n\\newblock 2020.\n\\end{{thebibliography}}", title)
+    };
+    fs::write(dir.path().join("main.bbl"), bbl("Deep learning for parsing")).unwrap();
+    fs::write(dir.path().join("ltr.bbl"), bbl("Further reading on parsing")).unwrap();
+    fs::write(dir.path().join("intro.bbl"), bbl("An introduction to parsing")).unwrap();
+    let main_tex_path = dir.path().join("main.tex");
+    fs::write(&main_tex_path, "\\newcites{ltr}{Further reading}\nMain \\cite{smith2020} and \\citeltr{smith2020}.\n\\include{intro}").unwrap();
+    fs::write(dir.path().join("intro.tex"), "Intro \\cite{other, smith2020}.").unwrap();
+
+    let (content, included_files) = extract_all_latex_with_spans(dir.path(), &main_tex_path).unwrap();
+    let bbl_files = ["main.bbl", "ltr.bbl", "intro.bbl"].map(|name| dir.path().join(name));
+    let (bib, content) = parse_document_bbl_files(&bbl_files, &content, &included_files).unwrap();
+
+    // Each file keeps its own smith2020, cited where that file resolves citations
+    assert_eq!(bib.entries.len(), 3);
+    assert_eq!(bib.get("smith2020").unwrap().get("title").unwrap(), "Deep learning for parsing");
+    assert_eq!(bib.get("ltr:smith2020").unwrap().get("title").unwrap(), "Further reading on parsing");
+    assert_eq!(bib.get("intro:smith2020").unwrap().get("title").unwrap(), "An introduction to parsing");
+    assert!(content.contains("Main \\cite{smith2020} and \\citeltr{ltr:smith2020}."));
+    assert!(content.contains("Intro \\cite{other, intro:smith2020}."));
+
+    // Without conflicts the content is left alone
+    let (bib, unchanged) = parse_document_bbl_files(&bbl_files[..1], &content, &included_files).unwrap();
+    assert_eq!(bib.entries.len(), 1);
+    assert_eq!(unchanged, content);
+}
+
+#[test]
+fn test_multibib_citations_reach_sections_and_survey() {
+    let dir = tempdir().unwrap();
+    let bbl = |title: &str| {
+        format!("\\begin{{thebibliography}}{{9}}\n\\bibitem{{smith2020}}\nJohn Smith.\n\\newblock {}.\n\\newblock 2020.\n\\end{{thebibliography}}", title)
+    };
+    fs::write(dir.path().join("main.bbl"), bbl("Deep learning for parsing")).unwrap();
+    fs::write(dir.path().join("ltr.bbl"), bbl("Further reading on parsing")).unwrap();
+    let content = "\\newcites{ltr}{Further reading}\n\\section{Related Work}\nParsing \\cite{smith2020}, see also \\citeltr{smith2020}.\n";
+    let bbl_files = ["main.bbl", "ltr.bbl"].map(|name| dir.path().join(name));
+    let (bibliography, content) = parse_document_bbl_files(&bbl_files, content, &[]).unwrap();
+
+    let sections = extract_sections_from_latex(&content, &bibliography).unwrap();
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].citations, vec!["ltr:smith2020", "smith2020"]);
+
+    let further_reading = bibliography.normalize_citation_key(bibliography.get("ltr:smith2020").unwrap());
+    let paper = ArxivPaper {
+        id: "2001.00001".to_string(),
+        title: "Parsing".to_string(),
+        authors: "Jane Doe".to_string(),
+        sections,
+        bibliography,
+        _temp_dir: dir,
+    };
+    let (survey, bibtex) = build_survey(&[paper]).unwrap();
+    assert!(survey.contains(&format!("\\citeltr{{{}}}", further_reading)), "{}", survey);
+    assert!(bibtex.contains("Further reading on parsing"));
+}
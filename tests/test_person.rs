@@ -0,0 +1,98 @@
+use bibextract::latex::person::latex_to_unicode;
+use bibextract::latex::{Bibliography, Person, PersonList};
+
+fn person(first: &str, von: &str, last: &str, jr: &str) -> Person {
+    Person {
+        first: first.to_string(),
+        von: von.to_string(),
+        last: last.to_string(),
+        jr: jr.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_bibtex_name_forms() {
+    assert_eq!(Person::parse("Donald E. Knuth"), person("Donald E.", "", "Knuth", ""));
+    assert_eq!(Person::parse("Ludwig van Beethoven"), person("Ludwig", "van", "Beethoven", ""));
+    assert_eq!(Person::parse("van Beethoven, Ludwig"), person("Ludwig", "van", "Beethoven", ""));
+    assert_eq!(Person::parse("Ford, Jr., Henry"), person("Henry", "", "Ford", "Jr."));
+    assert_eq!(Person::parse("Jean de la Fontaine"), person("Jean", "de la", "Fontaine", ""));
+    assert_eq!(Person::parse("{Barnes and Noble}"), person("", "", "{Barnes and Noble}", ""));
+    assert_eq!(Person::parse("Plato"), person("", "", "Plato", ""));
+
+    let knuth = Person::parse("Knuth, Donald E.");
+    assert_eq!(knuth.to_bibtex(), "Knuth, Donald E.");
+    assert_eq!(knuth.to_string(), "Donald E. Knuth");
+    assert_eq!(Person::parse("Ford, Jr., Henry").to_bibtex(), "Ford, Jr., Henry");
+    assert_eq!(Person::parse("Ludwig van Beethoven").surname(), "van Beethoven");
+}
+
+#[test]
+fn test_latex_accents() {
+    assert_eq!(latex_to_unicode(r"Erd{\H o}s"), "Erdős");
+    assert_eq!(latex_to_unicode(r#"G{\"o}del and Schr\"{o}dinger"#), "Gödel and Schrödinger");
+    assert_eq!(latex_to_unicode(r"Fran\c{c}ois Ch\'{\i}a \v Cech"), "François Chía Čech");
+    assert_eq!(latex_to_unicode(r"Yu~Gu and Paul Erd\H{o}s and {\O}ystein \ss"), "Yu Gu and Paul Erdős and Øystein ß");
+}
+
+#[test]
+fn test_parse_bibtex_list() {
+    let list = PersonList::parse_bibtex("LeCun, Yann and Yoshua Bengio and others");
+    assert_eq!(list.len(), 2);
+    assert!(list.is_truncated);
+    assert_eq!(list.persons[1], person("Yoshua", "", "Bengio", ""));
+    assert_eq!(list.to_bibtex(), "LeCun, Yann and Bengio, Yoshua and others");
+
+    // "and" inside braces does not separate names
+    let list = PersonList::parse_bibtex("{Barnes and Noble} AND Smith, J.");
+    assert_eq!(list.len(), 2);
+    assert!(!list.is_truncated);
+}
+
+#[test]
+fn test_parse_typeset_lists() {
+    let cases = [
+        // natbib: comma-separated with an Oxford comma
+        ("Daron Acemoglu, Pascual Restrepo, and Yu~Su.", "Acemoglu, Daron and Restrepo, Pascual and Su, Yu", false),
+        ("Daron Acemoglu and Pascual Restrepo.", "Acemoglu, Daron and Restrepo, Pascual", false),
+        // IEEEtran: initials first, emphasized et al.
+        (r"J.~Wei, X.~Wang, F.~Xia \emph{et~al.}", "Wei, J. and Wang, X. and Xia, F. and others", true),
+        // apalike: inverted names with initials
+        ("Baker, B., Akkaya, I., and Clune, J.", "Baker, B. and Akkaya, I. and Clune, J.", false),
+        ("Acemoglu, D. and Restrepo, P.", "Acemoglu, D. and Restrepo, P.", false),
+        // A single inverted name
+        ("Knuth, Donald~E.", "Knuth, Donald E.", false),
+        ("Abhimanyu Dubey, Abhinav Jauhri, et~al.", "Dubey, Abhimanyu and Jauhri, Abhinav and others", true),
+        (r#"Michiel de~Jong and G{\"o}del, K."#, "de Jong, Michiel and Gödel, K.", false),
+    ];
+    for (text, expected, truncated) in cases {
+        let list = PersonList::parse(text);
+        assert_eq!(list.to_bibtex(), expected, "parsing {:?}", text);
+        assert_eq!(list.is_truncated, truncated, "truncation of {:?}", text);
+    }
+    assert!(PersonList::parse("").is_empty());
+}
+
+#[test]
+fn test_names_from_sources() {
+    let bbl = r"\begin{thebibliography}{1}
+\providecommand{\natexlab}[1]{#1}
+
+\bibitem[Erd{\H o}s and R{\'e}nyi(1959)]{erdos1959}
+Paul Erd{\H o}s and Alfr{\'e}d R{\'e}nyi.
+\newblock On random graphs.
+\newblock 1959.
+
+\end{thebibliography}";
+    let bibliography = Bibliography::parse_bbl(bbl).unwrap();
+    let entry = bibliography.get("erdos1959").unwrap();
+    assert_eq!(entry.get("author").unwrap(), "Erdős, Paul and Rényi, Alfréd");
+    assert_eq!(entry.authors().first().unwrap().last, "Erdős");
+    assert_eq!(bibliography.normalize_citation_key(entry), "erdős_random_graphs_1959");
+
+    let bib = Bibliography::parse_bib(r#"@article{k, author = {G{\"o}del, Kurt and von Neumann, John and others}, title = {T}}"#).unwrap();
+    let authors = bib.get("k").unwrap().authors();
+    assert_eq!(authors.persons[1], person("John", "von", "Neumann", ""));
+    assert!(authors.is_truncated);
+    assert_eq!(bib.get("k").unwrap().get("author").unwrap(), "Gödel, Kurt and von Neumann, John and others");
+}
@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use bibextract::error::BibExtractError;
+use bibextract::latex::{
+    match_score, BibEntry, BibEntryBuilder, Candidate, DblpProvider, FieldSource, MergePolicy, MetadataProvider,
+    Verifier,
+};
+use mockito::{Matcher, Server};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A provider answering every search with a fixed record, counting its calls
+struct FixedProvider {
+    name: &'static str,
+    fields: Vec<(&'static str, &'static str)>,
+    score: f32,
+    calls: Arc<AtomicUsize>,
+}
+
+impl FixedProvider {
+    fn new(name: &'static str, fields: Vec<(&'static str, &'static str)>, score: f32) -> Self {
+        Self { name, fields, score, calls: Arc::new(AtomicUsize::new(0)) }
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for FixedProvider {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn search(&self, entry: &BibEntry) -> Result<Vec<Candidate>, BibExtractError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fields.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut verified = BibEntry::builder(entry.key.clone(), "article");
+        for (field, value) in &self.fields {
+            verified = verified.field(*field, *value);
+        }
+        Ok(vec![Candidate::new(verified.build(), self.score, self.name)])
+    }
+}
+
+/// A provider whose lookups always fail
+struct FailingProvider;
+
+#[async_trait]
+impl MetadataProvider for FailingProvider {
+    fn name(&self) -> &str {
+        "failing"
+    }
+
+    async fn lookup_id(&self, _entry: &BibEntry) -> Result<Option<Candidate>, BibExtractError> {
+        Err(BibExtractError::ApiError("service unavailable".to_string()))
+    }
+}
+
+fn entry_from(source: FieldSource) -> BibEntry {
+    let mut entry = BibEntry {
+        key: "smith2020".to_string(),
+        entry_type: "misc".to_string(),
+        fields: HashMap::from([
+            ("author".to_string(), "Smith, John".to_string()),
+            ("title".to_string(), "A title".to_string()),
+        ]),
+        ..Default::default()
+    };
+    entry.mark_source(source);
+    entry
+}
+
+fn bbl_entry() -> BibEntry {
+    entry_from(FieldSource::Bbl)
+}
+
+#[test]
+fn test_default_verifier_providers() {
+    let verifier = Verifier::default();
+    assert_eq!(verifier.provider_names(), vec!["arXiv", "DBLP", "Crossref", "OpenAlex", "arXiv search"]);
+    assert_eq!(verifier.policy(), MergePolicy::FirstMatch);
+    assert!(Verifier::new().provider_names().is_empty());
+}
+
+#[test]
+fn test_first_match_stops_at_priority_order() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let empty = FixedProvider::new("empty", vec![], 1.0);
+        let first = FixedProvider::new("first", vec![("journal", "First Journal")], 0.5);
+        let second = FixedProvider::new("second", vec![("journal", "Second Journal")], 1.0);
+        let (empty_calls, second_calls) = (Arc::clone(&empty.calls), Arc::clone(&second.calls));
+        let verifier = Verifier::new().with_provider(empty).with_provider(first).with_provider(second);
+
+        let mut entry = bbl_entry();
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("journal").unwrap(), "First Journal");
+        assert_eq!(entry.get("verified_source").unwrap(), "first");
+        assert_eq!(entry.source("journal"), Some(FieldSource::Verified));
+        assert_eq!(entry.entry_type, "article");
+        assert_eq!(empty_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 0);
+    });
+}
+
+#[test]
+fn test_set_priority_reorders_providers() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut verifier = Verifier::new()
+            .with_provider(FixedProvider::new("first", vec![("journal", "First Journal")], 1.0))
+            .with_provider(FixedProvider::new("second", vec![("journal", "Second Journal")], 1.0))
+            .with_provider(FixedProvider::new("third", vec![], 1.0));
+        verifier.set_priority(&["SECOND", "unknown"]);
+        assert_eq!(verifier.provider_names(), vec!["second", "first", "third"]);
+
+        let mut entry = bbl_entry();
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("journal").unwrap(), "Second Journal");
+    });
+}
+
+#[test]
+fn test_replace_keeps_default_order() {
+    // As with --offline --dblp-dump --arxiv-snapshot --acl-anthology
+    let mut verifier = Verifier::new();
+    verifier.replace("DBLP", Arc::new(FixedProvider::new("DBLP", vec![], 1.0)));
+    verifier.replace("arXiv", Arc::new(FixedProvider::new("arXiv", vec![], 1.0)));
+    assert_eq!(verifier.provider_names(), vec!["arXiv", "DBLP"]);
+    verifier.register(Arc::new(FixedProvider::new("ACL Anthology", vec![], 1.0)));
+    verifier.set_priority(&["arXiv", "ACL Anthology"]);
+    assert_eq!(verifier.provider_names(), vec!["arXiv", "ACL Anthology", "DBLP"]);
+
+    // Replacing keeps the place; providers outside the default chain go last
+    verifier.replace("dblp", Arc::new(FixedProvider::new("DBLP", vec![], 0.5)));
+    verifier.replace("OpenAlex", Arc::new(FixedProvider::new("OpenAlex", vec![], 1.0)));
+    verifier.replace("local", Arc::new(FixedProvider::new("local", vec![], 1.0)));
+    assert_eq!(verifier.provider_names(), vec!["arXiv", "ACL Anthology", "DBLP", "OpenAlex", "local"]);
+
+    let mut online = Verifier::default();
+    online.replace("DBLP", Arc::new(FixedProvider::new("DBLP", vec![], 1.0)));
+    assert_eq!(online.provider_names(), Verifier::default().provider_names());
+}
+
+#[test]
+fn test_merge_policies() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let providers = || {
+            Verifier::new()
+                .with_provider(FixedProvider::new("low", vec![("journal", "Low Journal")], 0.4))
+                .with_provider(FixedProvider::new("high", vec![("journal", "High Journal"), ("doi", "10.1/x")], 0.9))
+        };
+
+        let mut entry = bbl_entry();
+        assert!(providers().with_policy(MergePolicy::BestScore).verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("journal").unwrap(), "High Journal");
+        assert_eq!(entry.get("verified_source").unwrap(), "high");
+        assert_eq!(entry.get("verification_score").unwrap(), "0.90");
+
+        // The first match wins its fields; later ones only fill the gaps
+        let mut entry = bbl_entry();
+        assert!(providers().with_policy(MergePolicy::FillMissing).verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("journal").unwrap(), "Low Journal");
+        assert_eq!(entry.get("doi").unwrap(), "10.1/x");
+        assert_eq!(entry.get("verified_source").unwrap(), "low, high");
+        assert_eq!(entry.get("verification_score").unwrap(), "0.40");
+
+        // Candidates below the minimum score are ignored
+        let mut entry = bbl_entry();
+        assert!(providers().with_min_score(0.5).verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("journal").unwrap(), "High Journal");
+        let mut entry = bbl_entry();
+        assert!(!providers().with_min_score(0.95).verify_entry(&mut entry).await.unwrap());
+        assert!(entry.get("verified_source").is_none());
+    });
+}
+
+#[test]
+fn test_verifier_keeps_bib_fields_and_skips_failures() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let verifier = Verifier::new()
+            .with_provider(FailingProvider)
+            .with_provider(FixedProvider::new("fixed", vec![("title", "Verified title"), ("year", "2020")], 1.0));
+
+        let mut entry = entry_from(FieldSource::Bib);
+        assert!(verifier.verify_entry(&mut entry).await.unwrap());
+        assert_eq!(entry.get("title").unwrap(), "A title");
+        assert_eq!(entry.get("year").unwrap(), "2020");
+        assert_eq!(entry.entry_type, "misc");
+        assert_eq!(entry.get("verified_source").unwrap(), "fixed");
+    });
+}
+
+#[test]
+fn test_dblp_provider_candidates() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let _dblp_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::UrlEncoded("format".into(), "json".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"result": {"hits": {"@total": "2", "hit": [
+                {"info": {"title": "Unrelated work", "authors": {"author": [{"text": "Jane Roe"}]}, "year": "1999"}},
+                {"info": {"title": "Attention is all you need", "authors": {"author": [{"text": "Ashish Vaswani"}]},
+                          "venue": "NIPS", "year": "2017", "type": "Conference and Workshop Papers"}}
+            ]}}}"#)
+            .create_async()
+            .await;
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let mut entry = bbl_entry();
+        entry.set("title", "Attention is all you need".to_string());
+        entry.set("year", "2017".to_string());
+        let candidates = DblpProvider::new().search(&entry).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].provider, "DBLP");
+        assert_eq!(candidates[0].entry.entry_type, "inproceedings");
+        assert_eq!(candidates[0].entry.get("booktitle").unwrap(), "NIPS");
+        assert!((candidates[0].score - 4.0 / 6.0).abs() < 1e-6);
+        assert!(DblpProvider::new().lookup_id(&entry).await.unwrap().is_none());
+
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
+
+#[test]
+fn test_match_score_uses_title_similarity() {
+    let entry = BibEntryBuilder::new("erdos", "article")
+        .field("author", "Erd{\\H{o}}s, P{\\'a}l and R{\\'e}nyi, Alfr{\\'e}d")
+        .field("title", "On the {Evolution} of Random Graphs")
+        .field("year", "1960")
+        .build();
+    let record = BibEntryBuilder::new("record", "article")
+        .field("author", "Paul Erdős and Alfréd Rényi")
+        .field("title", "On the evolution of random graphs.")
+        .field("year", "1960")
+        .build();
+    assert_eq!(match_score(&entry, &record), 1.0);
+
+    // The same authors in the same year with another title are another paper
+    let other = BibEntryBuilder::new("other", "article")
+        .field("author", "Paul Erdős and Alfréd Rényi")
+        .field("title", "On random matrices")
+        .field("year", "1960")
+        .build();
+    assert_eq!(match_score(&entry, &other), 0.0);
+}
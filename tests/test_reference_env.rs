@@ -0,0 +1,51 @@
+use bibextract::latex::reference_env::to_thebibliography;
+use bibextract::latex::Bibliography;
+
+const AASTEX_REFERENCES: &str = r"\begin{references}
+\reference{hubble1929} Hubble, E. 1929, Proceedings of the National Academy of Science, 15, 168
+\reference{} Zwicky, F. 1933, Helvetica Physica Acta, 6, 110
+\end{references}";
+
+const IOP_HARVARD: &str = r"\begin{harvard}
+\harvarditem[Einstein et al]{Einstein, Podolsky and Rosen}{1935}{epr1935}
+Einstein A, Podolsky B and Rosen N 1935 Can quantum-mechanical description of physical reality be considered complete? {\it Phys. Rev.} {\bf 47} 777
+\harvarditem{Bell}{1964}{bell1964}
+Bell J S 1964 On the Einstein Podolsky Rosen paradox {\it Physics} {\bf 1} 195
+\end{harvard}";
+
+#[test]
+fn test_to_thebibliography() {
+    let converted = to_thebibliography(AASTEX_REFERENCES).unwrap();
+    assert!(converted.starts_with("\\begin{thebibliography}"));
+    assert!(converted.contains("\\bibitem{hubble1929} Hubble"));
+    assert!(converted.contains("\\bibitem{ref2} Zwicky"));
+
+    let converted = to_thebibliography(IOP_HARVARD).unwrap();
+    assert!(converted.contains("\\bibitem[Einstein, Podolsky and Rosen(1935)]{epr1935}"));
+    assert!(converted.contains("\\bibitem[Bell(1964)]{bell1964}"));
+
+    let converted = to_thebibliography("\\begin{thereferences}\n\\item First reference.\n\\item Second.\n\\end{thereferences}").unwrap();
+    assert!(converted.contains("\\bibitem{ref1} First reference."));
+    assert!(converted.contains("\\bibitem{ref2} Second."));
+
+    assert!(to_thebibliography(r"\begin{itemize}\item x\end{itemize}").is_none());
+}
+
+#[test]
+fn test_parse_reference_environments() {
+    let bibliography = Bibliography::parse_bbl(AASTEX_REFERENCES).unwrap();
+    assert_eq!(bibliography.entries.len(), 2);
+    let hubble = bibliography.get("hubble1929").unwrap();
+    assert_eq!(hubble.get("year").unwrap(), "1929");
+    assert!(bibliography.get("ref2").is_some());
+
+    let bibliography = Bibliography::parse_bbl(IOP_HARVARD).unwrap();
+    let epr = bibliography.get("epr1935").unwrap();
+    assert_eq!(epr.get("year").unwrap(), "1935");
+    assert!(epr.get("raw").unwrap().contains("Phys. Rev."));
+
+    let latex = format!("\\section{{Introduction}} \\cite{{bell1964}}\n{}", IOP_HARVARD);
+    let embedded = Bibliography::parse_embedded_bibliography(&latex).unwrap();
+    assert_eq!(embedded.entries.len(), 2);
+    assert!(embedded.get("bell1964").is_some());
+}
@@ -0,0 +1,165 @@
+use bibextract::latex::bbl_style::{GenericStyle, BIBITEM_REGEX};
+use bibextract::latex::{detect_bbl_style, tag_reference, BblStyleParser, Bibliography, ReferenceField};
+use std::fs;
+use std::path::Path;
+
+fn load_bbl_fixture(file_name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(file_name);
+    fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read {}", file_name))
+}
+
+/// The `(key, item)` pairs of a thebibliography `.bbl`
+fn bibitems(content: &str) -> Vec<(String, &str)> {
+    let end = content.find("\\end{thebibliography}").unwrap_or(content.len());
+    let starts: Vec<usize> = BIBITEM_REGEX.find_iter(content).map(|m| m.start()).collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let item = &content[start..starts.get(n + 1).copied().unwrap_or(end)];
+            (BIBITEM_REGEX.captures(item).unwrap()[2].to_string(), item)
+        })
+        .collect()
+}
+
+/// The tagged fields of a reference, with surrounding punctuation and braces removed
+fn tagged(reference: &str) -> [Option<String>; 5] {
+    let tagged = tag_reference(reference);
+    [
+        ReferenceField::Author,
+        ReferenceField::Year,
+        ReferenceField::Title,
+        ReferenceField::Container,
+        ReferenceField::Pages,
+    ]
+    .map(|field| {
+        tagged.text(field, reference).map(|text| {
+            let text = text.replace(['{', '}'], "").replace('~', " ").replace("\\emph", "").replace("\\it ", "");
+            text.trim_matches(|c: char| ".,:;".contains(c) || c.is_whitespace()).to_string()
+        })
+    })
+}
+
+fn expect(author: &str, year: &str, title: &str, container: Option<&str>, pages: Option<&str>) -> [Option<String>; 5] {
+    [Some(author), Some(year), Some(title), container, pages].map(|field| field.map(str::to_string))
+}
+
+#[test]
+fn test_tag_reference_layouts() {
+    // IEEE: quoted title, venue after "in"
+    assert_eq!(
+        tagged(r"A.~Author and B.~Other, ``A quoted title,'' in \emph{Proc. of the Conf. on Things}, vol.~3, 2020, pp. 10--20."),
+        expect("A. Author and B. Other", "2020", "A quoted title", Some("Proc. of the Conf. on Things"), Some("10--20"))
+    );
+    // apalike: parenthesized year after the names
+    assert_eq!(
+        tagged("Acemoglu, D. and Restrepo, P. (2018).\n\\newblock Artificial intelligence, automation, and work.\n\\newblock In {\\em The Economics of Artificial Intelligence}, pages 197--236."),
+        expect("Acemoglu, D. and Restrepo, P", "2018", "Artificial intelligence, automation, and work", Some("The Economics of Artificial Intelligence"), Some("197--236"))
+    );
+    // amsplain: emphasized title, plain journal up to the bold volume
+    assert_eq!(
+        tagged(r"P.~Erdos, \emph{Some remarks on the theory of graphs}, Bull. Amer. Math. Soc. \textbf{53} (1947), 292--294."),
+        expect("P. Erdos", "1947", "Some remarks on the theory of graphs", Some("Bull. Amer. Math. Soc"), Some("292--294"))
+    );
+    // Author-year without \newblock: the year ends the author list
+    assert_eq!(
+        tagged("Smith, J. and Doe, A. 2020. Learning to rank with trees. Journal of Search 5, 3 (2020), 1--10."),
+        expect("Smith, J. and Doe, A", "2020", "Learning to rank with trees", Some("Journal of Search"), Some("1--10"))
+    );
+    // IOP: no punctuation between authors, year and title
+    assert_eq!(
+        tagged(r"Einstein A, Podolsky B and Rosen N 1935 Can quantum-mechanical description of physical reality be considered complete? {\it Phys. Rev.} {\bf 47} 777"),
+        expect("Einstein A, Podolsky B and Rosen N", "1935", "Can quantum-mechanical description of physical reality be considered complete?", Some("Phys. Rev"), None)
+    );
+    // Vancouver: the initial's period ends the author list
+    assert_eq!(
+        tagged("Smith J, Doe A. Title of the article. J Med Res. 2020;5(2):1-10."),
+        expect("Smith J, Doe A", "2020", "Title of the article", Some("J Med Res"), Some("1-10"))
+    );
+    // Chicago: quoted title after the year
+    assert_eq!(
+        tagged("Smith, John, and Jane Doe. 2020. “Quoted Title.” \\emph{Journal of Things} 5 (2): 1--10."),
+        expect("Smith, John, and Jane Doe", "2020", "Quoted Title", Some("Journal of Things"), Some("1--10"))
+    );
+    // LNCS: `In:` before the proceedings, publisher and year at the end
+    assert_eq!(
+        tagged("Smith, J., Doe, A.: Title of the paper. In: Proc. of Something, pp. 1--10. Springer (2020)"),
+        expect("Smith, J., Doe, A", "2020", "Title of the paper", Some("Proc. of Something"), Some("1--10"))
+    );
+}
+
+#[test]
+fn test_tag_reference_edge_cases() {
+    assert!(tag_reference("").spans.is_empty());
+
+    // Identifiers are neither venues nor years
+    let reference = "Jane Doe.\n\\newblock A preprint.\n\\newblock arXiv:2010.12345 [cs].";
+    let tagged = tag_reference(reference);
+    assert!(tagged.span(ReferenceField::Container).is_none());
+    assert!(tagged.span(ReferenceField::Year).is_none());
+    assert!(!tagged.author_guessed);
+
+    // A first line that is not a list of names is still taken as the authors, but flagged
+    let reference = "The Example Consortium of many places.\n\\newblock A report on things.\n\\newblock 2021.";
+    let tagged = tag_reference(reference);
+    assert!(tagged.author_guessed);
+    assert_eq!(tagged.text(ReferenceField::Title, reference), Some("A report on things"));
+    assert_eq!(tagged.text(ReferenceField::Year, reference), Some("2021"));
+
+    // Spans come in reading order
+    let reference = r"A.~Author, ``Title,'' \emph{Journal}, vol.~1, pp. 1--2, 2001.";
+    let fields: Vec<ReferenceField> = tag_reference(reference).spans.iter().map(|span| span.field).collect();
+    assert_eq!(
+        fields,
+        vec![ReferenceField::Author, ReferenceField::Title, ReferenceField::Container, ReferenceField::Pages, ReferenceField::Year]
+    );
+}
+
+/// Every item of the natbib and IEEEtran fixtures, read by the generic parser, gives the same
+/// fields as the parser written for its style
+#[test]
+fn test_generic_style_matches_known_styles() {
+    for fixture in ["2.bbl", "3.bbl"] {
+        let content = load_bbl_fixture(fixture);
+        let style = detect_bbl_style(&content);
+        assert_ne!(style.name(), "generic");
+        let items = bibitems(&content);
+        assert!(!items.is_empty());
+        for (key, item) in items {
+            let generic = GenericStyle.parse_item(&key, item);
+            let expected = style.parse_item(&key, item);
+            for field in ["author", "title", "year", "journal", "booktitle", "volume", "number", "pages", "publisher", "eprint"] {
+                assert_eq!(generic.get(field), expected.get(field), "{} of {} in {}", field, key, fixture);
+            }
+            assert!(generic.warnings.is_empty(), "{} in {}", key, fixture);
+        }
+    }
+}
+
+/// The fields the generic parser reads from the fixture without a known style
+#[test]
+fn test_generic_style_fixture_regression() {
+    let content = load_bbl_fixture("1.bbl");
+    let expected = [
+        ("vpt", "Baker", "Video pretraining (vpt): Learning to act by watching unlabeled online videos", "2022", Some("booktitle")),
+        ("babyai_iclr19", "Chevalier-Boisvert", "BabyAI: First steps towards grounded language learning with a human in the loop", "2019", Some("booktitle")),
+        ("deng2023mind2web", "Deng", "Mind2web: Towards a generalist agent for the web", "2023", None),
+        ("llama3", "Dubey", "The llama 3 herd of models", "2024", Some("journal")),
+        ("minedojo", "Fan", "Minedojo: Building open-ended embodied agents with internet-scale knowledge", "2022", Some("booktitle")),
+        ("minerl", "Guss", "Minerl: A large-scale dataset of minecraft demonstrations", "2019", Some("journal")),
+        ("hu2021loralowrankadaptationlarge", "Hu", "Lora: Low-rank adaptation of large language models", "2021", None),
+        ("innermonologue", "Huang", "Inner monologue: Embodied reasoning through planning with language models", "2022", None),
+    ];
+    let bibliography = Bibliography::parse_bbl(&content).unwrap();
+    assert_eq!(bibliography.entries.len(), expected.len());
+    for (key, surname, title, year, venue) in expected {
+        let entry = bibliography.get(key).unwrap();
+        assert_eq!(entry.authors().persons[0].last, surname, "{}", key);
+        assert_eq!(entry.get("title").unwrap(), title, "{}", key);
+        assert_eq!(entry.get("year").unwrap(), year, "{}", key);
+        for field in ["journal", "booktitle"] {
+            assert_eq!(entry.get(field).is_some(), venue == Some(field), "{} of {}", field, key);
+        }
+        assert!(entry.warnings.is_empty(), "{}", key);
+    }
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use bibextract::latex::semantic_scholar::{external_ids, semantic_scholar_paper_to_entry};
+use bibextract::latex::{MetadataProvider, SemanticScholarProvider, Verifier};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+use common::{entry_with, keyed_entry_with};
+
+#[test]
+fn test_semantic_scholar_paper_ids() {
+    let entry = entry_with(&[("raw", "A. Author. Title. arXiv:2104.08653, doi:10.18653/v1/2021.acl-long.1")]);
+    assert_eq!(
+        SemanticScholarProvider::paper_ids(&entry),
+        vec!["ARXIV:2104.08653", "DOI:10.18653/v1/2021.acl-long.1"]
+    );
+    let entry = entry_with(&[("url", "https://api.semanticscholar.org/CorpusID:13756489")]);
+    assert_eq!(SemanticScholarProvider::paper_ids(&entry), vec!["CorpusId:13756489"]);
+    assert!(SemanticScholarProvider::paper_ids(&entry_with(&[("title", "No IDs")])).is_empty());
+}
+
+#[test]
+fn test_semantic_scholar_paper_to_entry() {
+    let paper = json!({
+        "paperId": "204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        "title": "Attention is All you Need",
+        "authors": [{"authorId": "40348417", "name": "Ashish Vaswani"}, {"authorId": "1846258", "name": "Noam M. Shazeer"}],
+        "year": 2017,
+        "venue": "Neural Information Processing Systems",
+        "journal": {"name": "ArXiv", "volume": "abs/1706.03762"},
+        "publicationTypes": ["JournalArticle", "Conference"],
+        "externalIds": {"DBLP": "journals/corr/VaswaniSPUJGKP17", "ArXiv": "1706.03762", "CorpusId": 13756489},
+        "url": "https://www.semanticscholar.org/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776"
+    });
+    let ids = external_ids(&paper);
+    assert_eq!(ids.get("ArXiv").unwrap(), "1706.03762");
+    assert_eq!(ids.get("CorpusId").unwrap(), "13756489");
+    assert_eq!(ids.get("DBLP").unwrap(), "journals/corr/VaswaniSPUJGKP17");
+
+    let entry = semantic_scholar_paper_to_entry(&paper, "vaswani").unwrap();
+    assert_eq!(entry.entry_type, "article");
+    assert_eq!(entry.get("author").unwrap(), "Vaswani, Ashish and Shazeer, Noam M.");
+    assert_eq!(entry.get("year").unwrap(), "2017");
+    // The ArXiv pseudo-journal gives way to the venue
+    assert_eq!(entry.get("journal").unwrap(), "Neural Information Processing Systems");
+    assert_eq!(entry.get("eprint").unwrap(), "1706.03762");
+    assert_eq!(entry.get("archivePrefix").unwrap(), "arXiv");
+    assert!(entry.get("doi").is_none());
+    assert_eq!(entry.get("dblp").unwrap(), "journals/corr/VaswaniSPUJGKP17");
+    assert_eq!(entry.get("corpusid").unwrap(), "13756489");
+    assert!(entry.get("url").unwrap().starts_with("https://www.semanticscholar.org/"));
+
+    let paper = json!({
+        "title": "BERT: Pre-training of Deep Bidirectional Transformers for Language Understanding",
+        "year": 2019,
+        "venue": "North American Chapter of the Association for Computational Linguistics",
+        "journal": {"pages": "4171-4186"},
+        "publicationTypes": ["Conference"],
+        "externalIds": {"ACL": "N19-1423", "DOI": "10.18653/v1/N19-1423"}
+    });
+    let entry = semantic_scholar_paper_to_entry(&paper, "bert").unwrap();
+    assert_eq!(entry.entry_type, "inproceedings");
+    assert_eq!(entry.get("booktitle").unwrap(), "North American Chapter of the Association for Computational Linguistics");
+    assert_eq!(entry.get("pages").unwrap(), "4171--4186");
+    assert_eq!(entry.get("doi").unwrap(), "10.18653/v1/N19-1423");
+    assert_eq!(entry.get("url").unwrap(), "https://aclanthology.org/N19-1423");
+    assert_eq!(entry.get("acl").unwrap(), "N19-1423");
+
+    assert!(semantic_scholar_paper_to_entry(&json!({"title": null}), "untitled").is_none());
+}
+
+#[test]
+fn test_semantic_scholar_provider() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let batch_mock = server
+            .mock("POST", "/graph/v1/paper/batch")
+            .match_query(Matcher::Regex("fields=".to_string()))
+            .match_header("x-api-key", "secret")
+            .match_body(Matcher::Json(json!({"ids": ["ARXIV:1706.03762", "DOI:10.1000/unknown"]})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"title": "Attention is All you Need", "year": 2017, "publicationTypes": ["Conference"],
+                            "venue": "NIPS", "externalIds": {"ArXiv": "1706.03762"}}, null]"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let match_mock = server
+            .mock("GET", "/graph/v1/paper/search/match")
+            .match_query(Matcher::UrlEncoded("query".into(), "Deep residual learning for image recognition".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": [{"title": "Deep Residual Learning for Image Recognition", "year": 2016,
+                                     "authors": [{"name": "Kaiming He"}], "publicationTypes": ["Conference"],
+                                     "venue": "CVPR", "externalIds": {"DOI": "10.1109/CVPR.2016.90"}, "matchScore": 180.5}]}"#)
+            .create_async()
+            .await;
+        let _no_match_mock = server
+            .mock("GET", "/graph/v1/paper/search/match")
+            .match_query(Matcher::UrlEncoded("query".into(), "Unknown".into()))
+            .with_status(404)
+            .with_body(r#"{"error": "Title match not found"}"#)
+            .create_async()
+            .await;
+        std::env::set_var("SEMANTIC_SCHOLAR_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let provider = SemanticScholarProvider::new().with_api_key("secret").with_rate_limit(Duration::from_millis(200));
+        let attention = keyed_entry_with("vaswani", &[("title", "Attention is all you need"), ("eprint", "1706.03762")]);
+        let unknown = keyed_entry_with("unknown", &[("doi", "10.1000/unknown")]);
+
+        // One batch request for the whole bibliography; lookups are then answered from it
+        provider.prefetch(&[attention.clone(), unknown.clone()]).await.unwrap();
+        let candidate = provider.lookup_id(&attention).await.unwrap().unwrap();
+        assert_eq!(candidate.score, 1.0);
+        assert_eq!(candidate.entry.get("booktitle").unwrap(), "NIPS");
+        assert!(provider.lookup_id(&unknown).await.unwrap().is_none());
+        batch_mock.assert_async().await;
+
+        // Title match, with requests spaced by the rate limit
+        let mut resnet = keyed_entry_with("he2016", &[
+            ("author", "He, Kaiming"),
+            ("title", "Deep residual learning for image recognition"),
+            ("year", "2016"),
+        ]);
+        let start = Instant::now();
+        let verifier = Verifier::new().with_provider(provider);
+        assert!(verifier.verify_entry(&mut resnet).await.unwrap());
+        assert_eq!(resnet.entry_type, "inproceedings");
+        assert_eq!(resnet.get("doi").unwrap(), "10.1109/CVPR.2016.90");
+        assert_eq!(resnet.get("verified_source").unwrap(), "Semantic Scholar");
+        let mut unmatched = keyed_entry_with("unknown", &[("title", "Unknown")]);
+        assert!(!verifier.verify_entry(&mut unmatched).await.unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        match_mock.assert_async().await;
+
+        // After a failed batch request, lookups fall through to the title search
+        std::env::set_var("API_TIMEOUT_SECS", "1");
+        let failed_batch_mock = server
+            .mock("POST", "/graph/v1/paper/batch")
+            .match_query(Matcher::Any)
+            .match_body(Matcher::Json(json!({"ids": ["DOI:10.1000/failing"]})))
+            .with_status(500)
+            .expect_at_least(1)
+            .create_async()
+            .await;
+        let provider = SemanticScholarProvider::new().with_rate_limit(Duration::from_millis(10));
+        let failing = keyed_entry_with("failing", &[("doi", "10.1000/failing")]);
+        provider.prefetch(std::slice::from_ref(&failing)).await.unwrap();
+        failed_batch_mock.assert_async().await;
+        failed_batch_mock.remove_async().await;
+        let resent_batch_mock = server
+            .mock("POST", "/graph/v1/paper/batch")
+            .match_query(Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+        assert!(provider.lookup_id(&failing).await.unwrap().is_none());
+        resent_batch_mock.assert_async().await;
+
+        std::env::remove_var("SEMANTIC_SCHOLAR_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
+
+#[test]
+fn test_default_verifier_with_semantic_scholar_key() {
+    assert_eq!(
+        Verifier::with_default_providers(Some("secret".to_string())).provider_names(),
+        vec!["arXiv", "DBLP", "Semantic Scholar", "Crossref", "OpenAlex", "arXiv search"]
+    );
+    assert_eq!(
+        Verifier::with_default_providers(Some(String::new())).provider_names(),
+        vec!["arXiv", "DBLP", "Crossref", "OpenAlex", "arXiv search"]
+    );
+}
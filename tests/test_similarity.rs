@@ -0,0 +1,52 @@
+use bibextract::latex::similarity::{
+    author_similarity, fold_text, levenshtein, levenshtein_ratio, title_similarity, token_set_ratio,
+};
+use bibextract::latex::person::fold_accents;
+use bibextract::latex::Person;
+
+#[test]
+fn test_fold_text() {
+    assert_eq!(fold_text("G{\\\"o}del's {Theorem}"), "godel s theorem");
+    assert_eq!(fold_text("Learning $\\alpha$-Divergences"), "learning divergences");
+    assert_eq!(fold_text("Ł\u{f3}d\u{17a}: Stra\u{df}e"), "lodz strasse");
+    assert_eq!(fold_accents("Erd\u{151}s and Ç"), "Erdos and C");
+}
+
+#[test]
+fn test_levenshtein() {
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("", "abc"), 3);
+    assert_eq!(levenshtein("naïve", "naive"), 1);
+    assert_eq!(levenshtein_ratio("", ""), 1.0);
+    assert_eq!(levenshtein_ratio("abcd", "abce"), 0.75);
+}
+
+#[test]
+fn test_token_set_ratio() {
+    assert_eq!(token_set_ratio("deep learning", "learning deep deep"), 1.0);
+    assert_eq!(token_set_ratio("", "anything"), 0.0);
+    // Missing words count against the match
+    assert!(token_set_ratio("attention is all you need", "attention is not all you need for q a") < 0.8);
+}
+
+#[test]
+fn test_title_similarity() {
+    assert_eq!(
+        title_similarity("{BERT}: Pre-training of Deep Bidirectional Transformers", "BERT: pre-training of deep bidirectional transformers."),
+        1.0
+    );
+    assert!(title_similarity("Learning $\\alpha$-divergences", "Learning α-Divergences") > 0.9);
+    assert_eq!(title_similarity("Na\\\"{\\i}ve Bayes", "Naive Bayes"), 1.0);
+    assert!(title_similarity("Neural machine translation by jointly learning to align and translate", "Neural machine translation by jointly learning to align and to translate") > 0.9);
+    assert!(title_similarity("Quantum Computing", "Completely Different Topic") < 0.5);
+}
+
+#[test]
+fn test_author_similarity() {
+    let persons = |names: &[&str]| -> Vec<Person> { names.iter().map(|name| Person::parse(name)).collect() };
+    assert_eq!(author_similarity(&persons(&["Smith, Alice"]), &persons(&["Alice Smith", "Bob Jones"])), 1.0);
+    assert_eq!(author_similarity(&persons(&["M{\\\"u}ller, K."]), &persons(&["Klaus Müller"])), 1.0);
+    assert_eq!(author_similarity(&persons(&["A. Smith", "B. Jones"]), &persons(&["Alice Smith", "Carol Brown"])), 0.5);
+    assert_eq!(author_similarity(&persons(&["A. Smith", "others"]), &persons(&["Alice Smith", "Carol Brown"])), 1.0);
+    assert_eq!(author_similarity(&[], &persons(&["Alice Smith"])), 0.0);
+}
@@ -0,0 +1,122 @@
+use bibextract::latex::{ArxivPaper, BibEntry, Bibliography, CitationStats, ExtractedSection};
+
+fn entry(key: &str, author: &str, title: &str, year: &str) -> BibEntry {
+    BibEntry::builder(key, "article")
+        .field("author", author)
+        .field("title", title)
+        .field("year", year)
+        .build()
+}
+
+fn make_paper(id: &str, sections: Vec<(&str, Vec<&str>)>, entries: Vec<BibEntry>) -> ArxivPaper {
+    let mut bibliography = Bibliography::new();
+    for entry in entries {
+        bibliography.insert(entry);
+    }
+    ArxivPaper {
+        id: id.to_string(),
+        title: format!("Paper {}", id),
+        authors: "Test Author".to_string(),
+        sections: sections
+            .into_iter()
+            .map(|(title, citations)| ExtractedSection {
+                title: title.to_string(),
+                content: String::new(),
+                citations: citations.into_iter().map(String::from).collect(),
+            })
+            .collect(),
+        bibliography,
+        _temp_dir: tempfile::TempDir::new().unwrap(),
+    }
+}
+
+fn sample_papers() -> Vec<ArxivPaper> {
+    // Both papers cite the transformer and BERT papers under different keys
+    let paper_a = make_paper(
+        "2101.00001",
+        vec![
+            ("Related Work", vec!["vaswani", "devlin", "lecun"]),
+            ("Background", vec!["vaswani"]),
+        ],
+        vec![
+            entry("vaswani", "Ashish Vaswani", "Attention is all you need", "2017"),
+            entry("devlin", "Jacob Devlin", "BERT pre-training of deep bidirectional transformers", "2019"),
+            entry("lecun", "Yann LeCun", "Deep learning", "2015"),
+        ],
+    );
+    let paper_b = make_paper(
+        "2101.00002",
+        vec![("Related Work", vec!["attn", "bert"])],
+        vec![
+            entry("attn", "Ashish Vaswani", "Attention is all you need", "2017"),
+            entry("bert", "Jacob Devlin", "BERT pre-training of deep bidirectional transformers", "2019"),
+            entry("gpt3", "Tom Brown", "Language models are few-shot learners", "2020"),
+        ],
+    );
+    vec![paper_a, paper_b]
+}
+
+#[test]
+fn test_entry_counts() {
+    let stats = CitationStats::from_papers(&sample_papers());
+    assert_eq!(stats.paper_count, 2);
+
+    // The transformer paper is cited by both papers and in three sections overall
+    let top = &stats.entries[0];
+    assert_eq!(top.key, "vaswani_attention_need_2017");
+    assert_eq!(top.paper_count(), 2);
+    assert_eq!(top.section_count, 3);
+
+    let lecun = stats.entries.iter().find(|e| e.key == "lecun_deep_learning_2015").unwrap();
+    assert_eq!(lecun.papers, vec!["2101.00001"]);
+
+    // gpt3 is in a bibliography but never cited in an extracted section
+    assert!(!stats.entries.iter().any(|e| e.key.starts_with("brown")));
+}
+
+#[test]
+fn test_co_citations_and_overlap() {
+    let stats = CitationStats::from_papers(&sample_papers());
+
+    let top_pair = &stats.co_citations[0];
+    assert_eq!(top_pair.count, 2);
+    assert_eq!(top_pair.first, "devlin_bert_training_deep_2019");
+    assert_eq!(top_pair.second, "vaswani_attention_need_2017");
+
+    assert_eq!(stats.overlaps.len(), 1);
+    let overlap = &stats.overlaps[0];
+    assert_eq!(overlap.shared, 2);
+    assert!((overlap.jaccard - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_year_histogram() {
+    let stats = CitationStats::from_papers(&sample_papers());
+    assert_eq!(stats.year_histogram.get("2017"), Some(&1));
+    assert_eq!(stats.year_histogram.get("2015"), Some(&1));
+    assert_eq!(stats.year_histogram.get("2020"), Some(&1));
+    assert_eq!(stats.year_histogram.values().sum::<usize>(), 4);
+}
+
+#[test]
+fn test_stats_json_and_table() {
+    let stats = CitationStats::from_papers(&sample_papers());
+
+    let json = stats.to_json();
+    assert_eq!(json["paper_count"], 2);
+    assert_eq!(json["entries"][0]["paper_count"], 2);
+    assert_eq!(json["year_histogram"]["2019"], 1);
+
+    let table = stats.to_table(10);
+    assert!(table.contains("Most cited entries across 2 papers"));
+    assert!(table.contains("vaswani_attention_need_2017"));
+    assert!(table.contains("0.500"));
+}
+
+#[test]
+fn test_stats_empty() {
+    let stats = CitationStats::from_papers(&[]);
+    assert!(stats.entries.is_empty());
+    assert!(stats.co_citations.is_empty());
+    assert!(stats.overlaps.is_empty());
+}
@@ -1,6 +1,12 @@
-use bibextract::latex::{BibEntry, Bibliography};
+mod common;
+
+use bibextract::latex::{BibEntry, Bibliography, FieldSource, MatchThresholds};
+use mockito::{Matcher, Server};
 use std::collections::HashMap;
 use serde_json::json;
+use tokio::runtime::Runtime;
+
+use common::entry_with;
 
 #[test]
 fn test_verify_from_dblp_successful_match() {
@@ -13,6 +19,7 @@ fn test_verify_from_dblp_successful_match() {
             ("title".to_string(), "Machine Learning Advances".to_string()),
             ("year".to_string(), "2023".to_string()),
         ]),
+        ..Default::default()
     };
 
     // Test the scoring logic without actual API calls
@@ -56,6 +63,7 @@ fn test_find_best_match_title_substring() {
             ("title".to_string(), "Deep Learning for Natural Language Processing".to_string()),
             ("year".to_string(), "2023".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -92,6 +100,7 @@ fn test_find_best_match_low_score_threshold() {
             ("title".to_string(), "Quantum Computing".to_string()),
             ("year".to_string(), "2022".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -161,6 +170,7 @@ fn test_find_best_match_multiple_hits_best_score() {
             ("title".to_string(), "Artificial Intelligence Applications".to_string()),
             ("year".to_string(), "2023".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -226,6 +236,7 @@ fn test_find_best_match_author_word_matching() {
             ("title".to_string(), "Research Study".to_string()),
             ("year".to_string(), "2023".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -250,8 +261,9 @@ fn test_find_best_match_author_word_matching() {
         }
     });
 
+    // The same authors and year do not make up for another title
     let best_match = bib.find_best_match_in_dblp(&dblp_results, &entry);
-    assert!(best_match.is_some());
+    assert!(best_match.is_none());
 }
 
 #[test]
@@ -304,6 +316,7 @@ fn test_find_best_match_in_dblp() {
             ("title".to_string(), "A Great Paper on Science".to_string()),
             ("year".to_string(), "2024".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -346,6 +359,7 @@ fn test_find_best_match_in_dblp_no_match() {
             ("title".to_string(), "A Completely Different Paper".to_string()),
             ("year".to_string(), "2023".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -387,6 +401,7 @@ fn test_find_best_match_partial_title_match() {
             ("title".to_string(), "Machine Learning in Computer Vision".to_string()),
             ("year".to_string(), "2024".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -423,6 +438,7 @@ fn test_find_best_match_year_mismatch() {
             ("title".to_string(), "A Paper".to_string()),
             ("year".to_string(), "2020".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -458,6 +474,7 @@ fn test_find_best_match_empty_results() {
             ("title".to_string(), "Some Title".to_string()),
             ("year".to_string(), "2024".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -499,6 +516,7 @@ fn test_find_best_match_author_scoring() {
             ("title".to_string(), "Research Paper".to_string()),
             ("year".to_string(), "2024".to_string()),
         ]),
+        ..Default::default()
     };
 
     let dblp_results = json!({
@@ -533,4 +551,177 @@ fn test_find_best_match_author_scoring() {
     assert_eq!(entry.get("author"), Some(&"Alice Smith and Bob Johnson and Carol Williams and David Brown and Eve Davis".to_string()));
     assert_eq!(entry.get("title"), Some(&"Research Paper".to_string()));
     assert_eq!(entry.get("year"), Some(&"2024".to_string()));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_verified_type_overrides_inferred_type() {
+    let rt = Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let mut server = Server::new_async().await;
+        let _dblp_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::UrlEncoded("format".into(), "json".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"result": {"hits": {"@total": "1", "hit": [{"info": {
+                "title": "Attention is all you need",
+                "authors": {"author": [{"text": "Ashish Vaswani"}, {"text": "Noam Shazeer"}]},
+                "venue": "NIPS",
+                "year": "2017",
+                "type": "Conference and Workshop Papers"
+            }}]}}}"#)
+            .create_async()
+            .await;
+        std::env::set_var("DBLP_BASE_URL", server.url());
+        std::env::set_var("API_TIMEOUT_SECS", "5");
+
+        let bib = Bibliography::new();
+        let fields = HashMap::from([
+            ("author".to_string(), "Vaswani, Ashish and Shazeer, Noam".to_string()),
+            ("title".to_string(), "Attention is all you need".to_string()),
+            ("year".to_string(), "2017".to_string()),
+        ]);
+
+        // Inferred from the .bbl item: replaced by the DBLP publication type
+        let mut inferred = BibEntry {
+            key: "vaswani".to_string(),
+            entry_type: "misc".to_string(),
+            fields: fields.clone(),
+            ..Default::default()
+        };
+        inferred.mark_source(FieldSource::Bbl);
+        assert!(bib.verify_entry(&mut inferred).await.unwrap());
+        assert_eq!(inferred.entry_type, "inproceedings");
+        assert_eq!(inferred.type_source, Some(FieldSource::Verified));
+        assert_eq!(inferred.get("booktitle").unwrap(), "NIPS");
+
+        // Declared in the author's .bib database: kept
+        let mut declared = BibEntry {
+            key: "vaswani".to_string(),
+            entry_type: "article".to_string(),
+            fields,
+            ..Default::default()
+        };
+        declared.mark_source(FieldSource::Bib);
+        assert!(bib.verify_entry(&mut declared).await.unwrap());
+        assert_eq!(declared.entry_type, "article");
+        assert_eq!(declared.type_source, Some(FieldSource::Bib));
+
+        std::env::remove_var("DBLP_BASE_URL");
+        std::env::remove_var("API_TIMEOUT_SECS");
+    });
+}
+
+fn dblp_hit(title: &str, year: &str, authors: &[&str]) -> serde_json::Value {
+    json!({
+        "result": {
+            "hits": {
+                "@total": "1",
+                "hit": [{
+                    "info": {
+                        "authors": {"author": authors.iter().map(|name| json!({"text": name})).collect::<Vec<_>>()},
+                        "title": title,
+                        "year": year
+                    }
+                }]
+            }
+        }
+    })
+}
+
+#[test]
+fn test_find_best_match_single_author() {
+    let bib = Bibliography::new();
+    let entry = entry_with(&[
+        ("author", "Hochreiter, Sepp"),
+        ("title", "Untersuchungen zu dynamischen neuronalen Netzen"),
+        ("year", "1991"),
+    ]);
+    let results = dblp_hit("Untersuchungen zu dynamischen neuronalen Netzen.", "1991", &["Sepp Hochreiter 0001"]);
+    let matches = bib.best_matches_in_dblp(&results, &entry);
+    assert_eq!(matches.len(), 1);
+    assert!((matches[0].0 - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_find_best_match_without_year_or_author() {
+    let bib = Bibliography::new();
+    let results = dblp_hit("Attention is All you Need", "2017", &["Ashish Vaswani"]);
+
+    let entry = entry_with(&[("author", "Vaswani, A."), ("title", "Attention is all you need")]);
+    let matches = bib.best_matches_in_dblp(&results, &entry);
+    assert!((matches[0].0 - 5.0 / 6.0).abs() < 1e-6);
+
+    let entry = entry_with(&[("title", "Attention is all you need")]);
+    let matches = bib.best_matches_in_dblp(&results, &entry);
+    assert!((matches[0].0 - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_find_best_match_folds_markup_accents_and_math() {
+    let bib = Bibliography::new();
+    let entry = entry_with(&[
+        ("author", "Erd{\\H{o}}s, P{\\'a}l and R{\\'e}nyi, Alfr{\\'e}d"),
+        ("title", "On the Evolution of Random Graphs in $\\mathbb{R}^n$"),
+        ("year", "1960"),
+    ]);
+    let results = dblp_hit("On the evolution of random graphs in R^n.", "1960", &["Paul Erdős", "Alfréd Rényi"]);
+    let matches = bib.best_matches_in_dblp(&results, &entry);
+    assert_eq!(matches.len(), 1);
+    assert!(matches[0].0 > 0.9);
+}
+
+#[test]
+fn test_find_best_match_thresholds() {
+    let bib = Bibliography::new();
+    let entry = entry_with(&[("author", "Doe, John"), ("title", "Graph neural networks for molecules"), ("year", "2021")]);
+    let results = dblp_hit("Graph neural networks for molecule generation", "2020", &["Jane Roe"]);
+
+    // Similar titles by someone else, another year: not enough by default
+    assert!(bib.best_matches_in_dblp(&results, &entry).is_empty());
+    let lenient = MatchThresholds::default().with_min_title_similarity(0.5).with_min_score(0.2);
+    let matches = bib.best_matches_in_dblp_with(&results, &entry, &lenient);
+    assert_eq!(matches.len(), 1);
+    assert!(matches[0].0 < 0.5);
+
+    let exact = dblp_hit("Graph Neural Networks for Molecules", "2021", &["John Doe"]);
+    let strict = MatchThresholds::default().with_min_score(0.99);
+    assert_eq!(bib.best_matches_in_dblp_with(&exact, &entry, &strict).len(), 1);
+}
+
+#[test]
+fn test_find_best_match_same_authors_and_year_different_title() {
+    let bib = Bibliography::new();
+    let entry = entry_with(&[("author", "Doe, John"), ("title", "Graph neural networks for molecules"), ("year", "2021")]);
+    let results = dblp_hit("Reinforcement learning for legged robots", "2021", &["John Doe"]);
+    assert!(bib.best_matches_in_dblp(&results, &entry).is_empty());
+    assert!(bib.find_best_match_in_dblp(&results, &entry).is_none());
+
+    // Not even with a lenient minimum score
+    let lenient = MatchThresholds::default().with_min_score(0.1);
+    assert!(bib.best_matches_in_dblp_with(&results, &entry, &lenient).is_empty());
+}
+
+#[test]
+fn test_find_best_match_skips_malformed_hits() {
+    let bib = Bibliography::new();
+    let entry = entry_with(&[("author", "Vaswani, A."), ("title", "Attention is all you need"), ("year", "2017")]);
+    let results = json!({
+        "result": {
+            "hits": {
+                "@total": "3",
+                "hit": [
+                    { "score": "1" },
+                    { "info": { "year": "2017" } },
+                    { "info": { "title": "Attention is All you Need.", "authors": { "author": [{ "text": "Ashish Vaswani" }] } } }
+                ]
+            }
+        }
+    });
+
+    // The hit without a year is still matched, without the year's point
+    let matches = bib.best_matches_in_dblp(&results, &entry);
+    assert_eq!(matches.len(), 1);
+    assert!((matches[0].0 - 5.0 / 6.0).abs() < 1e-6);
+}